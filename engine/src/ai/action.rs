@@ -1,12 +1,14 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use rltk::{DistanceAlg, Point};
 use shipyard::{AllStorages, UniqueView, View, EntityId, Get, IntoIter, IntoWithId};
 
-use crate::{world::{components::{Inventory, Item, ItemType, Position, SpatialKnowledge, Turn}, map::Map}, tiles::TileType, utils::Target};
+use crate::{world::{components::{Actor, ConstructionSite, DealsDamage, Inventory, Item, ItemType, Mana, Needs, PhysicalStats, Position, Ranged, SpatialKnowledge, Spell, Spellbook, Stockpile, Turn, Vision}, map::Map, systems::{system_construction, system_spells}}, tiles::TileType, utils::Target};
+use crate::raws::factions::{FactionTable, Relation};
 use crate::ai::intent::IntentArchetype;
 use super::{consideration::Consideration, input::{InputTargets, InputType}, intent::{Intent, Task}};
 
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Action {
     pub cons: Vec<Consideration>,
     pub priority: f32,
@@ -18,8 +20,15 @@ impl Action {
         let vpos = store.borrow::<View<Position>>().unwrap();
         let vinv = store.borrow::<View<Inventory>>().unwrap();
         let vitem = store.borrow::<View<Item>>().unwrap();
+        let vneeds = store.borrow::<View<Needs>>().unwrap();
+        let vsite = store.borrow::<View<ConstructionSite>>().unwrap();
+        let vstats = store.borrow::<View<PhysicalStats>>().unwrap();
+        let vmana = store.borrow::<View<Mana>>().unwrap();
+        let vspell = store.borrow::<View<Spell>>().unwrap();
+        let vdamage = store.borrow::<View<DealsDamage>>().unwrap();
 
         let map = store.borrow::<UniqueView<Map>>().unwrap();
+        let stockpile = store.borrow::<UniqueView<Stockpile>>().unwrap();
 
         // select targets for each intent
         let intents = self.expand_intent_archetype(store, id);
@@ -31,9 +40,16 @@ impl Action {
         let mut best = (0.0, intents[0].clone());
 
         for intent in intents {
+            // casting intents carry the spell after the victim
+            let spell = match intent.target.get(1) {
+                Some(Target::ENTITY(e)) if vspell.get(*e).is_ok() => Some(*e),
+                _ => None,
+            };
+            let blast = spell.map_or((0, 0), |spell| system_spells::blast(store, intent.owner, spell, intent.target[0].get_point(&vpos)));
 
             // get average of all consideration scores
             let mut scores: Vec<f32> = vec![];
+            let mut ruled_out = false;
             for c in self.cons.iter() {
 
                 // calculate input from intent
@@ -61,21 +77,43 @@ impl Action {
                             InputTargets::Fish => {
                                 inv.count_type(&vitem, ItemType::Fish) as f32
                             },
+                            InputTargets::Grain => {
+                                inv.count_type(&vitem, ItemType::Grain) as f32
+                            },
+                            InputTargets::Dirt => {
+                                inv.count_type(&vitem, ItemType::Dirt) as f32
+                            },
                             _ => todo!()
                         }
                     },
+                    // targets that aren't items are never stockpiled
+                    InputType::StockpileCount(target) => target.item_type().map_or(0., |typ| stockpile.count(typ) as f32),
+                    InputType::Hunger => vneeds.get(intent.owner).map_or(0., |n| n.hunger as f32),
+                    InputType::Fatigue => vneeds.get(intent.owner).map_or(0., |n| n.fatigue as f32),
+                    InputType::Shelter => vneeds.get(intent.owner).map_or(0., |n| n.shelter as f32),
+                    InputType::ConstructionDemand => system_construction::logs_wanted(&vsite, &vinv) as f32,
+                    InputType::Threat => visible_enemies(store, intent.owner).len() as f32,
+                    InputType::Health => vstats.get(intent.owner).map_or(1., |s| s.hp as f32 / s.max_hp as f32),
+                    InputType::Mana => vmana.get(intent.owner).map_or(0., |m| m.current as f32 / i32::max(m.max, 1) as f32),
+                    InputType::SpellCost => match (spell.and_then(|s| vspell.get(s).ok()), vmana.get(intent.owner)) {
+                        (Some(s), Ok(m)) => s.mana_cost as f32 / i32::max(m.max, 1) as f32,
+                        _ => 1.,
+                    },
+                    InputType::SpellDamage => spell.and_then(|s| vdamage.get(s).ok()).map_or(0., |d| d.damage as f32),
+                    InputType::SpellHits => blast.0 as f32,
+                    InputType::FriendlyFire => blast.1 as f32,
                 };
                 
                 let s = c.get_score(input);
     
-                // if s == 0. {
-                //     return (0.0);
-                // }
+                if c.required && s <= 0. {
+                    ruled_out = true;
+                }
     
                 scores.push(s);
             }
 
-            let score = average(&scores) * self.priority;
+            let score = if ruled_out { 0. } else { average(&scores) * self.priority };
 
             if score > best.0 {
                 best = (score, intent.clone());
@@ -103,7 +141,7 @@ impl Action {
             Task::Fish => { 
                 let space = vspace.get(id).unwrap();
 
-                for target in space.get_targets(store, InputTargets::Water) {
+                for target in space.get_targets(store, id, InputTargets::Water) {
                     match target {
                         Target::LOCATION(point) => {
                             // todo actually path to water to test if it should be considered?
@@ -133,9 +171,21 @@ impl Action {
                     turn: *turn,
                 });
             },
-            Task::Attack(target) | Task::MoveTo(target) | Task::Destroy(target) | Task::PickUpItem(target) => {
+            Task::Attack(target) | Task::MoveTo(target) | Task::Destroy(target) | Task::PickUpItem(target) | Task::Eat(target) | Task::Sleep(target) | Task::Till(target) | Task::Sow(target) | Task::Harvest(target) | Task::Flee(target) | Task::Burn(target) => {
+                let space = vspace.get(id).unwrap();
+                for target in space.get_targets(store, id, target) {
+                    intents.push(Intent {
+                        name: self.intent.name.clone(),
+                        owner: id,
+                        task: self.intent.task,
+                        target: vec![target],
+                        turn: *turn,
+                    });
+                }
+            },
+            Task::TakeFromStockpile(item_target) => {
                 let space = vspace.get(id).unwrap();
-                for target in space.get_targets(store, target) {
+                for target in space.get_targets(store, id, item_target.storage()) {
                     intents.push(Intent {
                         name: self.intent.name.clone(),
                         owner: id,
@@ -151,13 +201,48 @@ impl Action {
             Task::EquipItem => todo!(),
             Task::UnequipItem => todo!(),
             Task::UseWorkshop => todo!(),
+            Task::Cast(target) => {
+                let vpos = store.borrow::<View<Position>>().unwrap();
+                let vbook = store.borrow::<View<Spellbook>>().unwrap();
+                let vmana = store.borrow::<View<Mana>>().unwrap();
+                let vspell = store.borrow::<View<Spell>>().unwrap();
+                let vranged = store.borrow::<View<Ranged>>().unwrap();
+
+                let victims = match target {
+                    InputTargets::Enemy => visible_enemies(store, id),
+                    _ => vec![],
+                };
+
+                if let (Ok(book), Ok(mana), Ok(pos)) = (vbook.get(id), vmana.get(id), vpos.get(id)) {
+                    for spell in book.spells.iter() {
+                        if !vspell.get(*spell).map_or(false, |s| s.castable(mana)) {
+                            continue;
+                        }
+
+                        let range = vranged.get(*spell).map_or(0., |r| r.range as f32);
+                        for (victim, p) in victims.iter() {
+                            if DistanceAlg::Pythagoras.distance2d(pos.ps[0], *p) > range {
+                                continue;
+                            }
+
+                            intents.push(Intent {
+                                name: self.intent.name.clone(),
+                                owner: id,
+                                task: self.intent.task,
+                                target: vec![Target::from(*victim), Target::from(*spell)],
+                                turn: *turn,
+                            });
+                        }
+                    }
+                }
+            },
             Task::DepositItemToInventory(item_target, inv_target) => { 
                 let space = vspace.get(id).unwrap();
 
                 let inv = vinv.get(id).unwrap();
                 for (itemid, item) in vitem.iter().with_id() {
-                    if item_target.matches(item.typ) && inv.items.contains(&id){        
-                        for inv in space.get_targets(store, inv_target) {
+                    if item_target.matches(item.typ) && inv.items.contains(&itemid){        
+                        for inv in space.get_targets(store, id, inv_target) {
                             intents.push(Intent {
                                 name: self.intent.name.clone(),
                                 owner: id,
@@ -200,3 +285,28 @@ pub fn average(numbers: &[f32]) -> f32 {
     let count = numbers.len() as f32;
     sum / count
 }
+
+/// Hostiles in the owner's view that can fight, and where they stand
+fn visible_enemies(store: &AllStorages, id: EntityId) -> Vec<(EntityId, Point)> {
+    let map = store.borrow::<UniqueView<Map>>().unwrap();
+    let factions = store.borrow::<UniqueView<FactionTable>>().unwrap();
+    let vactor = store.borrow::<View<Actor>>().unwrap();
+    let vstats = store.borrow::<View<PhysicalStats>>().unwrap();
+    let vvision = store.borrow::<View<Vision>>().unwrap();
+
+    let vision = match vvision.get(id) {
+        Ok(vision) => vision,
+        Err(_) => return vec![],
+    };
+
+    let mut enemies = vec![];
+    for p in vision.visible_tiles.iter() {
+        for e in map.tile_content[map.point_idx(*p)].iter() {
+            if *e != id && vstats.get(*e).is_ok() && factions.relation_between(&vactor, id, *e) == Relation::Hostile {
+                enemies.push((*e, *p));
+            }
+        }
+    }
+
+    enemies
+}
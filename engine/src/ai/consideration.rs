@@ -9,6 +9,8 @@ pub struct Consideration {
     pub name: String,
     pub input_type: InputType,
     pub params: ConsiderationParam,
+    #[serde(default)]
+    pub required: bool, // a zero score here rules the whole intent out instead of being averaged in
 }
 
 impl Consideration {
@@ -17,6 +19,7 @@ impl Consideration {
             name: name,
             input_type: input_type,
             params: params,
+            required: false,
         }
     }
 
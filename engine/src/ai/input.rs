@@ -7,6 +7,18 @@ pub enum InputType {
     Const, // used as a baseline for things
     DistanceTo(InputTargets),
     Inventory(InputTargets), // intent owner's inventory
+    StockpileCount(InputTargets), // stored by the whole village
+    Hunger,  // intent owner's needs, 0 to MAX_NEED
+    Fatigue,
+    Shelter,
+    ConstructionDemand, // logs construction sites are still waiting on
+    Threat, // hostiles the owner can see right now
+    Health, // owner's hp as a fraction of max
+    Mana, // owner's mana as a fraction of max
+    SpellCost, // mana the intent's spell costs as a fraction of the owner's max
+    SpellDamage, // damage the intent's spell deals to each target
+    SpellHits, // enemies caught by the intent's spell
+    FriendlyFire, // friends caught by the intent's spell, the caster included
     // TargetInventory(InputTargets), // target's inventory
 }
 
@@ -22,14 +34,43 @@ pub enum InputTargets {
     Player,
     None,
     Orc,
+    Home, // the plank house the owner sleeps in
+    House, // any house in the village, the chief's included
+    ConstructionSite,
+    Farmland, // tilled tiles waiting to be sown
+    Soil, // open ground at the edge of a field that can be tilled
+    RipeCrop,
+    Granary,
+    Grain,
+    Wolf, // other wolves, for finding the pack
+    Ground, // open ground that can be dug into a trench
+    Wall,
+    Trench, // destroying a trench fills it back in
+    Dirt,
 }
 
 impl InputTargets {
     pub fn matches(&self, item: ItemType) -> bool {
+        self.item_type() == Some(item)
+    }
+
+    pub fn item_type(&self) -> Option<ItemType> {
+        match self {
+            InputTargets::Log => Some(ItemType::Log),
+            InputTargets::Fish => Some(ItemType::Fish),
+            InputTargets::Grain => Some(ItemType::Grain),
+            InputTargets::Dirt => Some(ItemType::Dirt),
+            _ => None,
+        }
+    }
+
+    /// Building the village keeps this item in
+    pub fn storage(&self) -> InputTargets {
         match self {
-            InputTargets::Log => item == ItemType::Log,
-            InputTargets::Fish => item == ItemType::Fish,
-            _ => false,
+            InputTargets::Log => InputTargets::LumberMill,
+            InputTargets::Fish => InputTargets::Fishery,
+            InputTargets::Grain => InputTargets::Granary,
+            _ => InputTargets::None,
         }
     }
 }
\ No newline at end of file
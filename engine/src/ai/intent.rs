@@ -1,11 +1,11 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use shipyard::{Component, EntityId};
 
 use crate::{world::components::{self, Turn}, utils::Target};
 
 use super::input::InputTargets;
 
-#[derive(Clone, Debug, Copy, PartialEq, Serialize)]
+#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Task {
     Fish,    // not an effect yet but maybe could be?
     Explore, //
@@ -22,6 +22,15 @@ pub enum Task {
     Attack(InputTargets),
     Idle,
     Spawn(InputTargets),
+    Eat(InputTargets),   // eat food stored in target
+    Sleep(InputTargets), // sleep in target
+    TakeFromStockpile(InputTargets), // take an item out of the building storing it
+    Till(InputTargets),
+    Sow(InputTargets),
+    Harvest(InputTargets),
+    Flee(InputTargets), // get away from target
+    Burn(InputTargets), // set fire to the target
+    Cast(InputTargets), // cast a known spell, targets are [victim, spell]
 }
 
 #[derive(Component, Clone, Debug)]
@@ -46,7 +55,7 @@ impl Intent {
 }
 
 // Actions are stored using archetype, and specific intents are generated on the fly
-#[derive(Component, Clone, Debug, PartialEq, Serialize)]
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct IntentArchetype {
     pub name: String,
     pub task: Task,
@@ -13,8 +13,9 @@ pub struct AI {}
 
 impl AI {
     pub fn choose_intent(actions: Vec<Action>, store: &AllStorages, id: EntityId) -> Intent {
-        if actions.len() < 1 {
-            panic!("No actions to choose from");
+        // a missing profile in raws/behaviors.json leaves nothing to choose from
+        if actions.is_empty() {
+            return Intent::idle();
         }
 
         let mut best = (0.0, Intent::idle());
@@ -1,16 +1,31 @@
 use config::{Config, File, FileFormat};
 use serde::{Deserialize, Serialize};
-use strum_macros::Display;
 
 use crate::world::map::XY;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct Settings {
     mode: String,
+    name: String,
+    #[serde(default)]
+    hidden: bool,
+    map_builder: String,
+    #[serde(default)]
+    keymap: Keymap,
     mapsize: [i32; 2],
     follow_player: bool,
     use_player_los: bool,
     show_player: bool,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    systems: Vec<String>,
+    #[serde(default)]
+    spawn_table: Vec<SpawnEntry>,
+    #[serde(default)]
+    starting_inventory: Vec<String>,
+    #[serde(default)]
+    starting_spells: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,41 +33,76 @@ struct SettingsList {
     settings: Vec<Settings>,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct GameSettings {
-    pub mode: GameMode,
+    pub mode: String, // id of the mode in game_modes.yml
+    pub name: String,
+    pub map_builder: String,
+    pub keymap: Keymap,
     pub mapsize: XY,
     pub follow_player: bool,
     pub use_player_los: bool,
     pub show_player: bool,
+    pub seed: u64, // drives every random draw, same seed and inputs give the same game
+    pub systems: Vec<String>, // names of the systems to run each turn, in order. Empty uses the default schedule
+    pub spawn_table: Vec<SpawnEntry>,
+    pub starting_inventory: Vec<String>, // names of items the player starts with
+    pub starting_spells: Vec<String>, // names of spells the player knows
+}
+
+impl GameSettings {
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        if let Some(seed) = seed {
+            self.seed = seed;
+        }
+        self
+    }
 }
 
-#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Display)]
-pub enum GameMode {
-    TestMode,
-    RL,
-    VillageSim,
-    OrcHalls,
+/// Which set of mode specific keys to use on top of the universal ones
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Keymap {
+    Basic,
+    Roguelike,
+    Village,
     MapDemo,
-    OrcArena,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::Basic
+    }
+}
+
+/// Weight of a spawn table entry is `weight + depth_weight * depth`, entries at or below 0 never spawn
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SpawnEntry {
+    pub name: String,
+    pub weight: i32,
+    #[serde(default)]
+    pub depth_weight: i32,
 }
 
 impl From<Settings> for GameSettings {
     fn from(settings: Settings) -> Self {
         GameSettings {
-            mode: match settings.mode.as_str() {
-                "roguelike" => GameMode::RL,
-                "village_sim" => GameMode::VillageSim,
-                "map_demo" => GameMode::MapDemo,
-                "test_mode" => GameMode::TestMode,
-                "orc_halls" => GameMode::OrcHalls,
-                "orc_arena" => GameMode::OrcArena,
-                _ => GameMode::RL, // Default value
-            },
+            mode: settings.mode,
+            name: settings.name,
+            map_builder: settings.map_builder,
+            keymap: settings.keymap,
             mapsize: settings.mapsize.into(),
             follow_player: settings.follow_player,
             use_player_los: settings.use_player_los,
             show_player: settings.show_player,
+            seed: match settings.seed {
+                Some(seed) => seed,
+                None => rltk::RandomNumberGenerator::new().next_u64(), // no seed configured, pick one
+            },
+            systems: settings.systems,
+            spawn_table: settings.spawn_table,
+            starting_inventory: settings.starting_inventory,
+            starting_spells: settings.starting_spells,
         }
     }
 }
@@ -103,31 +153,40 @@ impl From<Settings> for GameSettings {
 //     })
 // }
 
-pub fn get_config(mode: GameMode) -> Result<GameSettings, Box<dyn std::error::Error>> {
+fn load_settings() -> Result<SettingsList, Box<dyn std::error::Error>> {
     let settings = Config::builder()
         .add_source(File::new(&format!("config/{}", "game_modes"), FileFormat::Yaml).required(true))
         .build()?
         .try_deserialize::<SettingsList>()?;
 
-    let env = match mode {
-        GameMode::RL => "roguelike",
-        GameMode::VillageSim => "village_sim",
-        GameMode::MapDemo => "map_demo",
-        GameMode::TestMode => "test_mode",
-        GameMode::OrcHalls => "orc_halls",
-        GameMode::OrcArena => "orc_arena",
-    };
+    Ok(settings)
+}
+
+pub fn get_config(mode: &str) -> Result<GameSettings, Box<dyn std::error::Error>> {
+    let settings = load_settings()?;
 
     for s in settings.settings.iter() {
-        if s.mode == env {
+        if s.mode == mode {
             return Ok(s.clone().into());
         }
     }
 
-    Err("Settings not found".into())
+    Err(format!("Settings not found for mode {}", mode).into())
 
     // dbg!("settings not found, using first setting");
 
     // let s = GameSettings::from(settings.settings[0].clone());
     // Ok(s)
 }
+
+/// (id, display name) of every mode that should show up in the mode select menu
+pub fn get_mode_list() -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let settings = load_settings()?;
+
+    Ok(settings
+        .settings
+        .into_iter()
+        .filter(|s| !s.hidden)
+        .map(|s| (s.mode, s.name))
+        .collect())
+}
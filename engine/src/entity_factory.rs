@@ -1,37 +1,33 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use crate::ai::action::Action;
-use crate::ai::labors::AIBehaviors;
 use crate::ui::colors::{*};
 use crate::world::components::{
-    Actor, ActorType, AreaOfEffect, BlocksTile, ChiefHouse, PhysicalStats, Confusion, Consumable, DealsDamage,
-    DijkstraMapToMe, EquipmentSlot, Equippable, Faction, FishCleaner, Flammable, Inventory, Item, ItemType,
+    Actor, ActorType, AreaOfEffect, BlocksTile, ChiefHouse, PhysicalStats, Consumable, DealsDamage,
+    DijkstraMapToMe, Equippable, Faction, FishCleaner, Flammable, Granary, Inventory, Item,
     LocomotionType, Locomotive, LumberMill, MeleeDefenseBonus, MeleePowerBonus, Name, PlankHouse, Player, Position,
-    ProvidesHealing, Ranged, Renderable, SpatialKnowledge, Spawner, SpawnerType, Tree, Vision, RNG, CausesFire, Equipment, AddsGas, RemovesGas, Aging, Fish, Orc,
+    ProvidesHealing, Ranged, Renderable, SpatialKnowledge, Spawner, SpawnerType, Tree, Vision, RNG, SpawnTable, CausesFire, Equipment, AddsGas, RemovesGas, Aging, Fish, Orc, Energy, InflictsStatus, Needs, ConstructionSite, Sapling, Mana, Spell, Spellbook,
 };
+use crate::config::SpawnEntry;
+use crate::raws::{behaviors::BehaviorProfiles, RawMaster};
 use crate::world::map::{Map, XY};
+use crate::world::systems::system_energy::{ACTION_COST, NORMAL_SPEED};
 use crate::RenderOrder;
-use crate::tiles::{TileType, GasType};
+use crate::tiles::TileType;
 use crate::utils::rect::Rect;
 use crate::utils::weighted_table::WeightedTable;
 use rltk::{DijkstraMap, Point};
-use shipyard::{AllStoragesViewMut, EntityId, UniqueView, UniqueViewMut};
+use shipyard::{AddComponent, AllStoragesViewMut, EntityId, Get, Remove, UniqueView, UniqueViewMut, ViewMut};
 
 const MAX_MONSTERS: usize = 4;
 
-pub fn room_table(depth: usize) -> WeightedTable {
-    WeightedTable::new()
-        .add("Wolf", 10)
-        .add("Goblin", 10)
-        .add("Orc", 1 + depth)
-        .add("Health Potion", 7)
-        .add("Fireball Scroll", 2 + depth)
-        .add("Confusion Scroll", 2 + depth)
-        .add("Magic Missile Scroll", 4)
-        .add("Dagger", 2)
-        .add("Shield", 2)
-        .add("Longsword", depth - 1)
-        .add("Tower Shield", depth - 1)
+pub fn room_table(entries: &[SpawnEntry], depth: usize) -> WeightedTable {
+    let mut table = WeightedTable::new();
+    for e in entries.iter() {
+        let weight = e.weight + e.depth_weight * depth as i32;
+        table = table.add(&e.name, i32::max(weight, 0) as usize);
+    }
+    table
 }
 
 pub fn spawn_room(store: &mut AllStoragesViewMut, map: &Map, room: &Rect, depth: usize) {
@@ -51,8 +47,8 @@ pub fn spawn_room(store: &mut AllStoragesViewMut, map: &Map, room: &Rect, depth:
 }
 
 pub fn spawn_region(store: &mut AllStoragesViewMut, area: &[usize], map_depth: usize) {
-    let spawn_table = room_table(map_depth);
-    let mut spawn_points: HashMap<usize, String> = HashMap::new();
+    let spawn_table = room_table(&store.borrow::<UniqueView<SpawnTable>>().unwrap().0, map_depth);
+    let mut spawn_points: BTreeMap<usize, String> = BTreeMap::new();
     let mut areas: Vec<usize> = Vec::from(area);
 
     // Scope to keep the borrow checker happy
@@ -74,7 +70,9 @@ pub fn spawn_region(store: &mut AllStoragesViewMut, area: &[usize], map_depth: u
                 (rng.roll_dice(1, areas.len() as i32) - 1) as usize
             };
             let map_idx = areas[array_index];
-            spawn_points.insert(map_idx, spawn_table.roll(&mut rng).unwrap());
+            if let Some(name) = spawn_table.roll(&mut rng) {
+                spawn_points.insert(map_idx, name);
+            }
             areas.remove(array_index);
         }
     }
@@ -88,38 +86,246 @@ pub fn spawn_region(store: &mut AllStoragesViewMut, area: &[usize], map_depth: u
 /// Spawns a named entity (name in tuple.1) at the location in (tuple.0)
 fn spawn_entity(store: &mut AllStoragesViewMut, spawn: &(&usize, &String)) {
     let xy = store.run(|map: UniqueView<Map>| map.idx_xy(*spawn.0));
+    spawn_named(store, spawn.1, xy);
+}
+
+/// Spawns an entity from its definition in the raws, by the name used in spawn tables and starting inventories
+pub fn spawn_named(store: &mut AllStoragesViewMut, name: &str, xy: XY) -> Option<EntityId> {
+    let raw = match store.borrow::<UniqueView<RawMaster>>().unwrap().get(name) {
+        Some(raw) => raw.clone(),
+        None => {
+            dbg!("Unknown entity name", name);
+            return None;
+        }
+    };
 
-    match spawn.1.as_ref() {
-        "Wolf" => wolf(store, xy),
-        "Goblin" => goblin(store, xy),
-        "Orc" => orc(store, xy),
-        "Health Potion" => health_potion(store, xy),
-        "Fireball Scroll" => fireball_scroll(store, xy),
-        "Confusion Scroll" => confusion_scroll(store, xy),
-        "Magic Missile Scroll" => magic_missile_scroll(store, xy),
-        "Dagger" => dagger(store, xy),
-        "Shield" => shield(store, xy),
-        "Longsword" => longsword(store, xy),
-        "Tower Shield" => tower_shield(store, xy),
-        _ => unreachable!(),
+    let (width, height) = match raw.size {
+        Some([w, h]) => (w, h),
+        None => (1, 1),
     };
+    let mut ps = vec![];
+    for xi in 0..width {
+        for yi in 0..height {
+            ps.push(Point::new(xy.0 + xi, xy.1 + yi));
+        }
+    }
+
+    let e = store.add_entity((
+        Position { ps },
+        Name {
+            name: raw.display_name.clone().unwrap_or(raw.name.clone()),
+        },
+    ));
+
+    if let Some(r) = &raw.renderable {
+        store.add_component(e, (Renderable {
+            glyph: r.glyph,
+            fg: r.fg,
+            bg: r.bg,
+            order: r.order,
+            ..Default::default()
+        },));
+    }
+    if let Some(range) = raw.vision_range {
+        store.add_component(e, (Vision {
+            visible_tiles: Vec::new(),
+            range,
+            dirty: true,
+        },));
+    }
+    if raw.blocks_tile {
+        store.add_component(e, (BlocksTile {},));
+    }
+
+    // actors
+    if let Some(a) = &raw.actor {
+        let actions = if a.behaviors.is_empty() {
+            vec![]
+        } else {
+            store.borrow::<UniqueView<BehaviorProfiles>>().unwrap().actions_for(&a.behaviors)
+        };
+
+        store.add_component(e, (Actor {
+            faction: a.faction,
+            atype: a.atype,
+            behaviors: a.behaviors.clone(),
+            actions,
+            score: 0,
+        }, Energy { value: 0 }));
+    }
+    if let Some(l) = raw.locomotion {
+        store.add_component(e, (l,));
+    }
+    if let Some(s) = &raw.stats {
+        store.add_component(e, (PhysicalStats {
+            max_hp: s.hp,
+            hp: s.hp,
+            defense: s.defense,
+            power: s.power,
+            regen_rate: s.regen_rate,
+        },));
+    }
+    if let Some(capacity) = raw.inventory {
+        store.add_component(e, (Inventory {
+            capacity,
+            items: Vec::new(),
+        },));
+    }
+    if raw.spatial_knowledge {
+        store.add_component(e, (SpatialKnowledge { tiles: BTreeMap::new() },));
+    }
+    if raw.dijkstra_map {
+        store.add_component(e, (DijkstraMapToMe {
+            map: DijkstraMap::new_empty(0, 0, 0.),
+        },));
+    }
+    if raw.aging {
+        store.add_component(e, (Aging { turns: 0 },));
+    }
+    if raw.needs {
+        store.add_component(e, (Needs::default(),));
+    }
+    if raw.orc {
+        store.add_component(e, (Orc {},));
+    }
+    if raw.fish {
+        store.add_component(e, (Fish {},));
+    }
+    if let Some(m) = &raw.mana {
+        store.add_component(e, (Mana {
+            current: m.max,
+            max: m.max,
+            regen: m.regen,
+        }, Spellbook::default()));
+    }
+
+    // items
+    if let Some(typ) = raw.item {
+        store.add_component(e, (Item { typ },));
+    }
+    if raw.consumable {
+        store.add_component(e, (Consumable {},));
+    }
+    if let Some(heal) = raw.healing {
+        store.add_component(e, (ProvidesHealing { heal },));
+    }
+    if let Some(damage) = raw.damage {
+        store.add_component(e, (DealsDamage { damage },));
+    }
+    if let Some(range) = raw.ranged {
+        store.add_component(e, (Ranged { range },));
+    }
+    if let Some(radius) = raw.area_of_effect {
+        store.add_component(e, (AreaOfEffect { radius },));
+    }
+    if let Some(turns) = raw.causes_fire {
+        store.add_component(e, (CausesFire { turns },));
+    }
+    if let Some(status) = raw.inflicts_status {
+        store.add_component(e, (InflictsStatus { status },));
+    }
+    if let Some(slot) = raw.equippable {
+        store.add_component(e, (Equippable { slot },));
+    }
+    if let Some(power) = raw.melee_power {
+        store.add_component(e, (MeleePowerBonus { power },));
+    }
+    if let Some(defense) = raw.melee_defense {
+        store.add_component(e, (MeleeDefenseBonus { defense },));
+    }
+    if let Some(s) = &raw.spell {
+        store.add_component(e, (Spell {
+            mana_cost: s.mana_cost,
+            cooldown: s.cooldown,
+            cooldown_left: 0,
+        },));
+    }
+
+    // props and structures
+    if raw.flammable {
+        store.add_component(e, (Flammable {},));
+    }
+    if raw.tree {
+        store.add_component(e, (Tree {},));
+    }
+    if raw.sapling {
+        store.add_component(e, (Sapling::default(),));
+    }
+    if let Some(gas) = raw.adds_gas {
+        store.add_component(e, (AddsGas { gas },));
+    }
+    if raw.removes_gas {
+        store.add_component(e, (RemovesGas {},));
+    }
+    if let Some(housing_cap) = raw.housing {
+        store.add_component(e, (PlankHouse {
+            housing_cap,
+            villagers: vec![],
+        },));
+    }
+    if raw.chief_house {
+        store.add_component(e, (ChiefHouse {},));
+    }
+    if raw.lumber_mill {
+        store.add_component(e, (LumberMill {},));
+    }
+    if raw.fish_cleaner {
+        store.add_component(e, (FishCleaner {},));
+    }
+    if raw.granary {
+        store.add_component(e, (Granary {},));
+    }
+
+    for name in raw.spellbook.iter() {
+        learn_spell(store, e, name);
+    }
+
+    Some(e)
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Teaches `caster` a spell from the raws. Spells are kept in the spellbook and never sit on the map
+pub fn learn_spell(store: &mut AllStoragesViewMut, caster: EntityId, name: &str) -> Option<EntityId> {
+    let spell = spawn_named(store, name, (0, 0))?;
+    store.borrow::<ViewMut<Position>>().unwrap().remove(spell);
+
+    let mut vbook = store.borrow::<ViewMut<Spellbook>>().unwrap();
+    match (&mut vbook).get(caster) {
+        Ok(book) => book.spells.push(spell),
+        Err(_) => vbook.add_component_unchecked(caster, Spellbook { spells: vec![spell] }),
+    }
+
+    Some(spell)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EntitySpawnTypes {
     Villager
 }
 
-pub fn spawn_entity_type(store: &mut AllStoragesViewMut, etype: EntitySpawnTypes, pos: XY, actions: &Option<Vec<Action>>) {
-    match etype {
-        EntitySpawnTypes::Villager => {
-            let actions = match actions {
-                Some(a) => a.to_vec(),
-                None => vec![],
-            };
-            villager(store, pos, &actions);
-        },
+/// Spawns an entity, replacing the actions from its behavior profiles if `actions` is set
+pub fn spawn_entity_type(store: &mut AllStoragesViewMut, etype: EntitySpawnTypes, pos: XY, actions: &Option<Vec<Action>>) -> Option<EntityId> {
+    let e = match etype {
+        EntitySpawnTypes::Villager => spawn_named(store, "Villager", pos)?,
+    };
+
+    if let Some(actions) = actions {
+        if let Ok(actor) = (&mut store.borrow::<ViewMut<Actor>>().unwrap()).get(e) {
+            actor.actions = actions.to_vec();
+        }
+    }
+
+    Some(e)
+}
+
+/// Spawns an entity by name that fights for `faction` instead of the one in its raws
+pub fn spawn_for_faction(store: &mut AllStoragesViewMut, name: &str, xy: XY, faction: Faction) -> Option<EntityId> {
+    let e = spawn_named(store, name, xy)?;
+
+    if let Ok(actor) = (&mut store.borrow::<ViewMut<Actor>>().unwrap()).get(e) {
+        actor.faction = faction;
     }
+
+    Some(e)
 }
 
 pub fn player(store: &mut AllStoragesViewMut, pos: XY, is_render: bool) -> EntityId {
@@ -144,7 +350,7 @@ pub fn player(store: &mut AllStoragesViewMut, pos: XY, is_render: bool) -> Entit
         },
         Locomotive {
             mtype: LocomotionType::Ground,
-            speed: 1,
+            speed: NORMAL_SPEED,
         },
         Vision {
             visible_tiles: Vec::new(),
@@ -161,7 +367,7 @@ pub fn player(store: &mut AllStoragesViewMut, pos: XY, is_render: bool) -> Entit
             power: 5,
             regen_rate: 1,
         },
-        SpatialKnowledge { tiles: HashMap::new() },
+        SpatialKnowledge { tiles: BTreeMap::new() },
         Inventory {
             capacity: 20,
             items: Vec::new(),
@@ -169,495 +375,24 @@ pub fn player(store: &mut AllStoragesViewMut, pos: XY, is_render: bool) -> Entit
     ));
 
     store.add_component(e, (
+        Energy { value: ACTION_COST }, // the player gets the first move
         DijkstraMapToMe {
             map: DijkstraMap::new_empty(0, 0, 0.),
         },
-        Equipment::new()
-    ));
-
-    e
-}
-
-/// Monsters
-
-pub fn villager(store: &mut AllStoragesViewMut, xy: XY, actions: &Vec<Action>) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: 'v',
-            fg: COLOR_RED,
-            bg: COLOR_BG,
-            order: RenderOrder::NPC,
-            ..Default::default()
-        },
-        Vision {
-            visible_tiles: Vec::new(),
-            range: 20,
-            dirty: true,
-        },
-        Locomotive {
-            mtype: LocomotionType::Ground,
-            speed: 1,
-        },
-        Name {
-            name: "Villager".to_string(),
-        },
-        BlocksTile {},
-        Inventory {
-            capacity: 5,
-            items: Vec::new(),
-        },
-        SpatialKnowledge { tiles: HashMap::new() },
-        Actor {
-            faction: Faction::Villager,
-            atype: ActorType::Villager,
-            behaviors: vec![AIBehaviors::GatherWood, AIBehaviors::GatherFish, AIBehaviors::Wander],
-            actions: actions.to_vec(),
-            score: 0,
-        },
-        Aging {
-            turns: 0,
-        }
-    ))
-}
-
-pub fn fish(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Fish {},
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
+        Equipment::new(),
+        Mana {
+            current: 20,
+            max: 20,
+            regen: 1,
         },
-        Renderable {
-            glyph: 'f',
-            fg: COLOR_AMBER,
-            bg: COLOR_BG,
-            order: RenderOrder::NPC,
-            ..Default::default()
-        },
-        Vision {
-            visible_tiles: Vec::new(),
-            range: 2,
-            dirty: true,
-        },
-        Locomotive {
-            mtype: LocomotionType::Water,
-            speed: 1,
-        },
-        Name {
-            name: "Fish".to_string(),
-        },
-        Actor {
-            faction: Faction::Nature,
-            atype: ActorType::Fish,
-            behaviors: Vec::new(),
-            actions: vec![],
-            score: 0,
-        },
-        Item { typ: ItemType::Fish },
-    ))
-}
-
-pub fn orc(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    let e = monster(store, xy, 'o', "Orc".to_string());
-
-    store.add_component(e, (
-        Orc {},
+        Spellbook::default(),
     ));
 
     e
 }
 
-pub fn goblin(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    monster(store, xy, 'g', "Goblin".to_string())
-}
-
-pub fn monster(store: &mut AllStoragesViewMut, xy: XY, glyph: char, name: String) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph,
-            fg: COLOR_BROWN,
-            bg: COLOR_BG,
-            order: RenderOrder::NPC,
-            ..Default::default()
-        },
-        Vision {
-            visible_tiles: Vec::new(),
-            range: 8,
-            dirty: true,
-        },
-        Actor {
-            faction: Faction::Orcs,
-            atype: ActorType::Orc,
-            behaviors: vec![AIBehaviors::AttackEnemies],
-            actions: vec![],
-            score: 0,
-        },
-        Locomotive {
-            mtype: LocomotionType::Ground,
-            speed: 1,
-        },
-        Name { name },
-        BlocksTile {},
-        PhysicalStats {
-            max_hp: 8,
-            hp: 8,
-            defense: 1,
-            power: 4,
-            regen_rate: 0,
-        },
-        Inventory {
-            capacity: 5,
-            items: Vec::new(),
-        },
-    ))
-}
-
-pub fn wolf(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: 'w',
-            fg: COLOR_RED,
-            bg: COLOR_BG,
-            order: RenderOrder::NPC,
-            ..Default::default()
-        },
-        Vision {
-            visible_tiles: Vec::new(),
-            range: 5,
-            dirty: true,
-        },
-        Actor {
-            faction: Faction::Nature,
-            atype: ActorType::Wolf,
-            behaviors: vec![AIBehaviors::AttackEnemies],
-            actions: vec![],
-            score: 0,
-        },
-        Locomotive {
-            mtype: LocomotionType::Ground,
-            speed: 1,
-        },
-        Name {
-            name: "Wolf".to_string(),
-        },
-        BlocksTile {},
-        PhysicalStats {
-            max_hp: 8,
-            hp: 8,
-            defense: 1,
-            power: 4,
-            regen_rate: 1,
-        },
-    ))
-}
-
-#[allow(dead_code)]
-pub fn big_monster(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![
-                Point::new(xy.0, xy.1),
-                Point::new(xy.0 + 1, xy.1),
-                Point::new(xy.0, xy.1 + 1),
-                Point::new(xy.0 + 1, xy.1 + 1),
-            ],
-        },
-        Renderable {
-            glyph: 'o',
-            fg: COLOR_RED,
-            bg: COLOR_BG,
-            order: RenderOrder::NPC,
-            ..Default::default()
-        },
-        Vision {
-            visible_tiles: Vec::new(),
-            range: 8,
-            dirty: true,
-        },
-        Actor {
-            faction: Faction::Orcs,
-            atype: ActorType::Orc,
-            behaviors: vec![AIBehaviors::AttackEnemies],
-            actions: vec![],
-            score: 0,
-        },
-        Locomotive {
-            mtype: LocomotionType::Ground,
-            speed: 1,
-        },
-        Name {
-            name: "Monster".to_string(),
-        },
-        BlocksTile {},
-        PhysicalStats {
-            max_hp: 8,
-            hp: 8,
-            defense: 1,
-            power: 4,
-            regen_rate: 0,
-        },
-    ))
-}
-
-/// consumables
-
-const WEAPON_GLYPH: char = '/';
-const ARMOR_GLYPH: char = '{';
-const POTION_GLYPH: char = '!';
-const SCROLL_GLYPH: char = '?';
-const LOG_GLYPH: char = '=';
-
-pub fn health_potion(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: POTION_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Health potion".to_string(),
-        },
-        Item { typ: ItemType::Potion },
-        ProvidesHealing { heal: 8 },
-        Consumable {},
-    ))
-}
-
-pub fn magic_missile_scroll(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: SCROLL_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Magic missile scroll".to_string(),
-        },
-        Item { typ: ItemType::Scroll },
-        Consumable {},
-        DealsDamage { damage: 8 },
-        Ranged { range: 6 },
-    ))
-}
-
-pub fn fireball_scroll(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: SCROLL_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Fireball scroll".to_string(),
-        },
-        Item { typ: ItemType::Scroll },
-        Consumable {},
-        DealsDamage { damage: 20 },
-        Ranged { range: 6 },
-        AreaOfEffect { radius: 3 },
-        CausesFire { turns: 5 }
-    ))
-}
-
-pub fn confusion_scroll(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: SCROLL_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Confusion scroll".to_string(),
-        },
-        Item { typ: ItemType::Scroll },
-        Consumable {},
-        Ranged { range: 6 },
-        Confusion { turns: 4 },
-    ))
-}
-
-/// equippables
-
-pub fn dagger(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: WEAPON_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Dagger".to_string(),
-        },
-        Item { typ: ItemType::Weapon },
-        Equippable {
-            slot: EquipmentSlot::RightHand,
-        },
-        MeleePowerBonus { power: 4 },
-    ))
-}
-
-pub fn longsword(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: WEAPON_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "longsword".to_string(),
-        },
-        Item { typ: ItemType::Shield },
-        Equippable {
-            slot: EquipmentSlot::RightHand,
-        },
-        MeleePowerBonus { power: 8 },
-    ))
-}
-
-pub fn shield(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: ARMOR_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Shield".to_string(),
-        },
-        Item { typ: ItemType::Shield },
-        Equippable {
-            slot: EquipmentSlot::LeftHand,
-        },
-        MeleeDefenseBonus { defense: 4 },
-    ))
-}
-
-pub fn tower_shield(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: ARMOR_GLYPH,
-            fg: COLOR_ITEM,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Shield".to_string(),
-        },
-        Item { typ: ItemType::Shield },
-        Equippable {
-            slot: EquipmentSlot::LeftHand,
-        },
-        MeleeDefenseBonus { defense: 8 },
-    ))
-}
-
-pub fn log(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: LOG_GLYPH,
-            fg: COLOR_CEDAR,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Log".to_string(),
-        },
-        Item { typ: ItemType::Log },
-        Flammable {},
-    ))
-}
-
 // structures
 
-pub fn gas_adder(store: &mut AllStoragesViewMut, xy: XY, gastype: GasType) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: '=',
-            fg: COLOR_WALL.scale(0.25),
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Gas Vent".to_string(),
-        },
-        AddsGas { gas: gastype },
-    ))
-}
-
-pub fn gas_remover(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: '=',
-            fg: COLOR_WALL.scale(0.25),
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Gas Intake".to_string(),
-        },
-        RemovesGas {},
-    ))
-}
-
 pub fn spawner(
     store: &mut AllStoragesViewMut,
     xy: XY,
@@ -687,170 +422,56 @@ pub fn spawner(
             actions: vec![],
             score: 0,
         },
+        Energy { value: 0 },
     ))
 }
 
-pub fn tree(store: &mut AllStoragesViewMut, xy: XY) -> EntityId {
-    store.add_entity((
-        Position {
-            ps: vec![Point::new( xy.0, xy.1 )],
-        },
-        Renderable {
-            glyph: '|',
-            fg: COLOR_CEDAR,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Tree".to_string(),
-        },
-        Flammable {},
-        Tree {},
-    ))
-}
-
-pub fn plank_house(store: &mut AllStoragesViewMut, xy: XY, width: usize, height: usize) -> EntityId {
-    let mut ps = vec![];
-    for xi in 0..width {
-        for yi in 0..height {
-            ps.push(Point::new( xy.0 + xi as i32, xy.1 + yi as i32));
-        }
-    }
-
-    // TODO pick colors for buildings, maybe glyph?
-
-    store.add_entity((
-        Position { ps },
-        Renderable {
-            glyph: '#',
-            fg: COLOR_CEDAR,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Plank House".to_string(),
-        },
-        Flammable {},
-        PlankHouse {
-            housing_cap: 5,
-            villagers: vec![],
-        },
-        BlocksTile {},
-    ))
-}
+/// Lays out a site where villagers will build `building`, which needs a size and build cost in the raws
+pub fn construction_site(store: &mut AllStoragesViewMut, xy: XY, building: &str) -> Option<EntityId> {
+    let (size, logs_needed) = {
+        let raws = store.borrow::<UniqueView<RawMaster>>().unwrap();
+        let raw = match raws.get(building) {
+            Some(raw) => raw,
+            None => {
+                dbg!("No raw to build", building);
+                return None;
+            }
+        };
+        (raw.size.unwrap_or([1, 1]), raw.build_cost.unwrap_or(0))
+    };
 
-pub fn chief_house(store: &mut AllStoragesViewMut, xy: XY, width: usize, height: usize) -> EntityId {
     let mut ps = vec![];
-    for xi in 0..width {
-        for yi in 0..height {
-            ps.push(Point::new(xy.0 + xi as i32, xy.1 + yi as i32));
+    for xi in 0..size[0] {
+        for yi in 0..size[1] {
+            ps.push(Point::new(xy.0 + xi, xy.1 + yi));
         }
     }
 
-    // TODO pick colors for buildings, maybe glyph?
-
-    store.add_entity((
+    Some(store.add_entity((
         Position { ps },
         Renderable {
-            glyph: '#',
-            fg: COLOR_CEDAR,
+            glyph: '+',
+            fg: COLOR_DESATURATED_AMBER,
             bg: COLOR_BG,
             order: RenderOrder::Items,
             ..Default::default()
         },
         Name {
-            name: "chief_house".to_string(),
-        },
-        Flammable {},
-        ChiefHouse {},
-        BlocksTile {},
-    ))
-}
-
-pub fn fish_cleaner(store: &mut AllStoragesViewMut, xy: XY, width: usize, height: usize) -> EntityId {
-    let mut ps = vec![];
-    for xi in 0..width {
-        for yi in 0..height {
-            ps.push(Point::new( xy.0 + xi as i32, xy.1 + yi as i32));
-        }
-    }
-
-    // TODO pick colors for buildings, maybe glyph?
-
-    store.add_entity((
-        Position { ps },
-        Renderable {
-            glyph: '#',
-            fg: COLOR_UI_1,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
+            name: format!("{} site", building),
         },
-        Name {
-            name: "Fish Cleaner".to_string(),
+        ConstructionSite {
+            building: building.to_string(),
+            logs_needed,
+            logs_delivered: 0,
+            tiles_built: 0,
         },
-        Flammable {},
-        FishCleaner {},
-        BlocksTile {},
         Inventory {
-            capacity: 50,
+            capacity: logs_needed,
             items: Vec::new(),
         },
-        DijkstraMapToMe {
-            map: DijkstraMap::new_empty(0, 0, 0.),
-        },
-    ))
-}
-
-pub fn lumber_mill(store: &mut AllStoragesViewMut, xy: XY, width: usize, height: usize) -> EntityId {
-    let mut ps = vec![];
-    for xi in 0..width {
-        for yi in 0..height {
-            ps.push(Point::new( xy.0 + xi as i32, xy.1 + yi as i32));
-        }
-    }
-
-    // TODO pick colors for buildings, maybe glyph?
-
-    store.add_entity((
-        Position { ps },
-        Renderable {
-            glyph: '#',
-            fg: COLOR_AMBER,
-            bg: COLOR_BG,
-            order: RenderOrder::Items,
-            ..Default::default()
-        },
-        Name {
-            name: "Lumber Mill".to_string(),
-        },
-        Flammable {},
-        LumberMill {},
         BlocksTile {},
-        Inventory {
-            capacity: 50,
-            items: Vec::new(),
-        },
         DijkstraMapToMe {
             map: DijkstraMap::new_empty(0, 0, 0.),
         },
-    ))
-}
-
-/// misc
-
-pub fn tmp_fireball(store: &mut AllStoragesViewMut) -> EntityId {
-    store.add_entity((
-        Name {
-            name: "Fireball".to_string(),
-        },
-        Item { typ: ItemType::Scroll },
-        Consumable {},
-        DealsDamage { damage: 20 },
-        Ranged { range: 6 },
-        AreaOfEffect { radius: 1 },
-        CausesFire { turns: 3 },
-    ))
+    )))
 }
@@ -1,4 +1,4 @@
-use rltk::Point;
+use rltk::{Point, RandomNumberGenerator};
 use shipyard::{AllStoragesViewMut, World};
 
 use crate::{
@@ -21,8 +21,8 @@ impl MapBuilder for AernaBuilder {
     fn get_starting_position(&mut self) -> Position {
         self.starting_position.clone()
     }
-    fn build_map(&mut self) {
-        self.build()
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
@@ -63,7 +63,7 @@ impl AernaBuilder {
         }
     }
 
-    fn build(&mut self) {
+    fn build(&mut self, _rng: &mut RandomNumberGenerator) {
         // let mut rng = RandomNumberGenerator::new();
 
         // set edges to be a wall
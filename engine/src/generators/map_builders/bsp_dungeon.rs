@@ -22,8 +22,8 @@ impl MapBuilder for BspDungeonBuilder {
         self.starting_position.clone()
     }
 
-    fn build_map(&mut self) {
-        self.build()
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
@@ -54,8 +54,7 @@ impl BspDungeonBuilder {
         }
     }
 
-    fn build(&mut self) {
-        let mut rng = RandomNumberGenerator::new();
+    fn build(&mut self, rng: &mut RandomNumberGenerator) {
 
         self.rects.clear();
         self.rects
@@ -67,11 +66,11 @@ impl BspDungeonBuilder {
         // room in there, we place it and add it to the rooms list.
         let mut n_rooms = 0;
         while n_rooms < 240 {
-            let rect = self.get_random_rect(&mut rng);
+            let rect = self.get_random_rect(rng);
             if rect.size() < 8 {
                 continue;
             }
-            let candidate = self.get_random_sub_rect(rect, &mut rng);
+            let candidate = self.get_random_sub_rect(rect, rng);
 
             if self.is_possible(candidate) {
                 apply_room_to_map(&mut self.map, &candidate, TileType::Floor, true);
@@ -1,7 +1,7 @@
 use rltk::{Point, RandomNumberGenerator};
 use shipyard::{AllStoragesViewMut, World};
 
-use crate::{entity_factory, SHOW_MAPGEN_ANIMATION, utils::rect::Rect, tiles::TileType, world::map::XY};
+use crate::{entity_factory, SHOW_MAPGEN_ANIMATION, utils::rect::Rect, tiles::{TileType, CROP_RIPE}, world::map::XY};
 
 use super::{common::apply_room_to_map, Map, MapBuilder, Position};
 
@@ -26,8 +26,8 @@ impl MapBuilder for BspFarmBuilder {
         self.starting_position.clone()
     }
 
-    fn build_map(&mut self) {
-        self.build()
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
@@ -58,7 +58,7 @@ impl BspFarmBuilder {
         }
     }
 
-    fn build(&mut self) {
+    fn build(&mut self, rng: &mut RandomNumberGenerator) {
         //convert interior tiles to dirt
         for x in 1..self.map.size.0 - 1 {
             for y in 1..self.map.size.1 - 1 {
@@ -67,13 +67,11 @@ impl BspFarmBuilder {
             }
         }
 
-        let mut rng = RandomNumberGenerator::new();
-
         self.rects.clear();
         self.rects
             .push(Rect::new(1, 1, self.map.size.0 as i32 - 2, self.map.size.1 as i32 - 2)); // Start with a single map-sized rectangle
         let first_room = self.rects[0];
-        self.add_subrects_recursive(first_room, &mut rng); // Divide the first room
+        self.add_subrects_recursive(first_room, rng); // Divide the first room
 
         let rooms = self.rects.clone();
         for r in rooms.iter() {
@@ -107,6 +105,13 @@ impl BspFarmBuilder {
         let stairs = self.rooms[self.rooms.len() - 1].center();
         let stairs_idx = self.map.xy_idx((stairs.0, stairs.1));
         self.map.tiles[stairs_idx] = TileType::StairsDown;
+
+        // fields start out ready to harvest
+        for idx in 0..self.map.len() {
+            if self.map.tiles[idx] == TileType::Wheat {
+                self.map.vegetation[idx] = CROP_RIPE;
+            }
+        }
     }
 
     fn add_subrects_recursive(&mut self, rect: Rect, rng: &mut RandomNumberGenerator) {
@@ -24,8 +24,8 @@ impl MapBuilder for BspInteriorBuilder {
         self.starting_position.clone()
     }
 
-    fn build_map(&mut self) {
-        self.build()
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
@@ -56,14 +56,13 @@ impl BspInteriorBuilder {
         }
     }
 
-    fn build(&mut self) {
-        let mut rng = RandomNumberGenerator::new();
+    fn build(&mut self, rng: &mut RandomNumberGenerator) {
 
         self.rects.clear();
         self.rects
             .push(Rect::new(1, 1, self.map.size.0 as i32 - 2, self.map.size.1 as i32 - 2)); // Start with a single map-sized rectangle
         let first_room = self.rects[0];
-        self.add_subrects(first_room, &mut rng); // Divide the first room
+        self.add_subrects(first_room, rng); // Divide the first room
 
         let rooms = self.rects.clone();
         for r in rooms.iter() {
@@ -1,17 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashSet};
 
 use rltk::{Point, RandomNumberGenerator};
 use shipyard::{AllStoragesViewMut, World};
 
-use crate::{entity_factory, SHOW_MAPGEN_ANIMATION, tiles::TileType, world::map::XY};
+use crate::{entity_factory, SHOW_MAPGEN_ANIMATION, tiles::TileType, world::{components::{Faction, SpawnerType}, map::XY}};
 
-use super::{Map, MapBuilder, Position};
+use super::{world_rng, Map, MapBuilder, Position};
+
+/// Smallest noise area that can be flooded into a lake
+const LAKE_MIN_SIZE: usize = 40;
+const MAX_LAKES: usize = 2;
 
 pub struct CellularAutomataBuilder {
     map: Map,
     starting_position: Position,
     depth: usize,
-    noise_areas: HashMap<i32, Vec<usize>>,
+    noise_areas: BTreeMap<i32, Vec<usize>>,
+    lakes: Vec<Vec<usize>>,
 }
 
 impl MapBuilder for CellularAutomataBuilder {
@@ -22,15 +27,27 @@ impl MapBuilder for CellularAutomataBuilder {
     fn get_starting_position(&mut self) -> Position {
         self.starting_position.clone()
     }
-    fn build_map(&mut self) {
-        self.build()
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
+        let mut rng = world_rng(world);
         world.run(|mut store: AllStoragesViewMut| {
             for area in self.noise_areas.iter() {
                 entity_factory::spawn_region(&mut store, area.1, self.depth);
             }
+
+            // each lake gets a few fish to start its stock
+            for lake in self.lakes.iter() {
+                let idx = lake[rng.range(0, lake.len() as i32) as usize];
+                entity_factory::spawner(&mut store, self.map.idx_xy(idx), Faction::Nature, SpawnerType::Fish, 5);
+
+                for _ in 0..4 {
+                    let idx = lake[rng.range(0, lake.len() as i32) as usize];
+                    entity_factory::spawn_named(&mut store, "Fish", self.map.idx_xy(idx));
+                }
+            }
         });
     }
 
@@ -49,12 +66,12 @@ impl CellularAutomataBuilder {
                 ps: vec![Point::new(0, 0)],
             },
             depth: new_depth,
-            noise_areas: HashMap::new(),
+            noise_areas: BTreeMap::new(),
+            lakes: vec![],
         }
     }
 
-    fn build(&mut self) {
-        let mut rng = RandomNumberGenerator::new();
+    fn build(&mut self, rng: &mut RandomNumberGenerator) {
 
         // First we completely randomize the map, setting 55% of it to be floor.
         for y in 1..self.map.size.1 - 1 {
@@ -169,5 +186,55 @@ impl CellularAutomataBuilder {
                 }
             }
         }
+
+        self.build_lakes(rng, start_idx);
+    }
+
+    // floods the middle of a couple of the larger areas, leaving a shore so everything stays reachable
+    fn build_lakes(&mut self, rng: &mut RandomNumberGenerator, start_idx: usize) {
+        let mut candidates: Vec<i32> = self
+            .noise_areas
+            .iter()
+            .filter(|(_, area)| area.len() >= LAKE_MIN_SIZE && !area.contains(&start_idx))
+            .map(|(key, _)| *key)
+            .collect();
+
+        while self.lakes.len() < MAX_LAKES && !candidates.is_empty() {
+            let key = candidates.remove(rng.range(0, candidates.len() as i32) as usize);
+            let area: HashSet<usize> = self.noise_areas[&key].iter().copied().collect();
+            let w = self.map.size.0 as usize;
+
+            let lake: Vec<usize> = self.noise_areas[&key]
+                .iter()
+                .filter(|idx| [**idx - 1, **idx + 1, **idx - w, **idx + w].iter().all(|n| area.contains(n)))
+                .copied()
+                .collect();
+            if lake.len() < LAKE_MIN_SIZE / 4 {
+                continue;
+            }
+
+            for idx in lake.iter() {
+                self.map.tiles[*idx] = TileType::Water;
+            }
+
+            // undo the lake if it cuts anything off
+            self.map.set_blocked();
+            let dijkstra_map = rltk::DijkstraMap::new(self.map.size.0, self.map.size.1, &[start_idx], &self.map, 200.0);
+            let cut_off = self.map.tiles.iter().enumerate().any(|(i, tile)| {
+                (*tile == TileType::Floor || *tile == TileType::StairsDown) && dijkstra_map.map[i] == std::f32::MAX
+            });
+            if cut_off {
+                for idx in lake.iter() {
+                    self.map.tiles[*idx] = TileType::Floor;
+                }
+                continue;
+            }
+
+            self.noise_areas.get_mut(&key).unwrap().retain(|idx| !lake.contains(idx));
+            self.lakes.push(lake);
+            self.take_snapshot();
+        }
+
+        self.map.set_blocked();
     }
 }
@@ -1,9 +1,17 @@
 use crate::{utils::rect::Rect, tiles::TileType};
 
 use super::Map;
+use crate::world::components::RNG;
 use rltk::RandomNumberGenerator;
+use shipyard::{UniqueViewMut, World};
 use std::cmp;
 
+/// Builders spawn entities inside `world.run`, so they get their own generator seeded from the world's RNG
+pub fn world_rng(world: &World) -> RandomNumberGenerator {
+    let mut rng = world.borrow::<UniqueViewMut<RNG>>().unwrap();
+    RandomNumberGenerator::seeded(rng.0.next_u64())
+}
+
 pub fn rect_in_bounds(map: &mut Map, room: &Rect) -> bool {
     map.in_bounds((room.x1, room.y1)) && map.in_bounds((room.x2, room.y2))
 }
@@ -34,9 +42,7 @@ pub fn apply_horizontal_corridor(map: &mut Map, x1: i32, x2: i32, y: i32) {
     }
 }
 
-pub fn apply_drunkards_corrider(map: &mut Map, x1: i32, y1: i32, x2: i32, y2: i32) {
-    let mut rng = RandomNumberGenerator::new();
-
+pub fn apply_drunkards_corrider(map: &mut Map, x1: i32, y1: i32, x2: i32, y2: i32, rng: &mut RandomNumberGenerator) {
     let mut x = x1;
     let mut y = y1;
 
@@ -1,5 +1,5 @@
 use crate::world::map::XY;
-use crate::tiles::{TileType, GasType};
+use crate::tiles::TileType;
 use crate::utils::rect::Rect;
 use crate::{entity_factory, SHOW_MAPGEN_ANIMATION};
 use rltk::{Point, RandomNumberGenerator};
@@ -7,7 +7,7 @@ use shipyard::{AllStoragesViewMut, World};
 use std::cmp;
 
 use super::common::apply_drunkards_corrider;
-use super::{Map, MapBuilder, Position};
+use super::{world_rng, Map, MapBuilder, Position};
 
 pub struct DrunkardsBombingRunBuilder {
     map: Map,
@@ -25,12 +25,12 @@ impl MapBuilder for DrunkardsBombingRunBuilder {
         self.starting_position.clone()
     }
 
-    fn build_map(&mut self) {
-        self.rooms_and_corridors(50, 8, 12);
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.rooms_and_corridors(50, 8, 12, rng);
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
-        let mut rng = RandomNumberGenerator::new();
+        let mut rng = world_rng(world);
 
         world.run(|mut store: AllStoragesViewMut| {
             for room in self.rooms.iter().skip(1) {
@@ -46,9 +46,9 @@ impl MapBuilder for DrunkardsBombingRunBuilder {
 
                 if !self.map.is_wall(x, y) &&& self.map.tiles[idx] != &TileType::Water {
                     if i < 3 {
-                        entity_factory::gas_adder(&mut store, (x, y), GasType::Steam);
+                        entity_factory::spawn_named(&mut store, "Steam Vent", (x, y));
                     } else {{
-                        entity_factory::gas_remover(&mut store, (x, y));
+                        entity_factory::spawn_named(&mut store, "Gas Intake", (x, y));
                     }}
                     i += 1;
                 }
@@ -75,9 +75,7 @@ impl DrunkardsBombingRunBuilder {
         }
     }
 
-    fn rooms_and_corridors(&mut self, max_rooms: i32, min_size: i32, max_size: i32) {
-        let mut rng = RandomNumberGenerator::new();
-
+    fn rooms_and_corridors(&mut self, max_rooms: i32, min_size: i32, max_size: i32, rng: &mut RandomNumberGenerator) {
         self.take_snapshot();
         for _ in 0..max_rooms {
             let w: i32 = rng.range(min_size, max_size);
@@ -127,7 +125,7 @@ impl DrunkardsBombingRunBuilder {
             let (x1, y1) = self.rooms[i].center();
             let (x2, y2) = room.center();
 
-            apply_drunkards_corrider(&mut self.map, x1, y1, x2, y2);
+            apply_drunkards_corrider(&mut self.map, x1, y1, x2, y2, rng);
 
             self.take_snapshot();
         }
@@ -145,7 +143,7 @@ impl DrunkardsBombingRunBuilder {
         };
         self.take_snapshot();
 
-        self.bomb_level();
+        self.bomb_level(rng);
         self.take_snapshot();
 
         // Find islands of walls and convert to other features
@@ -199,8 +197,7 @@ impl DrunkardsBombingRunBuilder {
         }
     }
 
-    fn bomb_level(&mut self) {
-        let mut rng = RandomNumberGenerator::new();
+    fn bomb_level(&mut self, rng: &mut RandomNumberGenerator) {
         let mut candidates: Vec<i32> = vec![];
 
         for i in 0..self.map.tiles.len() {
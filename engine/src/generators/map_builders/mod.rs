@@ -25,23 +25,26 @@ use self::village::VillageBuilder;
 mod village_world;
 use self::village_world::VillageWorldBuilder;
 
+mod trench;
+use self::trench::TrenchBuilder;
+
 mod common;
 use common::*;
+use rltk::RandomNumberGenerator;
 use shipyard::World;
 
 use crate::world::components::Position;
 use crate::world::map::{Map, XY};
 
 pub trait MapBuilder {
-    fn build_map(&mut self);
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator);
     fn spawn_entities(&mut self, world: &mut World);
     fn get_map(&mut self) -> Map;
     fn get_starting_position(&mut self) -> Position;
     fn take_snapshot(&mut self);
 }
 
-pub fn random_builder(new_depth: usize, size: XY) -> Box<dyn MapBuilder> {
-    let mut rng = rltk::RandomNumberGenerator::new();
+pub fn random_builder(new_depth: usize, size: XY, rng: &mut RandomNumberGenerator) -> Box<dyn MapBuilder> {
     let builder = rng.roll_dice(1, 7);
     match builder {
         1 => Box::new(BspDungeonBuilder::new(new_depth, size)),
@@ -73,3 +76,21 @@ pub fn arena_builder(new_depth: usize, size: XY) -> Box<dyn MapBuilder> {
 pub fn orc_halls_builder(new_depth: usize, size: XY) -> Box<dyn MapBuilder> {
     Box::new(BspDungeonBuilder::new(new_depth, size))
 }
+
+/// Builder for the `map_builder` name used in game_modes.yml
+pub fn named_builder(name: &str, new_depth: usize, size: XY, rng: &mut RandomNumberGenerator) -> Option<Box<dyn MapBuilder>> {
+    match name {
+        "random" => Some(random_builder(new_depth, size, rng)),
+        "village" => Some(village_builder(new_depth, size)),
+        "village_world" => Some(village_world_builder(new_depth, size)),
+        "drunkards" => Some(rl_builder(new_depth, size)),
+        "arena" => Some(arena_builder(new_depth, size)),
+        "bsp_dungeon" => Some(orc_halls_builder(new_depth, size)),
+        "bsp_interior" => Some(Box::new(BspInteriorBuilder::new(new_depth, size))),
+        "bsp_farm" => Some(Box::new(BspFarmBuilder::new(new_depth, size))),
+        "cellular_automata" => Some(Box::new(CellularAutomataBuilder::new(new_depth, size))),
+        "simple" => Some(Box::new(SimpleMapBuilder::new(new_depth, size))),
+        "trench" => Some(Box::new(TrenchBuilder::new(new_depth, size))),
+        _ => None,
+    }
+}
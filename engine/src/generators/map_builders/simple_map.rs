@@ -23,8 +23,8 @@ impl MapBuilder for SimpleMapBuilder {
         self.starting_position.clone()
     }
 
-    fn build_map(&mut self) {
-        self.rooms_and_corridors(10, 4, 8);
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.rooms_and_corridors(10, 4, 8, rng);
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
@@ -54,9 +54,7 @@ impl SimpleMapBuilder {
         }
     }
 
-    fn rooms_and_corridors(&mut self, max_rooms: i32, min_size: i32, max_size: i32) {
-        let mut rng = RandomNumberGenerator::new();
-
+    fn rooms_and_corridors(&mut self, max_rooms: i32, min_size: i32, max_size: i32, rng: &mut RandomNumberGenerator) {
         self.take_snapshot();
         for _ in 0..max_rooms {
             let w: i32 = rng.range(min_size, max_size);
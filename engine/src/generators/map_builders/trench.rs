@@ -0,0 +1,147 @@
+use rltk::{Point, RandomNumberGenerator};
+use shipyard::{AllStoragesViewMut, UniqueViewMut, World};
+
+use crate::{
+    world::components::{Faction, SpawnerType, War},
+    entity_factory, SHOW_MAPGEN_ANIMATION, tiles::TileType, world::map::XY,
+};
+
+use super::{Map, MapBuilder, Position};
+
+/// Width of no-man's-land between the two front lines
+const NO_MANS_LAND: i32 = 16;
+
+/// How far behind its front line a wizard digs in
+const DUGOUT_DEPTH: i32 = 6;
+
+/// Minions already in the trench when the war starts
+const STARTING_GARRISON: i32 = 6;
+
+const CRATERS: i32 = 12;
+
+/// Two wizards dug in on opposite sides of an arena, each with a front line of trenches and a spawner feeding it minions
+pub struct TrenchBuilder {
+    map: Map,
+    starting_position: Position,
+}
+
+impl MapBuilder for TrenchBuilder {
+    fn get_map(&mut self) -> Map {
+        self.map.clone()
+    }
+
+    fn get_starting_position(&mut self) -> Position {
+        self.starting_position.clone()
+    }
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
+    }
+
+    fn spawn_entities(&mut self, world: &mut World) {
+        world.run(|mut store: AllStoragesViewMut| {
+            let mid = self.map.size.1 / 2;
+
+            for (faction, dir) in [(Faction::Wizard1, 1), (Faction::Wizard2, -1)] {
+                let front = self.front_x(dir);
+
+                // wizards come knowing their spells, see raws/entities.json
+                entity_factory::spawn_for_faction(&mut store, "Wizard", (front - dir * DUGOUT_DEPTH, mid), faction);
+
+                // reinforcements come in from the back of the map
+                let back = if dir > 0 { 2 } else { self.map.size.0 - 3 };
+                entity_factory::spawner(&mut store, (back, mid), faction, SpawnerType::Orc, 15);
+
+                let spacing = (self.map.size.1 - 6) / STARTING_GARRISON;
+                for i in 0..STARTING_GARRISON {
+                    let y = 3 + spacing / 2 + i * spacing;
+                    entity_factory::spawn_for_faction(&mut store, "Orc", (self.trench_x(front, dir, y), y), faction);
+                }
+            }
+
+            store.borrow::<UniqueViewMut<War>>().unwrap().sides = vec![Faction::Wizard1, Faction::Wizard2];
+        });
+    }
+
+    fn take_snapshot(&mut self) {
+        if SHOW_MAPGEN_ANIMATION {
+            self.map.history.push(self.map.tiles.clone());
+        }
+    }
+}
+
+impl TrenchBuilder {
+    pub fn new(_new_depth: usize, size: XY) -> TrenchBuilder {
+        TrenchBuilder {
+            map: Map::new(size),
+            starting_position: Position {
+                ps: vec![Point::new(0, 0)],
+            },
+        }
+    }
+
+    /// Front line of the side facing `dir`, 1 for the west side and -1 for the east
+    fn front_x(&self, dir: i32) -> i32 {
+        self.map.size.0 / 2 - dir * NO_MANS_LAND / 2
+    }
+
+    /// Trenches zig zag so a blast can't run down the whole line
+    fn trench_x(&self, front: i32, dir: i32, y: i32) -> i32 {
+        if (y / 4) % 2 == 0 {
+            front
+        } else {
+            front + dir
+        }
+    }
+
+    fn build(&mut self, rng: &mut RandomNumberGenerator) {
+        // an arena of grass walled in at the edges
+        for y in 0..self.map.size.1 {
+            for x in 0..self.map.size.0 {
+                let idx = self.map.xy_idx((x, y));
+                if x == 0 || y == 0 || x == self.map.size.0 - 1 || y == self.map.size.1 - 1 {
+                    self.map.tiles[idx] = TileType::Wall;
+                } else {
+                    self.map.tiles[idx] = TileType::Grass;
+                }
+            }
+        }
+        self.take_snapshot();
+
+        // no-man's-land is churned up by old shelling
+        for _ in 0..CRATERS {
+            let cx = self.map.size.0 / 2 + rng.range(-NO_MANS_LAND / 2 + 2, NO_MANS_LAND / 2 - 1);
+            let cy = rng.range(3, self.map.size.1 - 3);
+            for x in cx - 1..=cx + 1 {
+                for y in cy - 1..=cy + 1 {
+                    if rng.roll_dice(1, 3) > 1 {
+                        let idx = self.map.xy_idx((x, y));
+                        self.map.tiles[idx] = TileType::Dirt;
+                    }
+                }
+            }
+        }
+        self.take_snapshot();
+
+        let mid = self.map.size.1 / 2;
+        for dir in [1, -1] {
+            let front = self.front_x(dir);
+
+            for y in 3..self.map.size.1 - 3 {
+                let idx = self.map.xy_idx((self.trench_x(front, dir, y), y));
+                self.map.tiles[idx] = TileType::Trench;
+            }
+
+            // a communication trench back to the wizard's dugout
+            for i in 1..=DUGOUT_DEPTH {
+                let idx = self.map.xy_idx((front - dir * i, mid));
+                self.map.tiles[idx] = TileType::Trench;
+            }
+
+            self.take_snapshot();
+        }
+
+        self.starting_position = Position {
+            ps: vec![Point::new(self.map.size.0 / 2, 1)],
+        };
+    }
+}
@@ -3,10 +3,10 @@ use shipyard::{AllStoragesViewMut, World};
 
 use crate::{
     world::components::{Faction, SpawnerType},
-    entity_factory, SHOW_MAPGEN_ANIMATION, tiles::TileType, world::map::XY, ai::labors::{get_actions, AIBehaviors},
+    entity_factory, SHOW_MAPGEN_ANIMATION, tiles::{TileType, CROP_RIPE}, world::map::XY,
 };
 
-use super::{Map, MapBuilder, Position};
+use super::{world_rng, Map, MapBuilder, Position};
 
 pub struct VillageBuilder {
     map: Map,
@@ -21,22 +21,30 @@ impl MapBuilder for VillageBuilder {
     fn get_starting_position(&mut self) -> Position {
         self.starting_position.clone()
     }
-    fn build_map(&mut self) {
-        self.build()
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
-        let mut rng = RandomNumberGenerator::new();
+        let mut rng = world_rng(world);
         world.run(|mut store: AllStoragesViewMut| {
+            // a clearing in the north east woods for the wolves' den
+            let den = (self.map.size.0 - 30, 8);
+
             for y in 1..self.map.size.1 / 2 {
                 for x in 1..self.map.size.0 - 1 {
                     let roll = rng.roll_dice(1, 100);
-                    if roll < 35 {
-                        entity_factory::tree(&mut store, (x, y));
+                    let in_den = (x - den.0).abs() <= 2 && (y - den.1).abs() <= 2;
+                    if roll < 35 && !in_den {
+                        entity_factory::spawn_named(&mut store, "Tree", (x, y));
                     }
                 }
             }
 
+            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (-1, 0)] {
+                entity_factory::spawn_named(&mut store, "Wolf", (den.0 + dx, den.1 + dy));
+            }
+
             entity_factory::spawner(
                 &mut store,
                 (1, self.map.size.1 - 7),
@@ -45,16 +53,24 @@ impl MapBuilder for VillageBuilder {
                 1,
             );
 
+            // a starting stock for the river, it only grows from what's already there
+            for _ in 0..30 {
+                let x = rng.range(2, self.map.size.0 - 1);
+                let y = rng.range(self.map.size.1 - 9, self.map.size.1 - 3);
+                entity_factory::spawn_named(&mut store, "Fish", (x, y));
+            }
+
             for i in 1..=10 {
-                entity_factory::plank_house(&mut store, (20 + 10 * i, self.map.size.1 - 14), 4, 4);
+                entity_factory::spawn_named(&mut store, "Plank House", (20 + 10 * i, self.map.size.1 - 14));
             }
 
-            entity_factory::chief_house(&mut store, (40, self.map.size.1 - 27), 20, 8);
-            entity_factory::lumber_mill(&mut store, (20, self.map.size.1 - 27), 8, 8);
-            entity_factory::fish_cleaner(&mut store, (10, self.map.size.1 - 17), 5, 5);
+            entity_factory::spawn_named(&mut store, "Chief House", (40, self.map.size.1 - 27));
+            entity_factory::spawn_named(&mut store, "Lumber Mill", (20, self.map.size.1 - 27));
+            entity_factory::spawn_named(&mut store, "Fish Cleaner", (10, self.map.size.1 - 17));
+            entity_factory::spawn_named(&mut store, "Granary", (64, self.map.size.1 - 27));
 
             for i in 0..20 {
-                entity_factory::villager(&mut store, (15, self.map.size.1 - 25 - i), &get_actions(&vec![AIBehaviors::GatherWood, AIBehaviors::GatherFish]));
+                entity_factory::spawn_named(&mut store, "Villager", (15, self.map.size.1 - 25 - i));
             }
         });
     }
@@ -76,7 +92,7 @@ impl VillageBuilder {
         }
     }
 
-    fn build(&mut self) {
+    fn build(&mut self, _rng: &mut RandomNumberGenerator) {
         // Set the map to grass with a river
         for y in 1..self.map.size.1 - 1 {
             for x in 1..self.map.size.0 - 1 {
@@ -90,6 +106,15 @@ impl VillageBuilder {
             }
         }
 
+        // a field of wheat ready to harvest
+        for y in self.map.size.1 - 40..self.map.size.1 - 32 {
+            for x in 64..84 {
+                let idx = self.map.xy_idx((x, y));
+                self.map.tiles[idx] = TileType::Wheat;
+                self.map.vegetation[idx] = CROP_RIPE;
+            }
+        }
+
         self.take_snapshot();
 
         self.starting_position = Position {
@@ -1,9 +1,9 @@
 use rltk::{Point, RandomNumberGenerator};
 use shipyard::{AllStoragesViewMut, World};
 
-use crate::{entity_factory, SHOW_MAPGEN_ANIMATION, tiles::TileType, world::map::XY, ai::labors::{AIBehaviors, get_actions}};
+use crate::{entity_factory, SHOW_MAPGEN_ANIMATION, tiles::TileType, world::map::XY};
 
-use super::{Map, MapBuilder, Position};
+use super::{world_rng, Map, MapBuilder, Position};
 
 pub struct VillageWorldBuilder {
     map: Map,
@@ -18,12 +18,12 @@ impl MapBuilder for VillageWorldBuilder {
     fn get_starting_position(&mut self) -> Position {
         self.starting_position.clone()
     }
-    fn build_map(&mut self) {
-        self.build()
+    fn build_map(&mut self, rng: &mut RandomNumberGenerator) {
+        self.build(rng)
     }
 
     fn spawn_entities(&mut self, world: &mut World) {
-        let mut rng = RandomNumberGenerator::new();
+        let mut rng = world_rng(world);
 
         let mut used_idx = vec![];
 
@@ -34,7 +34,7 @@ impl MapBuilder for VillageWorldBuilder {
             if !self.map.is_wall(x, y) && self.map.tiles[idx] != TileType::Water && !used_idx.contains(&idx) {
                 used_idx.push(idx);
                 world.run(|mut store: AllStoragesViewMut| {
-                    entity_factory::villager(&mut store, (x, y), &get_actions(&vec![AIBehaviors::GatherWood, AIBehaviors::GatherFish]));
+                    entity_factory::spawn_named(&mut store, "Villager", (x, y));
                 });
             }
         }
@@ -83,7 +83,7 @@ impl VillageWorldBuilder {
         }
     }
 
-    fn build(&mut self) {
+    fn build(&mut self, rng: &mut RandomNumberGenerator) {
         // Set the map to grass
         for y in 1..self.map.size.1 - 1 {
             for x in 1..self.map.size.0 - 1 {
@@ -105,7 +105,7 @@ impl VillageWorldBuilder {
             // todo test with width multiple of villwidth
             while y <= self.map.size.1 - villsize.1 {
                 let mut villbuilder = super::village_builder(0, villsize);
-                villbuilder.build_map();
+                villbuilder.build_map(rng);
 
                 let map = villbuilder.get_map();
 
@@ -1,9 +1,9 @@
 
-use rand::Rng;
+use rltk::RandomNumberGenerator;
 
 use crate::{world::map::{Map, XY}, tiles::TileType};
 
-pub fn basic_fill(map: &mut Map) {
+pub fn basic_fill(map: &mut Map, rng: &mut RandomNumberGenerator) {
     let size = map.size;
 
     for i in 0..map.tiles.len() {
@@ -11,7 +11,7 @@ pub fn basic_fill(map: &mut Map) {
     }
 
     for i in 0..240 {
-        let start1 = map.xy_idx(rnd_point(size));
+        let start1 = map.xy_idx(rnd_point(size, rng));
         if i % 3 == 0 {
             map.tiles[start1] = TileType::Dirt;
         } else if i % 3 == 1 {
@@ -61,11 +61,8 @@ fn fill_recursive(map: &mut Map, depth: usize) {
     }
 }
 
-fn rnd_point(size: XY) -> XY {
-    let mut rng = rand::thread_rng();
-    let x: f32 = rng.gen();
-    let y: f32 = rng.gen();
-    ((x * size.0 as f32) as i32, (y * size.1 as f32) as i32)
+fn rnd_point(size: XY, rng: &mut RandomNumberGenerator) -> XY {
+    (rng.range(0, size.0), rng.range(0, size.1))
 }
 
 pub fn get_neighbors(point: XY) -> Vec<XY> {
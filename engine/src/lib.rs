@@ -1,17 +1,14 @@
-use config::GameMode;
 use error_iter::ErrorIter as _;
 
 use log::error;
+use serde::{Deserialize, Serialize};
 use pixels::{Pixels, SurfaceTexture};
 use ui::input_handler;
 use winit::{dpi::LogicalSize, event::Event, event_loop::{ControlFlow, EventLoop}, window::{Window, WindowBuilder}};
 use winit_input_helper::WinitInputHelper;
-use world::{Game, GameState};
+use world::{events::GameEvent, Game, GameState};
 
 
-#[macro_use]
-extern crate lazy_static;
-
 pub mod utils;
 pub mod entity_factory;
 pub mod tiles;
@@ -23,6 +20,8 @@ pub mod ui;
 pub mod generators;
 pub mod world;
 pub mod config;
+pub mod raws;
+pub mod simulation;
 
 
 pub const SHOW_MAPGEN_ANIMATION: bool = true;
@@ -44,9 +43,8 @@ const HEIGHT: i32 = 960;
 pub const DISABLE_MAPGEN_ANIMATION: bool = false;
 
 pub const MAIN_MENU_OPTIONS: usize = 2;
-pub const MODE_SELECT_OPTIONS: usize = 3;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum RenderOrder {
     Items = 0,
     NPC,
@@ -54,9 +52,19 @@ pub enum RenderOrder {
     Particle,
 }
 
+/// Options passed in from the command line
+#[derive(Clone, Debug, Default)]
+pub struct EngineOptions {
+    pub mode: Option<String>, // id of the mode to start in, from game_modes.yml
+    pub seed: Option<u64>, // overrides the seed in the mode config
+    pub record: Option<String>, // file to record input commands to
+    pub replay: Option<String>, // file to replay input commands from, the keyboard is ignored
+}
+
 pub trait EngineDelegate {
     fn update(&mut self);
     fn time_advanced(&mut self);
+    fn on_event(&mut self, _event: &GameEvent) {}
 }
 
 pub struct Engine {
@@ -69,7 +77,7 @@ pub struct Engine {
 }
 
 impl Engine {
-    pub fn new(event_loop: &EventLoop<()>, delegate: Box<dyn EngineDelegate>) -> Self {
+    pub fn new(event_loop: &EventLoop<()>, delegate: Box<dyn EngineDelegate>, options: EngineOptions) -> Self {
         env_logger::init();
 
         // create the window
@@ -92,8 +100,21 @@ impl Engine {
             Pixels::new(WIDTH as u32, HEIGHT as u32, surface_texture).unwrap() //todo error check unwrap
         };
     
-        let mut game = Game::new(GameMode::VillageSim);
+        let mode = options.mode.clone().unwrap_or("village_sim".to_string());
+        let mut game = Game::new(&mode, options.seed);
         // game.world_sim.get_log_mut().messages.push("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.".to_string());
+        if let Some(path) = &options.replay {
+            if let Err(e) = game.start_replay(path) {
+                eprintln!("Failed to load replay {}: {}", path, e);
+                std::process::exit(1);
+            }
+        } else if let Some(path) = &options.record {
+            if let Err(e) = game.start_recording(path) {
+                eprintln!("Failed to create recording {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+
         game.screen.setup_consoles();
         game.set_state(GameState::PreTurn);
 
@@ -146,6 +167,10 @@ impl Engine {
             // Update internal state and request a redraw
             self.delegate.update();
             self.game.update();
+            self.game.replay_input();
+            for event in self.game.world_sim.drain_events() {
+                self.delegate.on_event(&event);
+            }
             if self.game.state == GameState::PostTurn {
                 self.delegate.time_advanced();
             }
@@ -154,7 +179,7 @@ impl Engine {
 
         // this should probably come before update
         match event {
-            Event::WindowEvent { event, .. } => {
+            Event::WindowEvent { event, .. } if self.game.replay.is_none() => {
                 let state = input_handler::handle_input(event, &mut self.game);
                 if state != GameState::None {
                     self.game.set_state(state);
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use shipyard::{EntityId, Get, UniqueView, UniqueViewMut, ViewMut, World};
 
@@ -7,7 +7,7 @@ use crate::world::map::Map;
 use crate::tiles::TileType;
 use crate::utils::InvalidPoint;
 
-pub fn get_player_map_knowledge(world: &World) -> HashMap<usize, (TileType, Vec<EntityId>)> {
+pub fn get_player_map_knowledge(world: &World) -> BTreeMap<usize, (TileType, Vec<EntityId>)> {
     let player_id = world.borrow::<UniqueView<PlayerID>>().unwrap().0;
 
     if let Ok(vspace) = world.borrow::<ViewMut<SpatialKnowledge>>() {
@@ -16,7 +16,7 @@ pub fn get_player_map_knowledge(world: &World) -> HashMap<usize, (TileType, Vec<
         }
     }
 
-    HashMap::new()
+    BTreeMap::new()
 }
 
 pub fn get_player_viewshed(world: &World) -> Vision {
@@ -50,15 +50,18 @@ pub fn reveal_map(world: &World) {
     }
 }
 
-pub fn try_next_level(world: &World) -> bool {
+/// Depth the stairs under the player lead to, if there are any
+pub fn try_use_stairs(world: &World) -> Option<usize> {
     let player_pos = world.borrow::<UniqueView<PPoint>>().unwrap().0;
     let map = world.borrow::<UniqueView<Map>>().unwrap();
     let player_idx = map.xy_idx(player_pos.to_xy());
-    if map.tiles[player_idx] == TileType::StairsDown {
-        true
-    } else {
-        let mut log = world.borrow::<UniqueViewMut<GameLog>>().unwrap();
-        log.messages.push(format!("There is no stairs down here"));
-        false
+    match map.tiles[player_idx] {
+        TileType::StairsDown => Some(map.depth + 1),
+        TileType::StairsUp if map.depth > 1 => Some(map.depth - 1),
+        _ => {
+            let mut log = world.borrow::<UniqueViewMut<GameLog>>().unwrap();
+            log.messages.push(format!("There are no stairs here"));
+            None
+        }
     }
 }
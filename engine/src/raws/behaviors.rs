@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use shipyard::Unique;
+
+use crate::ai::{action::Action, labors::AIBehaviors};
+
+/// Named sets of utility AI actions from raws/behaviors.json
+#[derive(Clone, Debug, Default, Unique)]
+pub struct BehaviorProfiles {
+    pub profiles: BTreeMap<String, Vec<Action>>,
+}
+
+impl BehaviorProfiles {
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            profiles: serde_json::from_str(&std::fs::read_to_string(path)?)?,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Vec<Action>> {
+        self.profiles.get(name)
+    }
+
+    /// The idle profile followed by the profile of every behavior, in order
+    pub fn actions_for(&self, behaviors: &[AIBehaviors]) -> Vec<Action> {
+        let mut actions: Vec<Action> = vec![];
+
+        let names = std::iter::once("idle").chain(behaviors.iter().filter_map(|b| b.profile_name()));
+        for name in names {
+            match self.get(name) {
+                Some(profile) => actions.extend(profile.iter().cloned()),
+                None => {
+                    dbg!("Missing behavior profile", name);
+                }
+            }
+        }
+
+        actions
+    }
+}
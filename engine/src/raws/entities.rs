@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::labors::AIBehaviors,
+    tiles::GasType,
+    ui::colors::{Color, COLOR_BG},
+    world::components::{ActorType, EquipmentSlot, Faction, ItemType, Locomotive, StatusEffect},
+    RenderOrder,
+};
+
+/// Layout of raws/entities.json. The lists are only there to keep the file organized
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EntityRaws {
+    #[serde(default)]
+    pub mobs: Vec<EntityRaw>,
+    #[serde(default)]
+    pub items: Vec<EntityRaw>,
+    #[serde(default)]
+    pub props: Vec<EntityRaw>,
+    #[serde(default)]
+    pub spells: Vec<EntityRaw>,
+}
+
+/// One entity definition. Every field but `name` is optional and maps to a component
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntityRaw {
+    pub name: String, // what spawn tables and code refer to
+    pub display_name: Option<String>, // name shown in game, defaults to `name`
+    pub size: Option<[i32; 2]>, // footprint for multi tile entities, anchored at the top left
+
+    // rendering and senses
+    pub renderable: Option<RenderableRaw>,
+    pub vision_range: Option<i32>,
+    pub blocks_tile: bool,
+
+    // actors
+    pub actor: Option<ActorRaw>,
+    pub locomotion: Option<Locomotive>,
+    pub stats: Option<StatsRaw>,
+    pub inventory: Option<i32>, // capacity
+    pub spatial_knowledge: bool,
+    pub dijkstra_map: bool,
+    pub aging: bool,
+    pub needs: bool, // hunger, fatigue and shelter
+    pub orc: bool,
+    pub fish: bool,
+    pub mana: Option<ManaRaw>,
+    pub spellbook: Vec<String>, // names of the spells it knows
+
+    // items
+    pub item: Option<ItemType>,
+    pub consumable: bool,
+    pub healing: Option<i32>,
+    pub damage: Option<i32>,
+    pub ranged: Option<i32>,
+    pub area_of_effect: Option<i32>,
+    pub causes_fire: Option<i32>,
+    pub inflicts_status: Option<StatusEffect>,
+    pub equippable: Option<EquipmentSlot>,
+    pub melee_power: Option<i32>,
+    pub melee_defense: Option<i32>,
+
+    // spells, with the same payload fields as items
+    pub spell: Option<SpellRaw>,
+
+    // props and structures
+    pub flammable: bool,
+    pub tree: bool,
+    pub sapling: bool,
+    pub adds_gas: Option<GasType>,
+    pub removes_gas: bool,
+    pub housing: Option<i32>, // makes a plank house with this many beds
+    pub build_cost: Option<i32>, // logs villagers need to build this
+    pub chief_house: bool,
+    pub lumber_mill: bool,
+    pub fish_cleaner: bool,
+    pub granary: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RenderableRaw {
+    pub glyph: char,
+    pub fg: Color,
+    #[serde(default = "default_bg")]
+    pub bg: Color,
+    pub order: RenderOrder,
+}
+
+fn default_bg() -> Color {
+    COLOR_BG
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActorRaw {
+    pub atype: ActorType,
+    pub faction: Faction,
+    #[serde(default)]
+    pub behaviors: Vec<AIBehaviors>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatsRaw {
+    pub hp: i32,
+    pub defense: i32,
+    pub power: i32,
+    #[serde(default)]
+    pub regen_rate: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManaRaw {
+    pub max: i32,
+    #[serde(default)]
+    pub regen: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpellRaw {
+    pub mana_cost: i32,
+    #[serde(default)]
+    pub cooldown: i32,
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use shipyard::{EntityId, Get, Unique, View};
+
+use crate::world::components::{Actor, Faction};
+
+/// Standing a faction has with itself
+pub const MAX_STANDING: i32 = 100;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Relation {
+    Hostile,
+    Neutral,
+    Allied,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct FactionRaws {
+    hostile_at: i32,
+    allied_at: i32,
+    standings: Vec<StandingRaw>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct StandingRaw {
+    factions: (Faction, Faction),
+    standing: i32,
+}
+
+/// How every faction feels about every other, from raws/factions.json. Pairs that aren't listed have a standing of 0
+#[derive(Clone, Debug, Unique)]
+pub struct FactionTable {
+    pub hostile_at: i32, // standing at or below this is hostile
+    pub allied_at: i32,  // standing at or above this is allied
+    standings: HashMap<(Faction, Faction), i32>,
+}
+
+impl Default for FactionTable {
+    fn default() -> Self {
+        Self {
+            hostile_at: -50,
+            allied_at: 50,
+            standings: HashMap::new(),
+        }
+    }
+}
+
+impl FactionTable {
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let raws: FactionRaws = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+        if raws.hostile_at >= raws.allied_at {
+            return Err("hostile_at must be below allied_at".into());
+        }
+
+        let mut table = Self {
+            hostile_at: raws.hostile_at,
+            allied_at: raws.allied_at,
+            standings: HashMap::new(),
+        };
+        for s in raws.standings {
+            table.set_standing(s.factions.0, s.factions.1, s.standing);
+        }
+
+        Ok(table)
+    }
+
+    /// Standings go both ways
+    pub fn set_standing(&mut self, a: Faction, b: Faction, standing: i32) {
+        self.standings.insert((a, b), standing);
+        self.standings.insert((b, a), standing);
+    }
+
+    pub fn standing(&self, a: Faction, b: Faction) -> i32 {
+        if a == b {
+            return MAX_STANDING;
+        }
+        *self.standings.get(&(a, b)).unwrap_or(&0)
+    }
+
+    pub fn relation(&self, a: Faction, b: Faction) -> Relation {
+        let standing = self.standing(a, b);
+        if standing <= self.hostile_at {
+            Relation::Hostile
+        } else if standing >= self.allied_at {
+            Relation::Allied
+        } else {
+            Relation::Neutral
+        }
+    }
+
+    pub fn is_hostile(&self, a: Faction, b: Faction) -> bool {
+        self.relation(a, b) == Relation::Hostile
+    }
+
+    /// Relation between two entities. Anything without an actor has no faction and is neutral to everyone
+    pub fn relation_between(&self, vactor: &View<Actor>, a: EntityId, b: EntityId) -> Relation {
+        match (vactor.get(a), vactor.get(b)) {
+            (Ok(a), Ok(b)) => self.relation(a.faction, b.faction),
+            _ => Relation::Neutral,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+pub mod behaviors;
+pub mod entities;
+pub mod factions;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use shipyard::Unique;
+
+use self::behaviors::BehaviorProfiles;
+use self::entities::{EntityRaw, EntityRaws};
+use self::factions::FactionTable;
+
+pub const ENTITY_RAWS_PATH: &str = "raws/entities.json";
+pub const BEHAVIOR_RAWS_PATH: &str = "raws/behaviors.json";
+pub const FACTION_RAWS_PATH: &str = "raws/factions.json";
+
+/// Every entity definition from the raw files, looked up by name
+#[derive(Clone, Debug, Default, Unique)]
+pub struct RawMaster {
+    pub entities: Vec<EntityRaw>,
+    index: HashMap<String, usize>,
+}
+
+impl RawMaster {
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let raws: EntityRaws = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+        let mut master = RawMaster::default();
+        for raw in raws.mobs.into_iter().chain(raws.items).chain(raws.props).chain(raws.spells) {
+            if master.index.contains_key(&raw.name) {
+                return Err(format!("Duplicate raw {}", raw.name).into());
+            }
+            master.index.insert(raw.name.clone(), master.entities.len());
+            master.entities.push(raw);
+        }
+
+        Ok(master)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EntityRaw> {
+        self.index.get(name).map(|i| &self.entities[*i])
+    }
+}
+
+/// Loads the raws, or logs and falls back to an empty set so a bad file doesn't take the game down
+pub fn load_raws() -> RawMaster {
+    match RawMaster::load(ENTITY_RAWS_PATH) {
+        Ok(raws) => raws,
+        Err(e) => {
+            dbg!("Failed to load raws", e.to_string());
+            RawMaster::default()
+        }
+    }
+}
+
+pub fn load_behaviors() -> BehaviorProfiles {
+    match BehaviorProfiles::load(BEHAVIOR_RAWS_PATH) {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            dbg!("Failed to load behaviors", e.to_string());
+            BehaviorProfiles::default()
+        }
+    }
+}
+
+pub fn load_factions() -> FactionTable {
+    match FactionTable::load(FACTION_RAWS_PATH) {
+        Ok(table) => table,
+        Err(e) => {
+            dbg!("Failed to load factions", e.to_string());
+            FactionTable::default()
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use shipyard::{Component, Get, IntoIter, UniqueView, UniqueViewMut, View};
+
+use crate::{
+    config::{get_config, GameSettings},
+    world::{
+        components::{FrameTime, GameLog, PhysicalStats, Turn},
+        effects,
+        events::GameEvent,
+        map::Map,
+        systems::system_particle::{self, PARTICLE_TIME},
+    },
+    world_sim::WorldSim,
+};
+
+/// Headless entry point around `WorldSim`. Doesn't need a window, `Screen` or `Assets`,
+/// so it can be used for batch runs and tests.
+pub struct Simulation {
+    pub world_sim: WorldSim,
+}
+
+impl Simulation {
+    pub fn new(mode: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let settings = get_config(mode)?;
+        Ok(Self::from_settings(settings))
+    }
+
+    /// Same as `new` but with a fixed seed, so two runs produce the same world
+    pub fn with_seed(mode: &str, seed: u64) -> Result<Self, Box<dyn std::error::Error>> {
+        let settings = get_config(mode)?.with_seed(Some(seed));
+        Ok(Self::from_settings(settings))
+    }
+
+    pub fn from_settings(settings: GameSettings) -> Self {
+        let mut world_sim = WorldSim::new(settings.clone());
+        world_sim.reset_engine(settings);
+
+        Self { world_sim }
+    }
+
+    /// Advance the world by one turn
+    pub fn step(&mut self) {
+        // there is no frame clock when headless, so particles live for exactly one turn
+        self.world_sim.world.borrow::<UniqueViewMut<FrameTime>>().unwrap().0 = PARTICLE_TIME;
+        self.world_sim.world.run(system_particle::update_particles);
+        self.world_sim.world.run(effects::run_effects_queue);
+
+        self.world_sim.run_turn();
+    }
+
+    pub fn step_n(&mut self, turns: usize) {
+        for _ in 0..turns {
+            self.step();
+        }
+    }
+
+    /// Events published since the last call
+    pub fn drain_events(&self) -> Vec<GameEvent> {
+        self.world_sim.drain_events()
+    }
+
+    pub fn turn(&self) -> i32 {
+        self.world_sim.world.borrow::<UniqueView<Turn>>().unwrap().0
+    }
+
+    pub fn get_map(&self) -> UniqueView<'_, Map> {
+        self.world_sim.get_map()
+    }
+
+    pub fn get_log(&self) -> UniqueView<'_, GameLog> {
+        self.world_sim.get_log()
+    }
+
+    /// Number of entities that have a component of type T
+    pub fn count<T: Component + Send + Sync>(&self) -> usize {
+        self.world_sim.world.borrow::<View<T>>().unwrap().iter().count()
+    }
+
+    pub fn player_dead(&self) -> bool {
+        let player_id = self.world_sim.get_player_id().0;
+        let vstats = self.world_sim.world.borrow::<View<PhysicalStats>>().unwrap();
+
+        match vstats.get(player_id) {
+            Ok(stats) => stats.hp <= 0,
+            Err(_) => false,
+        }
+    }
+}
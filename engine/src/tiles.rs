@@ -17,6 +17,8 @@ pub enum TileType {
     WoodWall,
     WoodDoor,
     WoodFloor,
+    Farmland, // tilled and waiting to be sown
+    Trench, // dug out ground for soldiers to shelter in
 }
 
 pub type TileRenderable = (char, Color, Color);
@@ -37,10 +39,40 @@ impl TileType {
             TileType::WoodWall => ('#', colors::COLOR_DARKEST_AMBER, colors::COLOR_BG),
             TileType::WoodDoor => ('+', colors::COLOR_DARKEST_AMBER, colors::COLOR_BG),
             TileType::WoodFloor => ('.', colors::COLOR_DARKEST_AMBER, colors::COLOR_BG),
+            TileType::Farmland => ('=', colors::COLOR_DARK_SEPIA, colors::COLOR_BG),
+            TileType::Trench => ('░', colors::COLOR_DARK_SEPIA, colors::COLOR_DARKEST_SEPIA),
+        }
+    }
+
+    /// Percent of ranged and blast damage soaked up by standing on this tile
+    pub fn cover(self) -> i32 {
+        match self {
+            TileType::Trench => 50,
+            _ => 0,
+        }
+    }
+
+    /// How much slower it is to step onto this tile than open ground
+    pub fn move_cost(self) -> f32 {
+        match self {
+            TileType::Trench => 1.5,
+            _ => 1.0,
+        }
+    }
+
+    /// What digging leaves behind, the turns of work it takes and the material it turns up
+    pub fn dig(self) -> Option<(TileType, i32, &'static str)> {
+        match self {
+            TileType::Grass | TileType::Dirt | TileType::Sand | TileType::Floor | TileType::Farmland => Some((TileType::Trench, 2, "Dirt")),
+            TileType::Wall => Some((TileType::Floor, 6, "Rubble")),
+            _ => None,
         }
     }
 }
 
+/// Growth a wheat tile needs in `Map.vegetation` before it can be harvested
+pub const CROP_RIPE: i32 = 30;
+
 pub const STABLE_GAS_AMOUNT: usize = 7;
 
 #[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
@@ -1,14 +1,15 @@
 use crate::{
-    config::GameMode, entity_factory, player, utils::{dir_to_offset, dir_to_point, InvalidPoint}, world::{components::{Actor, Inventory, Item, PPoint, PhysicalStats, PlayerID, Position, Ranged, WantsToUseItem}, effects::{add_effect, EffectType}, map::{to_point, Map}, Game, GameState}
+    config::Keymap, player, utils::{dir_to_offset, dir_to_point, InvalidPoint}, world::{components::{Actor, Inventory, Item, PPoint, PhysicalStats, PlayerID, Position, Ranged, Spellbook, Tick, Turn, WantsToUseItem}, effects::{EffectQueue, EffectType}, map::{to_point, Map, XY}, systems::{system_construction, system_energy::{ACTION_COST, QUICK_ACTION_COST}}, Game, GameState}
 };
 use rltk::DistanceAlg;
-use shipyard::{EntityId, Get, UniqueView, UniqueViewMut, View, ViewMut, IntoIter, IntoWithId, AllStoragesViewMut};
+use serde::{Deserialize, Serialize};
+use shipyard::{EntityId, Get, UniqueView, UniqueViewMut, View, ViewMut, IntoIter, IntoWithId};
 use winit::event::{WindowEvent, VirtualKeyCode, ElementState};
 
-use crate::ui::screen::menu_config::{MainMenuSelection, ModeSelectSelection};
+use crate::ui::screen::menu_config::MainMenuSelection;
 
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum InputCommand {
     None,
     Move { dir: i32 },
@@ -18,15 +19,20 @@ pub enum InputCommand {
     Get,
     Explore,
     RevealMap,
-    Fireball,
+    ShowSpells,
     UseStairs,
     Pause,
     Apply, 
     Drop,
+    DesignateBuilding { pos: XY },
+    Dig { pos: XY },
+    Fill { pos: XY }, // shovel dirt back into a trench
 
     //debug
     Reset,
     PrintAIParams,
+    ShowSystems,
+    ReloadBehaviors,
 
     //ui
     ZoomIn,
@@ -36,6 +42,15 @@ pub enum InputCommand {
 }
 
 impl InputCommand {
+    /// Energy the player spends when this command takes their turn
+    pub fn energy_cost(&self) -> i32 {
+        match self {
+            InputCommand::Get | InputCommand::Drop => QUICK_ACTION_COST,
+            InputCommand::RevealMap => 0,
+            _ => ACTION_COST,
+        }
+    }
+
     fn execute(&self, game: &mut Game, creator: Option<EntityId>) -> GameState {
         let world = &game.world_sim.world;
 
@@ -46,19 +61,25 @@ impl InputCommand {
         return match self {
             InputCommand::None => GameState::None,
             InputCommand::Move { dir } => {
-                if game.world_sim.settings.mode == GameMode::MapDemo {
+                if game.world_sim.settings.keymap == Keymap::MapDemo {
                     game.screen.pan_map(dir_to_offset(*dir).to_xy());
                 }
 
                 let updown = if *dir == 8 { -1 } else if *dir == 2 { 1 } else { 0 };
                 match game.state {
                     GameState::MainMenu { selection } => return GameState::MainMenu { selection: selection.modify(updown) },
-                    GameState::ModeSelect { selection } => return GameState::ModeSelect { selection: selection.modify(updown) },
+                    GameState::ModeSelect { selection } => {
+                        let count = game.modes.len() as i32;
+                        if count == 0 {
+                            return GameState::None;
+                        }
+                        return GameState::ModeSelect { selection: (selection as i32 + updown).rem_euclid(count) as usize }
+                    },
                     GameState::ShowInventory { selection } => {
                         let newselection = selection as i32 + updown;
                         if let Ok(inv) = game.world_sim.world.borrow::<View<Inventory>>().unwrap().get(player_id) {
                             if newselection < 0 {
-                                return GameState::ShowInventory { selection: inv.items.len() }
+                                return GameState::ShowInventory { selection: inv.items.len().saturating_sub(1) }
                             } else if newselection as usize >= inv.items.len() {
                                 return GameState::ShowInventory { selection: 0 }
                             } else {
@@ -66,33 +87,43 @@ impl InputCommand {
                             }
                         }
                     },
+                    GameState::ShowSystems { selection } => {
+                        let len = game.world_sim.schedule.systems.len() as i32;
+                        let newselection = (selection as i32 + updown + len) % i32::max(len, 1);
+                        return GameState::ShowSystems { selection: newselection as usize };
+                    },
+                    GameState::ShowSpells { selection } => {
+                        let len = game.world_sim.world.borrow::<View<Spellbook>>().unwrap().get(player_id).map_or(0, |book| book.spells.len()) as i32;
+                        let newselection = (selection as i32 + updown).rem_euclid(i32::max(len, 1));
+                        return GameState::ShowSpells { selection: newselection as usize };
+                    },
                     _ => {},
                 };
 
                 let tile_idx = game.world_sim.get_map().point_idx(dir_to_point(player_pos, *dir, 1));
 
-                add_effect(creator, EffectType::MoveOrAttack {tile_idx});
+                game.world_sim.add_effect(creator, EffectType::MoveOrAttack {tile_idx});
 
                 GameState::PlayerActed
             }
             InputCommand::ShowInventory => GameState::ShowInventory { selection: 0 },
             InputCommand::Wait => {
-                add_effect(creator, EffectType::Wait {}); //todo is this weird on sim mode?
+                game.world_sim.add_effect(creator, EffectType::Wait {}); //todo is this weird on sim mode?
                 GameState::PlayerActed
             }
             InputCommand::Escape => {
                 match game.state {
                     GameState::MainMenu { .. } => GameState::Exit,
-                    GameState::ShowInventory { .. } | GameState::ShowItemActions { .. } | GameState::ShowTargeting { .. } => GameState::PreTurn,
+                    GameState::ShowInventory { .. } | GameState::ShowItemActions { .. } | GameState::ShowTargeting { .. } | GameState::ShowSystems { .. } | GameState::ShowSpells { .. } => GameState::PreTurn,
                     _ => GameState::MainMenu { selection: MainMenuSelection::Play },
                 }
             },
             InputCommand::Get => {
-                world.run(|vitem: View<Item>, map: UniqueView<Map>| {
+                world.run(|mut effects: UniqueViewMut<EffectQueue>, vitem: View<Item>, map: UniqueView<Map>| {
                     let player_pos_idx = map.point_idx(player_pos);
                     for e in map.tile_content[player_pos_idx].iter() {
                         if let Ok(_) = vitem.get(*e) {
-                            add_effect(creator, EffectType::PickUp { entity: *e });
+                            effects.add_effect(creator, EffectType::PickUp { entity: *e });
                         }
                     }
                 });
@@ -103,7 +134,7 @@ impl InputCommand {
                 let item = match game.state {
                     GameState::ShowInventory { selection } => {
                         if let Ok(inv) = world.borrow::<View<Inventory>>().unwrap().get(player_id) {
-                            Some(inv.items[selection])
+                            inv.items.get(selection).copied()
                         } else {
                             None
                         }
@@ -115,13 +146,13 @@ impl InputCommand {
                 };
 
                 if let Some(item) = item {
-                    add_effect(creator, EffectType::Drop { entity: item });
+                    game.world_sim.add_effect(creator, EffectType::Drop { entity: item });
                 }
 
                 GameState::PlayerActed
             },
             InputCommand::Explore => {
-                add_effect(creator, EffectType::Explore {});
+                game.world_sim.add_effect(creator, EffectType::Explore {});
 
                 GameState::PlayerActed
             }
@@ -130,22 +161,42 @@ impl InputCommand {
 
                 GameState::PlayerActed
             }
-            InputCommand::Fireball => {
-                let fireball = entity_factory::tmp_fireball(&mut game.world_sim.world.borrow::<AllStoragesViewMut>().unwrap());
-
-                return GameState::ShowTargeting {
-                    range: 6,
-                    item: fireball,
-                    target: (0,0)
+            InputCommand::ShowSpells => {
+                match game.state {
+                    GameState::ShowSpells { .. } => GameState::PreTurn,
+                    _ => GameState::ShowSpells { selection: 0 },
+                }
+            }
+            InputCommand::DesignateBuilding { pos } => {
+                let msg = match game.world_sim.designate_construction(*pos, system_construction::HOUSE) {
+                    Some(_) => "Marked out a new plank house",
+                    None => "There's no room to build there",
                 };
+                game.world_sim.get_log_mut().messages.push(msg.to_string());
+
+                GameState::None
+            }
+            InputCommand::Dig { pos } | InputCommand::Fill { pos } => {
+                if DistanceAlg::Pythagoras.distance2d(to_point(*pos), player_pos) >= 1.5 {
+                    game.world_sim.get_log_mut().messages.push("That's too far away".to_string());
+                    return GameState::None;
+                }
+
+                let tile_idx = game.world_sim.get_map().xy_idx(*pos);
+                match self {
+                    InputCommand::Fill { .. } => game.world_sim.add_effect(creator, EffectType::Fill { tile_idx }),
+                    _ => game.world_sim.add_effect(creator, EffectType::Dig { tile_idx }),
+                }
+
+                GameState::PlayerActed
             }
             InputCommand::UseStairs => {
-                // if player::try_next_level(&world) {
-                //     GameState::NextLevel
-                // } else {
-                //     GameState::AwaitingInput
-                // }
-                GameState::None
+                if let Some(depth) = player::try_use_stairs(&world) {
+                    game.world_sim.change_depth(depth);
+                    GameState::PlayerActed
+                } else {
+                    GameState::None
+                }
             }
             InputCommand::Reset => {
                 game.reset(None);
@@ -164,17 +215,14 @@ impl InputCommand {
                     GameState::MainMenu { selection } => {
                         match selection {
                             MainMenuSelection::Play => GameState::ShowMapHistory,
-                            MainMenuSelection::ModeSelect => GameState::ModeSelect { selection: ModeSelectSelection::from_repr(0).unwrap() },
+                            MainMenuSelection::ModeSelect => GameState::ModeSelect { selection: 0 },
                             MainMenuSelection::Quit => GameState::Exit,
                         }
                     },
                     GameState::ModeSelect { selection } => {
                         game.screen.reset();
-                        match selection {
-                            ModeSelectSelection::MapDemo => game.reset(Some(GameMode::MapDemo)),
-                            ModeSelectSelection::RL => game.reset(Some(GameMode::RL)),
-                            ModeSelectSelection::VillageSim => game.reset(Some(GameMode::VillageSim)),
-                            ModeSelectSelection::OrcArena => game.reset(Some(GameMode::OrcArena)),
+                        if let Some((id, _)) = game.modes.get(selection).cloned() {
+                            game.reset(Some(id));
                         }
 
                         GameState::ShowMapHistory
@@ -187,6 +235,30 @@ impl InputCommand {
                         }
                         GameState::None
                     },
+                    GameState::ShowSystems { selection } => {
+                        game.world_sim.schedule.toggle(selection);
+                        GameState::ShowSystems { selection }
+                    },
+                    GameState::ShowSpells { selection } => {
+                        let spell = match world.borrow::<View<Spellbook>>().unwrap().get(player_id) {
+                            Ok(book) => book.spells.get(selection).copied(),
+                            Err(_) => None,
+                        };
+
+                        // ranged spells need a target, the rest are cast on yourself
+                        if let Some(spell) = spell {
+                            let range = world.borrow::<View<Ranged>>().unwrap().get(spell).map(|r| r.range);
+                            match range {
+                                Ok(range) => return GameState::ShowTargeting { range, item: spell, target: (0, 0) },
+                                Err(_) => {
+                                    game.world_sim.world.add_component(player_id, WantsToUseItem { item: spell, target: None });
+                                    return GameState::PlayerActed;
+                                }
+                            }
+                        }
+
+                        GameState::None
+                    },
                     GameState::ShowTargeting { range, item, target } => {
                         // if target is valid use item
                         if DistanceAlg::Pythagoras.distance2d(to_point(target), player_pos) < range as f32 {
@@ -207,7 +279,7 @@ impl InputCommand {
                 let item = match game.state {
                     GameState::ShowInventory { selection } => {
                         if let Ok(inv) = world.borrow::<View<Inventory>>().unwrap().get(player_id) {
-                            Some(inv.items[selection])
+                            inv.items.get(selection).copied()
                         } else {
                             None
                         }
@@ -280,6 +352,12 @@ impl InputCommand {
                     _ => game.state
                 }
             },
+            InputCommand::ShowSystems => {
+                match game.state {
+                    GameState::ShowSystems { .. } => GameState::PreTurn,
+                    _ => GameState::ShowSystems { selection: 0 },
+                }
+            },
             InputCommand::PrintAIParams => {
                 let vactor = game.world_sim.world.borrow::<ViewMut<Actor>>().unwrap();
                 println!("=======================================");
@@ -291,6 +369,15 @@ impl InputCommand {
                 }
                 GameState::None
             },
+            InputCommand::ReloadBehaviors => {
+                match game.world_sim.reload_behaviors() {
+                    Ok(_) => game.world_sim.get_log_mut().messages.push("Reloaded behaviors".to_string()),
+                    Err(e) => {
+                        dbg!("Failed to reload behaviors", e.to_string());
+                    }
+                }
+                GameState::None
+            },
         };
     }
 }
@@ -315,10 +402,12 @@ pub fn map_keys(event: WindowEvent, game: &Game) -> InputCommand {
                     VirtualKeyCode::Right => InputCommand::Move { dir: 6 },
                     VirtualKeyCode::Up => InputCommand::Move { dir: 8 },
                     VirtualKeyCode::Down => InputCommand::Move { dir: 2 },
-                    VirtualKeyCode::F => InputCommand::Fireball,
+                    VirtualKeyCode::F => InputCommand::ShowSpells,
                     VirtualKeyCode::W => InputCommand::Wait,
                     VirtualKeyCode::R => InputCommand::Reset,
                     VirtualKeyCode::P => InputCommand::PrintAIParams,
+                    VirtualKeyCode::F1 => InputCommand::ShowSystems,
+                    VirtualKeyCode::F5 => InputCommand::ReloadBehaviors,
                     VirtualKeyCode::Return => InputCommand::Enter,
                     VirtualKeyCode::NumpadEnter => InputCommand::Enter,
                     VirtualKeyCode::Escape => InputCommand::Escape,
@@ -330,8 +419,8 @@ pub fn map_keys(event: WindowEvent, game: &Game) -> InputCommand {
             };
     
             if cmd == InputCommand::None {
-                cmd = match game.world_sim.settings.mode {
-                    GameMode::RL | GameMode::OrcHalls => match input.virtual_keycode {
+                cmd = match game.world_sim.settings.keymap {
+                    Keymap::Roguelike => match input.virtual_keycode {
                         None => InputCommand::None,
                         Some(key) => match key {
                             VirtualKeyCode::G => InputCommand::Get,
@@ -340,14 +429,18 @@ pub fn map_keys(event: WindowEvent, game: &Game) -> InputCommand {
                             VirtualKeyCode::I => InputCommand::ShowInventory,
                             VirtualKeyCode::A => InputCommand::Apply,
                             VirtualKeyCode::D => InputCommand::Drop,
+                            VirtualKeyCode::Period => InputCommand::UseStairs,
+                            VirtualKeyCode::T => InputCommand::Dig { pos: game.screen.get_mouse_game_pos() },
+                            VirtualKeyCode::Y => InputCommand::Fill { pos: game.screen.get_mouse_game_pos() },
                             VirtualKeyCode::Escape => InputCommand::Escape,
                             _ => InputCommand::None,
                         },
                     },
-                    GameMode::VillageSim => match input.virtual_keycode {
+                    Keymap::Village => match input.virtual_keycode {
                         None => InputCommand::None,
                         Some(key) => match key {
                             VirtualKeyCode::Space => InputCommand::Pause,
+                            VirtualKeyCode::B => InputCommand::DesignateBuilding { pos: game.screen.get_mouse_game_pos() },
                             _ => InputCommand::None,
                         }
                     },
@@ -363,9 +456,27 @@ pub fn map_keys(event: WindowEvent, game: &Game) -> InputCommand {
 }
 
 pub fn handle_input(event: WindowEvent, game: &mut Game) -> GameState {
+    let command = map_keys(event, game);
+
+    run_command(command, game)
+}
+
+/// Executes a command for the player, recording it if a recorder is attached
+pub fn run_command(command: InputCommand, game: &mut Game) -> GameState {
     let player_id = game.world_sim.world.borrow::<UniqueViewMut<PlayerID>>().unwrap().0;
 
-    let command = map_keys(event, game);
+    if command != InputCommand::None {
+        if let Some(recorder) = &mut game.recorder {
+            let tick = game.world_sim.world.borrow::<UniqueView<Tick>>().unwrap().0;
+            let turn = game.world_sim.world.borrow::<UniqueView<Turn>>().unwrap().0;
+            recorder.record(tick, turn, command);
+        }
+    }
+
+    let state = command.execute(game, Some(player_id));
+    if state == GameState::PlayerActed {
+        game.world_sim.spend_energy(player_id, command.energy_cost());
+    }
 
-    return command.execute(game, Some(player_id));
+    return state;
 }
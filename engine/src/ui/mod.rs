@@ -1,4 +1,5 @@
 pub mod assets;
 pub mod screen;
 pub mod input_handler;
+pub mod replay;
 pub mod colors;
\ No newline at end of file
@@ -0,0 +1,114 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::GameSettings,
+    ui::input_handler::InputCommand,
+};
+
+/// One line of a replay file. A `Start` is written every time the world is reset.
+/// Inputs are keyed on the world's `Tick`, so replays don't depend on how fast frames were drawn
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReplayLine {
+    Start { seed: u64, mode: String },
+    Input { tick: i32, turn: i32, command: InputCommand },
+}
+
+/// Writes commands as json lines, flushed one at a time so a crash still leaves a usable file
+pub struct Recorder {
+    writer: BufWriter<File>,
+}
+
+impl Recorder {
+    pub fn create(path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    pub fn start(&mut self, settings: &GameSettings) {
+        self.write(&ReplayLine::Start {
+            seed: settings.seed,
+            mode: settings.mode.clone(),
+        });
+    }
+
+    pub fn record(&mut self, tick: i32, turn: i32, command: InputCommand) {
+        self.write(&ReplayLine::Input { tick, turn, command });
+    }
+
+    fn write(&mut self, line: &ReplayLine) {
+        match serde_json::to_string(line) {
+            Ok(s) => {
+                if let Err(e) = writeln!(self.writer, "{}", s).and_then(|_| self.writer.flush()) {
+                    dbg!("Failed to write replay", e);
+                }
+            }
+            Err(e) => {
+                dbg!("Failed to serialize replay line", e);
+            }
+        }
+    }
+}
+
+pub struct Replay {
+    pub lines: VecDeque<ReplayLine>,
+}
+
+impl Replay {
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = VecDeque::new();
+
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            lines.push_back(serde_json::from_str(&line)?);
+        }
+
+        Ok(Self { lines })
+    }
+
+    /// Mode of the first world in the replay
+    pub fn mode(&self) -> Option<String> {
+        match self.lines.front() {
+            Some(ReplayLine::Start { mode, .. }) => Some(mode.clone()),
+            _ => None,
+        }
+    }
+
+    /// Pops the next `Start` if the world is being reset
+    pub fn next_start(&mut self) -> Option<(u64, String)> {
+        match self.lines.front() {
+            Some(ReplayLine::Start { seed, mode }) => {
+                let start = (*seed, mode.clone());
+                self.lines.pop_front();
+                Some(start)
+            }
+            _ => None,
+        }
+    }
+
+    /// Pops the next command if it was entered on or before this world tick
+    pub fn next_command(&mut self, tick: i32) -> Option<(i32, InputCommand)> {
+        match self.lines.front() {
+            Some(ReplayLine::Input { tick: t, turn, command }) if *t <= tick => {
+                let next = (*turn, *command);
+                self.lines.pop_front();
+                Some(next)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
@@ -1,13 +1,13 @@
 use std::iter::zip;
 
-use crate::{ai::intent::Intent, player::get_player_map_knowledge, ui::colors::{self, Color}, utils::InvalidPoint, world::{components::{Consumable, Equipment, Equippable, FrameTime, Inventory, Name, OnFire, PPoint, PhysicalStats, PlayerID, Position, Turn, Vision}, map::{Map, XY}, Game, GameState}};
+use crate::{ai::intent::Intent, player::get_player_map_knowledge, ui::colors::{self, Color}, utils::InvalidPoint, world::{components::{ConstructionSite, Consumable, Equipment, Equippable, FrameTime, Inventory, Mana, Name, Needs, PPoint, PhysicalStats, PlayerID, Position, Spell, Spellbook, StatusEffects, StatusKind, Turn, Vision}, map::{Map, XY}, Game, GameState}};
 use rltk::Point;
 use shipyard::{UniqueView, View, Get, World, IntoIter, IntoWithId};
 use strum::EnumCount;
 
 use crate::{WIDTH, ui::assets::{cp437_converter::{to_cp437, string_to_cp437}, Assets, sprites::Drawable}, HEIGHT, ui::screen::RangedTargetResult};
 
-use super::{Glyph, menu_config::{MainMenuSelection, ModeSelectSelection}, MAX_ZOOM};
+use super::{Glyph, menu_config::MainMenuSelection, MAX_ZOOM};
 
 #[derive(Debug, PartialEq)]
 pub enum ConsoleMode {
@@ -18,6 +18,8 @@ pub enum ConsoleMode {
     Context,
     Inventory,
     ItemInfo,
+    Systems,
+    Spells,
 }
 
 // todo add a way to dim a console, for overlay purposes
@@ -96,6 +98,12 @@ impl Console {
             ConsoleMode::ItemInfo => {
                 self.render_item_info(frame, game);
             },
+            ConsoleMode::Systems => {
+                self.render_systems(frame, game);
+            },
+            ConsoleMode::Spells => {
+                self.render_spells(frame, game);
+            },
         }
     }
 
@@ -170,19 +178,17 @@ impl Console {
 
             y += 2 * self.gsize;
 
-            for i in 0..=ModeSelectSelection::COUNT {
-                if let Some(opt) = ModeSelectSelection::from_repr(i) {
-                    self.print_string(
-                        &game.assets,
-                        frame,
-                        &opt.to_string(),
-                        (x, y),
-                        if selection as usize == i { colors::COLOR_UI_3 } else { colors::COLOR_UI_2 },
-                        self.gsize
-                    );
-        
-                    y += self.gsize;
-                }
+            for (i, (_, name)) in game.modes.iter().enumerate() {
+                self.print_string(
+                    &game.assets,
+                    frame,
+                    name,
+                    (x, y),
+                    if selection == i { colors::COLOR_UI_3 } else { colors::COLOR_UI_2 },
+                    self.gsize
+                );
+
+                y += self.gsize;
             }
         }
     }
@@ -339,17 +345,33 @@ impl Console {
             }
         }
 
-        if let Ok(vonfire) = game.world_sim.world.borrow::<View<OnFire>>() {
-            if let Ok(fire) = vonfire.get(player_id) {
+        if let Ok(vmana) = game.world_sim.world.borrow::<View<Mana>>() {
+            if let Ok(mana) = vmana.get(player_id) {
                 self.print_string(
                     &game.assets,
                     frame,
-                    &format!("FIRE {}", fire.turns),
+                    &format!("MP: {}/{}", mana.current, mana.max),
                     (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
-                    colors::COLOR_FIRE,
+                    colors::COLOR_UI_2,
                     self.gsize
                 );
-                // y += 1;
+                y += 1;
+            }
+        }
+
+        if let Ok(vstatus) = game.world_sim.world.borrow::<View<StatusEffects>>() {
+            if let Ok(statuses) = vstatus.get(player_id) {
+                for status in statuses.effects.iter() {
+                    self.print_string(
+                        &game.assets,
+                        frame,
+                        &format!("{} {}", status.kind.name().to_uppercase(), status.turns),
+                        (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
+                        status_color(status.kind),
+                        self.gsize
+                    );
+                    y += 1;
+                }
             }
         }
     }
@@ -358,7 +380,7 @@ impl Console {
         let screen = &game.screen;
         let world = &game.world_sim.world;
         let map = world.borrow::<UniqueView<Map>>().unwrap();
-        let settings = game.world_sim.settings;
+        let settings = &game.world_sim.settings;
 
         self.draw_box(
             &game.assets,
@@ -389,7 +411,9 @@ impl Console {
         let vstats = world.borrow::<View<PhysicalStats>>().unwrap();
         let vinv = world.borrow::<View<Inventory>>().unwrap();
         let vintent = world.borrow::<View<Intent>>().unwrap();
-        let vonfire = world.borrow::<View<OnFire>>().unwrap();
+        let vstatus = world.borrow::<View<StatusEffects>>().unwrap();
+        let vneeds = world.borrow::<View<Needs>>().unwrap();
+        let vsite = world.borrow::<View<ConstructionSite>>().unwrap();
         
         /* Debug stuff */
         self.print_string(
@@ -504,14 +528,40 @@ impl Console {
                 );
             }
 
-            if let Ok(fire) = vonfire.get(*e) {
+            if let Ok(statuses) = vstatus.get(*e) {
+                for status in statuses.effects.iter() {
+                    y += 1;
+                    self.print_string(
+                        &game.assets,
+                        frame,
+                        &format!(" {} {}", status.kind.name().to_uppercase(), status.turns),
+                        (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
+                        status_color(status.kind),
+                        self.gsize
+                    );
+                }
+            }
+
+            if let Ok(needs) = vneeds.get(*e) {
                 y += 1;
                 self.print_string(
                     &game.assets,
                     frame,
-                    &format!("FIRE {}", fire.turns),
+                    &format!(" Hunger {} Fatigue {} Shelter {}", needs.hunger, needs.fatigue, needs.shelter),
                     (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
-                    colors::COLOR_FIRE,
+                    colors::COLOR_UI_2,
+                    self.gsize
+                );
+            }
+    
+            if let Ok(site) = vsite.get(*e) {
+                y += 1;
+                self.print_string(
+                    &game.assets,
+                    frame,
+                    &format!(" Logs {}/{}", site.logs_delivered, site.logs_needed),
+                    (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
+                    colors::COLOR_UI_2,
                     self.gsize
                 );
             }
@@ -664,6 +714,114 @@ impl Console {
         }
     }
 
+    pub fn render_systems(&self, frame: &mut [u8], game: &Game) {
+        if let GameState::ShowSystems { selection } = game.state {
+            let mut y = 1;
+            let mut total = 0.;
+
+            for (i, system) in game.world_sim.schedule.systems.iter().enumerate() {
+                let color = if i == selection {
+                    colors::COLOR_UI_3
+                } else if system.enabled {
+                    colors::COLOR_UI_2
+                } else {
+                    colors::COLOR_GREY
+                };
+
+                self.print_string(
+                    &game.assets,
+                    frame,
+                    &format!("{} {:<16}{:>6.2}ms", if system.enabled { "+" } else { "-" }, system.name, system.avg_ms),
+                    (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
+                    color,
+                    self.gsize
+                );
+
+                if system.enabled {
+                    total += system.avg_ms;
+                }
+                y += 1;
+            }
+
+            y += 1;
+            self.print_string(
+                &game.assets,
+                frame,
+                &format!("  {:<16}{:>6.2}ms", "total", total),
+                (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
+                colors::COLOR_UI_4,
+                self.gsize
+            );
+
+            y += 2;
+            self.draw_box(
+                &game.assets,
+                frame,
+                (self.pos.0, self.pos.1),
+                (self.size.0, y * self.gsize),
+                colors::COLOR_UI_1,
+                colors::COLOR_BG,
+                self.gsize,
+                "Systems".to_string()
+            );
+        }
+    }
+
+    pub fn render_spells(&self, frame: &mut [u8], game: &Game) {
+        let player_id = game.world_sim.get_player_id().0;
+        let vbook = game.world_sim.world.borrow::<View<Spellbook>>().unwrap();
+        let vspell = game.world_sim.world.borrow::<View<Spell>>().unwrap();
+        let vmana = game.world_sim.world.borrow::<View<Mana>>().unwrap();
+        let vname = game.world_sim.world.borrow::<View<Name>>().unwrap();
+
+        if let GameState::ShowSpells { selection } = game.state {
+            let mut y = 1;
+
+            if let (Ok(book), Ok(mana)) = (vbook.get(player_id), vmana.get(player_id)) {
+                for (i, id) in book.spells.iter().enumerate() {
+                    if let (Ok(spell), Ok(name)) = (vspell.get(*id), vname.get(*id)) {
+                        let color = if i == selection {
+                            colors::COLOR_UI_3
+                        } else if spell.castable(mana) {
+                            colors::COLOR_UI_2
+                        } else {
+                            colors::COLOR_GREY
+                        };
+
+                        // cooldown shows instead of cost while the spell recharges
+                        let cost = if spell.cooldown_left > 0 {
+                            format!("{}t", spell.cooldown_left)
+                        } else {
+                            format!("{}mp", spell.mana_cost)
+                        };
+
+                        self.print_string(
+                            &game.assets,
+                            frame,
+                            &format!("- {:<18}{:>5}", name.name, cost),
+                            (self.pos.0 + self.gsize, self.pos.1 + y * self.gsize),
+                            color,
+                            self.gsize
+                        );
+                        y += 1;
+                    }
+                }
+            }
+
+            y += 1;
+            self.draw_box(
+                &game.assets,
+                frame,
+                (self.pos.0, self.pos.1),
+                (self.size.0, y * self.gsize),
+                colors::COLOR_UI_1,
+                colors::COLOR_BG,
+                self.gsize,
+                "Spells".to_string()
+            );
+        }
+    }
+
     pub fn render_item_info(&self, frame: &mut [u8], game: &Game) {
         let vname = game.world_sim.world.borrow::<View<Name>>().unwrap();
         let vequip = game.world_sim.world.borrow::<View<Equippable>>().unwrap();
@@ -968,3 +1126,11 @@ impl Console {
         (RangedTargetResult::NoResponse, None)
     }
 }
+
+fn status_color(kind: StatusKind) -> Color {
+    match kind {
+        StatusKind::Burning => colors::COLOR_FIRE,
+        StatusKind::Poisoned => colors::COLOR_CHARTREUSE,
+        _ => colors::COLOR_UI_3,
+    }
+}
@@ -102,6 +102,20 @@ impl Screen {
         let xmenu = self.size.0/2 - wmenu/2;
         let ymenu = hinfo;
         self.consoles.push(Console::new((wmenu, hmenu), (xmenu, ymenu), ConsoleMode::ItemInfo, gsize));
+
+        // systems debug console
+        let wmenu = gsize * 30;
+        let hmenu = gsize * 30;
+        let xmenu = self.size.0 - wmenu - gsize;
+        let ymenu = hinfo;
+        self.consoles.push(Console::new((wmenu, hmenu), (xmenu, ymenu), ConsoleMode::Systems, gsize));
+
+        // spell menu console
+        let wmenu = gsize * 30;
+        let hmenu = gsize * 20;
+        let xmenu = self.size.0 - wmenu - gsize;
+        let ymenu = hinfo;
+        self.consoles.push(Console::new((wmenu, hmenu), (xmenu, ymenu), ConsoleMode::Spells, gsize));
     }
 
     pub fn reset(&mut self) {
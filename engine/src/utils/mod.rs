@@ -1,4 +1,4 @@
-use rltk::{BaseMap, DijkstraMap, Point, RGBA};
+use rltk::{BaseMap, DijkstraMap, Point, RandomNumberGenerator, RGBA};
 use shipyard::{EntityId, View, Get};
 use crate::{world::map::{Map, XY}, world::components::Position};
 
@@ -98,6 +98,24 @@ pub fn get_neighbors(point: Point) -> Vec<Point> {
     ]
 }
 
+/// Open neighbouring tile furthest from `threat`, `from` itself if there's nowhere better
+pub fn away_from(map: &Map, from: Point, threat: Point) -> Point {
+    let mut best = (from, rltk::DistanceAlg::Pythagoras.distance2d(from, threat));
+
+    for p in get_neighbors(from) {
+        if !map.in_bounds((p.x, p.y)) || map.blocked[map.point_idx(p)] {
+            continue;
+        }
+
+        let dist = rltk::DistanceAlg::Pythagoras.distance2d(p, threat);
+        if dist > best.1 {
+            best = (p, dist);
+        }
+    }
+
+    best.0
+}
+
 // translates dir according to roguelike numpad convention - 1 is SW, 9 is NE
 // pub fn get_movement(point: Point, dir: usize, movemod: i32) -> Point {
 //     match dir {
@@ -238,3 +256,11 @@ pub fn normalize(num: i32) -> i32 {
         num / num.abs()
     }
 }
+
+// Fisher-Yates shuffle driven by the world rng so runs are reproducible
+pub fn shuffle<T>(v: &mut Vec<T>, rng: &mut RandomNumberGenerator) {
+    for i in (1..v.len()).rev() {
+        let j = rng.range(0, i as i32 + 1) as usize;
+        v.swap(i, j);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use rltk::{self, DijkstraMap, Point};
 use serde::{Deserialize, Serialize};
@@ -7,7 +7,7 @@ use strum::IntoEnumIterator; // 0.17.1
 use strum_macros::EnumIter; // 0.17.1
 
 use crate::{
-    ai::{action::Action, input::InputTargets, labors::AIBehaviors}, world::map::Map, tiles::{GasType, TileType}, ui::colors::{Color, COLOR_BG}, utils::Target, RenderOrder
+    ai::{action::Action, input::InputTargets, labors::AIBehaviors}, config::SpawnEntry, raws::factions::{FactionTable, Relation}, world::{map::Map, systems::system_farming}, tiles::{GasType, TileType}, ui::colors::{Color, COLOR_BG}, utils::Target, RenderOrder
 };
 
 /// Unique components
@@ -23,6 +23,10 @@ pub struct PlayerID(pub EntityId);
 #[derive(Clone, Debug, Unique, Copy)]
 pub struct Turn(pub i32);
 
+/// Number of times the schedule has run. There are several ticks to a turn, see `system_energy`
+#[derive(Clone, Debug, Unique, Copy)]
+pub struct Tick(pub i32);
+
 #[derive(Clone, Unique)]
 pub struct RNG(pub rltk::RandomNumberGenerator);
 
@@ -32,6 +36,30 @@ pub struct PPoint(pub Point);
 #[derive(Clone, Debug, Unique, Copy)]
 pub struct FrameTime(pub f32);
 
+/// Spawn table of the current game mode, used by map builders to place monsters and items
+#[derive(Clone, Debug, Default, Unique)]
+pub struct SpawnTable(pub Vec<SpawnEntry>);
+
+/// Village wide ledger of the items stored in structures, recounted every tick by `system_stockpile`
+#[derive(Clone, Debug, Default, Unique)]
+pub struct Stockpile {
+    pub counts: BTreeMap<ItemType, i32>,
+}
+
+impl Stockpile {
+    pub fn count(&self, typ: ItemType) -> i32 {
+        *self.counts.get(&typ).unwrap_or(&0)
+    }
+}
+
+/// The wizards fighting it out in trench war, empty in every other mode. Settled once a side has lost its wizard
+#[derive(Clone, Debug, Default, Unique, Serialize, Deserialize)]
+pub struct War {
+    pub sides: Vec<Faction>,
+    pub over: bool,
+    pub winner: Option<Faction>, // stays none if both wizards died
+}
+
 /// Basic UI components
 
 #[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -53,7 +81,7 @@ impl Position {
     }
 }
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Renderable {
     pub glyph: char,
     pub fg: Color,
@@ -76,7 +104,7 @@ impl Default for Renderable {
     }
 }
 
-#[derive(Component, Clone, Debug, PartialEq)]
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Vision {
     pub visible_tiles: Vec<rltk::Point>,
     pub range: i32,
@@ -95,23 +123,27 @@ impl Vision {
     }
 }
 
-#[derive(Component, Clone, Debug, PartialEq)]
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Name {
     pub name: String,
 }
 
 /// Entity properties
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Player {}
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Orc {}
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+/// Part of a raid, marches on the village when there's no one to fight
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Raider {}
+
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Fish {}
 
-#[derive(Component, Clone, Debug, PartialEq)]
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Actor { 
     pub atype: ActorType,
     pub faction: Faction,
@@ -120,7 +152,7 @@ pub struct Actor {
     pub score: i32, // actors score points for executing behaviors
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Faction {
     Nuetral,
     Nature,
@@ -131,7 +163,7 @@ pub enum Faction {
     Wizard2,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ActorType {
     Player,
     Orc,
@@ -139,6 +171,7 @@ pub enum ActorType {
     Fish,
     Spawner,
     Wolf,
+    Wizard,
 }
 
 /// Structures
@@ -149,48 +182,78 @@ pub struct PlankHouse {
     pub villagers: Vec<EntityId>,
 }
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChiefHouse {}
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+/// A building going up. Villagers haul logs into its inventory and `system_construction` builds with them
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConstructionSite {
+    pub building: String, // raw name of what this turns into
+    pub logs_needed: i32,
+    pub logs_delivered: i32,
+    pub tiles_built: i32,
+}
+
+impl ConstructionSite {
+    pub fn is_supplied(&self) -> bool {
+        self.logs_delivered >= self.logs_needed
+    }
+}
+
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct LumberMill {}
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FishCleaner {}
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Granary {}
+
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SpawnerType {
     Orc,
     Fish,
 }
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Spawner {
     pub typ: SpawnerType,
     pub rate: i32,
 }
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Tree {}
 
+/// Young tree, `system_ecology` swaps it for a `Tree` once it's old enough
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sapling {
+    pub age: i32,
+}
+
 /// Entity properties
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum LocomotionType {
     Ground,
     Water,
 }
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Locomotive {
     pub mtype: LocomotionType,
-    pub speed: i32,
+    pub speed: i32, // energy gained per tick
+}
+
+/// Entities act once they have saved up enough energy, and acting spends it
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Energy {
+    pub value: i32,
 }
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BlocksTile {}
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PhysicalStats {
     pub max_hp: i32,
     pub hp: i32,
@@ -201,16 +264,52 @@ pub struct PhysicalStats {
 
 #[derive(Component, Clone, Debug, PartialEq)]
 pub struct SpatialKnowledge {
-    pub tiles: HashMap<usize, (TileType, Vec<EntityId>)>,
+    pub tiles: BTreeMap<usize, (TileType, Vec<EntityId>)>,
 }
 
 impl SpatialKnowledge {
-    pub fn get_targets(&self, store: &AllStorages, target: InputTargets) -> Vec<Target> {
+    pub fn get_targets(&self, store: &AllStorages, owner: EntityId, target: InputTargets) -> Vec<Target> {
         let map = store.borrow::<UniqueView<Map>>().unwrap();
+        let factions = store.borrow::<UniqueView<FactionTable>>().unwrap();
 
         let mut targets = vec![];
 
         for (idx, (tile, entities)) in self.tiles.iter() {
+            // these are about the ground rather than what's on it
+            match target {
+                InputTargets::Farmland => {
+                    if *tile == TileType::Farmland {
+                        targets.push(Target::from(map.idx_point(*idx)));
+                    }
+                },
+                InputTargets::RipeCrop => {
+                    if system_farming::is_ripe(&map, *idx) {
+                        targets.push(Target::from(map.idx_point(*idx)));
+                    }
+                },
+                InputTargets::Soil => {
+                    if system_farming::can_till(&map, *idx) && system_farming::borders_field(store, &map, *idx) {
+                        targets.push(Target::from(map.idx_point(*idx)));
+                    }
+                },
+                InputTargets::Ground => {
+                    if tile.dig().map_or(false, |(dug, _, _)| dug == TileType::Trench) {
+                        targets.push(Target::from(map.idx_point(*idx)));
+                    }
+                },
+                InputTargets::Wall => {
+                    if *tile == TileType::Wall {
+                        targets.push(Target::from(map.idx_point(*idx)));
+                    }
+                },
+                InputTargets::Trench => {
+                    if *tile == TileType::Trench {
+                        targets.push(Target::from(map.idx_point(*idx)));
+                    }
+                },
+                _ => {}
+            }
+
             for id in entities {
                 match target {
                     InputTargets::Tree => {
@@ -252,7 +351,15 @@ impl SpatialKnowledge {
                             }
                         }
                     },
-                    InputTargets::Enemy => todo!(),
+                    InputTargets::Enemy => {
+                        let vactor = store.borrow::<View<Actor>>().unwrap();
+                        if *id != owner && factions.relation_between(&vactor, owner, *id) == Relation::Hostile {
+                            let t = Target::from(*id);
+                            if !targets.contains(&t) {
+                                targets.push(t);
+                            }
+                        }
+                    },
                     InputTargets::Fish => {
                         if let Ok(_) = store.borrow::<View<Fish>>().unwrap().get(*id){
                             let t = Target::from(*id);
@@ -270,6 +377,66 @@ impl SpatialKnowledge {
                         }
                     },
                     InputTargets::None => { },
+                    InputTargets::Home => {
+                        if let Ok(house) = store.borrow::<View<PlankHouse>>().unwrap().get(*id){
+                            let t = Target::from(*id);
+                            if house.villagers.contains(&owner) && !targets.contains(&t) {
+                                targets.push(t);
+                            }
+                        }
+                    },
+                    InputTargets::House => {
+                        let t = Target::from(*id);
+                        let is_house = store.borrow::<View<PlankHouse>>().unwrap().get(*id).is_ok() || store.borrow::<View<ChiefHouse>>().unwrap().get(*id).is_ok();
+                        if is_house && !targets.contains(&t) {
+                            targets.push(t);
+                        }
+                    },
+                    InputTargets::ConstructionSite => {
+                        if let Ok(_) = store.borrow::<View<ConstructionSite>>().unwrap().get(*id){
+                            let t = Target::from(*id);
+                            if !targets.contains(&t) {
+                                targets.push(t);
+                            }
+                        }
+                    },
+                    InputTargets::Granary => {
+                        if let Ok(_) = store.borrow::<View<Granary>>().unwrap().get(*id){
+                            let t = Target::from(*id);
+                            if !targets.contains(&t) {
+                                targets.push(t);
+                            }
+                        }
+                    },
+                    InputTargets::Grain => {
+                        if let Ok(item) = store.borrow::<View<Item>>().unwrap().get(*id){
+                            if item.typ == ItemType::Grain {
+                                let t = Target::from(*id);
+                                if !targets.contains(&t) {
+                                    targets.push(t);
+                                }
+                            }
+                        }
+                    },
+                    InputTargets::Dirt => {
+                        if let Ok(item) = store.borrow::<View<Item>>().unwrap().get(*id){
+                            if item.typ == ItemType::Dirt {
+                                let t = Target::from(*id);
+                                if !targets.contains(&t) {
+                                    targets.push(t);
+                                }
+                            }
+                        }
+                    },
+                    InputTargets::Wolf => {
+                        if let Ok(actor) = store.borrow::<View<Actor>>().unwrap().get(*id){
+                            let t = Target::from(*id);
+                            if *id != owner && actor.atype == ActorType::Wolf && !targets.contains(&t) {
+                                targets.push(t);
+                            }
+                        }
+                    },
+                    InputTargets::Farmland | InputTargets::RipeCrop | InputTargets::Soil | InputTargets::Ground | InputTargets::Wall | InputTargets::Trench => {},
                     InputTargets::Orc => {
                         if let Ok(_) = store.borrow::<View<Fish>>().unwrap().get(*id){
                             let t = Target::from(*id);
@@ -291,14 +458,25 @@ pub struct DijkstraMapToMe {
     pub map: DijkstraMap,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct IsCamera {}
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct Aging {
     pub turns: i32,
 }
 
+/// Most a need can build up to, villagers suffer once one gets here
+pub const MAX_NEED: i32 = 100;
+
+/// How badly a villager wants food, sleep and a roof. 0 is content, `MAX_NEED` is desperate
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Needs {
+    pub hunger: i32,
+    pub fatigue: i32,
+    pub shelter: i32, // turns since last sleeping under a roof
+}
+
 /// Entity intents
 
 #[derive(Component, Clone, Copy)]
@@ -329,12 +507,12 @@ pub struct WantsToUseItem {
 }
 
 /// Inventory components
-#[derive(Component, Clone, Debug, PartialEq)]
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AddsGas {
     pub gas: GasType,
 }
 
-#[derive(Component, Clone, Debug, PartialEq)]
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RemovesGas {
 }
 
@@ -357,7 +535,7 @@ impl Inventory {
     }
 }
 
-#[derive(Component, PartialEq, Copy, Clone, Eq, Hash, Debug, EnumIter)]
+#[derive(Component, PartialEq, Copy, Clone, Eq, Hash, PartialOrd, Ord, Debug, EnumIter, Serialize, Deserialize)]
 pub enum EquipmentSlot {
     LeftHand,
     RightHand,
@@ -370,13 +548,13 @@ pub enum EquipmentSlot {
 
 #[derive(Component, Clone, PartialEq)]
 pub struct Equipment {
-    pub items: HashMap<EquipmentSlot, Option<EntityId>>,
+    pub items: BTreeMap<EquipmentSlot, Option<EntityId>>,
 }
 
 impl Equipment {
     pub fn new() -> Self {
         Self { 
-            items: HashMap::from([
+            items: BTreeMap::from([
                 (EquipmentSlot::LeftHand, None),
                 (EquipmentSlot::RightHand, None),
                 (EquipmentSlot::Torso, None),
@@ -403,7 +581,7 @@ impl Equipment {
     }
 }
 
-#[derive(Component, Copy, Clone)]
+#[derive(Component, Copy, Clone, Serialize, Deserialize)]
 pub struct Equippable {
     pub slot: EquipmentSlot,
 }
@@ -416,7 +594,7 @@ pub struct Equipped { // todo is this necessary?
 
 /// Item properties
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ItemType {
     Log,
     Shield,
@@ -424,64 +602,182 @@ pub enum ItemType {
     Potion,
     Scroll,
     Fish,
+    Grain,
+    Dirt, // dug out of the ground, used to fill trenches back in
+    Rubble,
 }
 
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Item {
     pub typ: ItemType,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct Consumable {}
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct MeleePowerBonus {
     pub power: i32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct MeleeDefenseBonus {
     pub defense: i32,
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct ProvidesHealing {
     pub heal: i32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct Ranged {
     pub range: i32,
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct DealsDamage {
     pub damage: i32,
 }
 
-#[derive(Component, Clone, Copy)]
-pub struct Confusion {
-    pub turns: i32,
+/// Puts a status effect on whoever the item is used on
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
+pub struct InflictsStatus {
+    pub status: StatusEffect,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct AreaOfEffect {
     pub radius: i32,
 }
 
-/// Fire components
+/// Spells
 
-#[derive(Component, Clone, Copy)]
-pub struct OnFire {
+/// What a caster spends on spells, refilled a little every turn
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Mana {
+    pub current: i32,
+    pub max: i32,
+    pub regen: i32,
+}
+
+/// A known spell. What it does comes from the same components a scroll uses, but it costs mana instead of being used up
+#[derive(Component, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Spell {
+    pub mana_cost: i32,
+    pub cooldown: i32, // turns before it can be cast again
+    pub cooldown_left: i32,
+}
+
+impl Spell {
+    pub fn castable(&self, mana: &Mana) -> bool {
+        self.cooldown_left <= 0 && mana.current >= self.mana_cost
+    }
+}
+
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct Spellbook {
+    pub spells: Vec<EntityId>,
+}
+
+/// Status effects
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum StatusKind {
+    Confused,
+    Burning,
+    Poisoned,
+    Regenerating,
+    Hasted,
+    Slowed,
+    Blind,
+    Sick,
+}
+
+/// What happens when a status is applied to an entity that already has it
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Stacking {
+    Refresh,   // keep whichever lasts longer
+    Extend,    // durations add up
+    Intensify, // magnitudes add up, keep whichever lasts longer
+}
+
+impl StatusKind {
+    pub fn stacking(&self) -> Stacking {
+        match self {
+            StatusKind::Confused | StatusKind::Sick => Stacking::Extend,
+            StatusKind::Poisoned => Stacking::Intensify,
+            _ => Stacking::Refresh,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusKind::Confused => "confused",
+            StatusKind::Burning => "burning",
+            StatusKind::Poisoned => "poisoned",
+            StatusKind::Regenerating => "regenerating",
+            StatusKind::Hasted => "hasted",
+            StatusKind::Slowed => "slowed",
+            StatusKind::Blind => "blind",
+            StatusKind::Sick => "sick",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
     pub turns: i32,
+    #[serde(default = "default_magnitude")]
+    pub magnitude: i32, // damage or healing per turn for statuses that have it
 }
 
-#[derive(Component, Clone, Copy)]
+fn default_magnitude() -> i32 {
+    1
+}
+
+/// Every status effect on an entity, counted down once per turn by `system_status`
+#[derive(Component, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    pub fn add(&mut self, status: StatusEffect) {
+        match self.effects.iter_mut().find(|s| s.kind == status.kind) {
+            Some(existing) => match status.kind.stacking() {
+                Stacking::Refresh => {
+                    existing.turns = i32::max(existing.turns, status.turns);
+                    existing.magnitude = i32::max(existing.magnitude, status.magnitude);
+                }
+                Stacking::Extend => existing.turns += status.turns,
+                Stacking::Intensify => {
+                    existing.turns = i32::max(existing.turns, status.turns);
+                    existing.magnitude += status.magnitude;
+                }
+            },
+            None => self.effects.push(status),
+        }
+    }
+
+    pub fn get(&self, kind: StatusKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|s| s.kind == kind)
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.get(kind).is_some()
+    }
+}
+
+/// Fire components
+
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct CausesFire {
     pub turns: i32,
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
 pub struct Flammable {}
 
 /// Save components
@@ -3,9 +3,11 @@ use shipyard::{AddComponent, Get, UniqueViewMut, ViewMut};
 use super::*;
 use crate::world::components::PhysicalStats;
 use crate::world::components::GameLog;
+use crate::world::events::{EventStream, GameEvent};
 
 pub fn inflict_damage(store: &mut AllStoragesViewMut, damage: &EffectSpawner) {
     let mut log = store.borrow::<UniqueViewMut<GameLog>>().unwrap();
+    let mut events = store.borrow::<UniqueViewMut<EventStream>>().unwrap();
 
     if let EffectType::Damage { amount, target } = &damage.effect_type {
         if let Ok(mut vs) = store.borrow::<ViewMut<PhysicalStats>>() {
@@ -15,6 +17,8 @@ pub fn inflict_damage(store: &mut AllStoragesViewMut, damage: &EffectSpawner) {
                         let mut stats = stats.clone();
                         stats.hp -= amount;
                         vs.add_component_unchecked(target, stats);
+
+                        events.push(GameEvent::DamageDealt { source: damage.creator, target, amount: *amount });
                     }
                     Err(_e) => {
                         log.messages.push(format!("Damage failed!!"));
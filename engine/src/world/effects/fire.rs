@@ -1,37 +1,18 @@
-use std::cmp;
-
-use shipyard::{UniqueViewMut, ViewMut, EntitiesViewMut};
+use shipyard::UniqueViewMut;
 
 use super::*;
-use crate::{world::map::Map, world::components::OnFire};
+use crate::{world::map::Map, world::components::{StatusEffect, StatusKind}, world::systems::system_fire::MAX_FIRE};
 
 pub fn inflict_fire(store: &mut AllStoragesViewMut, effect: &EffectSpawner) {
     if let EffectType::Fire { turns, target } = &effect.effect_type {
-        let mut to_add_fire = vec![];
         for target in get_effected_entities(&store, &target) {
-            store.run(|vfire: ViewMut<OnFire>| {
-                if let Ok(fire) = vfire.get(target) {
-                    to_add_fire.push((
-                        target,
-                        OnFire {
-                            turns: cmp::min(fire.turns, *turns),
-                        },
-                    ));
-                } else {
-                    to_add_fire.push((target, OnFire { turns: *turns }));
-                }
+            status::add_status(&store, target, StatusEffect {
+                kind: StatusKind::Burning,
+                turns: i32::min(*turns, MAX_FIRE),
+                magnitude: 1,
             });
         }
 
-        store.run(|mut vfire: ViewMut<OnFire>, entities: EntitiesViewMut | {
-            for (target, fire) in to_add_fire {
-                if entities.is_alive(target) {
-                    entities.add_component(target, &mut vfire, fire);
-                }
-            }
-        });
-
-
         for tile_idx in get_effected_tiles(&store, &target) {
             let mut map = store.borrow::<UniqueViewMut<Map>>().unwrap();
             if map.is_flammable(tile_idx) {
@@ -39,4 +20,4 @@ pub fn inflict_fire(store: &mut AllStoragesViewMut, effect: &EffectSpawner) {
             }
         }
     }
-}
\ No newline at end of file
+}
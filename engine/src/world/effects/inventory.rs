@@ -2,6 +2,7 @@ use shipyard::{AddComponent, Get, Remove, UniqueView, UniqueViewMut, View, ViewM
 
 use super::*;
 use crate::world::components::{Equipped, GameLog, Inventory, Name, PlayerID, Position, WantsToPickupItem};
+use crate::world::events::{EventStream, GameEvent};
 
 pub fn pick_up(store: &AllStoragesViewMut, effect: &EffectSpawner) {
     let mut vpos = store.borrow::<ViewMut<Position>>().unwrap();
@@ -25,6 +26,7 @@ pub fn pick_up(store: &AllStoragesViewMut, effect: &EffectSpawner) {
         // if let Ok(name) = vname.get(id) {
             if let Ok(inv) = (&mut vinv).get(id) {
                 inv.items.push(*target);
+                store.borrow::<UniqueViewMut<EventStream>>().unwrap().push(GameEvent::ItemPickedUp { entity: id, item: *target });
             } else {
                 dbg!("Entity has no inventory");
             }
@@ -1,13 +1,12 @@
 use std::collections::VecDeque;
-use std::sync::Mutex;
 
 mod damage;
 pub use damage::inflict_damage;
 
 mod delete;
 
-mod confusion;
-pub use confusion::inflict_confusion;
+mod status;
+pub use status::{add_status, inflict_status};
 
 mod fire;
 pub use fire::inflict_fire;
@@ -19,19 +18,16 @@ pub use inventory::pick_up;
 
 mod movement;
 mod spawn;
+mod terrain;
 
-use shipyard::{AllStoragesViewMut, EntityId, Get, UniqueView, View};
+use shipyard::{AllStoragesViewMut, EntityId, Get, Unique, UniqueView, UniqueViewMut, View};
 
-use crate::{world::components::Position, world::map::Map, entity_factory::EntitySpawnTypes};
-
-lazy_static! {
-    pub static ref EFFECT_QUEUE: Mutex<VecDeque<EffectSpawner>> = Mutex::new(VecDeque::new());
-}
+use crate::{world::components::{Position, StatusEffect}, world::map::Map, entity_factory::EntitySpawnTypes};
 
 #[derive(Clone)]
 pub enum EffectType {
     Damage { amount: i32, target: Targets },
-    Confusion { turns: i32, target: Targets },
+    Status { status: StatusEffect, target: Targets },
     Fire { turns: i32, target: Targets },
     PickUp { entity: EntityId },
     Drop { entity: EntityId },
@@ -42,6 +38,8 @@ pub enum EffectType {
     Wait {},
     Delete { entity: EntityId },
     Spawn { etype: EntitySpawnTypes, target: Targets },
+    Dig { tile_idx: usize },
+    Fill { tile_idx: usize },
 }
 
 #[derive(Clone)]
@@ -58,20 +56,38 @@ pub struct EffectSpawner {
     pub effect_type: EffectType,
 }
 
-pub fn add_effect(creator: Option<EntityId>, effect_type: EffectType) {
-    EFFECT_QUEUE
-        .lock()
-        .unwrap()
-        .push_back(EffectSpawner { creator, effect_type });
+/// Effects waiting to be applied. Each world has its own queue
+#[derive(Default, Unique)]
+pub struct EffectQueue {
+    queue: VecDeque<EffectSpawner>,
+}
+
+impl EffectQueue {
+    pub fn add_effect(&mut self, creator: Option<EntityId>, effect_type: EffectType) {
+        self.queue.push_back(EffectSpawner { creator, effect_type });
+    }
+
+    pub fn pop(&mut self) -> Option<EffectSpawner> {
+        self.queue.pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
 }
 
 pub fn run_effects_queue(mut store: AllStoragesViewMut) {
     loop {
-        let effect: Option<EffectSpawner> = EFFECT_QUEUE.lock().unwrap().pop_front();
+        // only hold the borrow long enough to pop, effects are free to queue more effects
+        let effect: Option<EffectSpawner> = store.borrow::<UniqueViewMut<EffectQueue>>().unwrap().pop();
         if let Some(effect) = &effect {
             match effect.effect_type {
                 EffectType::Damage { .. } => damage::inflict_damage(&mut store, effect),
-                EffectType::Confusion { .. } => confusion::inflict_confusion(&mut store, effect),
+                EffectType::Status { .. } => status::inflict_status(&store, effect),
                 EffectType::Fire { .. } => fire::inflict_fire(&mut store, effect),
                 EffectType::PickUp { .. } => inventory::pick_up(&store, effect),
                 EffectType::Drop { .. } => inventory::drop_item(&store, effect),
@@ -82,6 +98,8 @@ pub fn run_effects_queue(mut store: AllStoragesViewMut) {
                 EffectType::Delete { .. } => delete::delete(&mut store, effect),
                 EffectType::MoveOrAttack { .. } => movement::try_move_or_attack(&store, effect, true),
                 EffectType::Spawn { .. } => spawn::spawn(&mut store, effect),
+                EffectType::Dig { .. } => terrain::dig(&mut store, effect),
+                EffectType::Fill { .. } => terrain::fill(&mut store, effect),
             }
         } else {
             // this happens when the queue is empty
@@ -89,7 +107,7 @@ pub fn run_effects_queue(mut store: AllStoragesViewMut) {
         }
     }
 
-    if EFFECT_QUEUE.lock().unwrap().len() > 0 {
+    if !store.borrow::<UniqueView<EffectQueue>>().unwrap().is_empty() {
         dbg!("ERROR: Finished running effecgs queue but there's still effects left");
     }
 }
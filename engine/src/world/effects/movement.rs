@@ -1,11 +1,12 @@
 use rltk::{DijkstraMap, Point};
-use shipyard::{AddComponent, Get, UniqueViewMut, View, ViewMut};
+use shipyard::{AddComponent, Get, UniqueView, UniqueViewMut, View, ViewMut};
 
 use super::*;
 use crate::{
+    raws::factions::{FactionTable, Relation},
     world::components::{
-        BlocksTile, IsCamera, LocomotionType, Locomotive, OnFire, PPoint, PhysicalStats, Player, Position, SpatialKnowledge, Vision, WantsToAttack
-    }, world::map::Map, tiles::TileType, utils::{dijkstra_backtrace, normalize, point_plus}
+        Actor, BlocksTile, Energy, IsCamera, LocomotionType, Locomotive, PPoint, PhysicalStats, Player, Position, SpatialKnowledge, StatusEffects, StatusKind, Vision, WantsToAttack, RNG
+    }, world::map::Map, world::systems::system_status::has_status, tiles::TileType, utils::{dijkstra_backtrace, normalize, point_plus}, world::systems::system_energy::ACTION_COST
 };
 
 pub fn try_move_or_attack(store: &AllStoragesViewMut, effect: &EffectSpawner, attack: bool) {
@@ -34,11 +35,20 @@ pub fn try_move_or_attack(store: &AllStoragesViewMut, effect: &EffectSpawner, at
 
     if let Ok(pos) = (&mut vpos).get(entity) {
         let tp = map.idx_point(tile_idx);
-        let dp = Point {
+        let mut dp = Point {
             x: normalize(tp.x - pos.ps[0].x),
             y: normalize(tp.y - pos.ps[0].y),
         };
 
+        // confused entities stumble in a random direction
+        if !is_camera && has_status(&store.borrow::<View<StatusEffects>>().unwrap(), entity, StatusKind::Confused) {
+            let mut rng = store.borrow::<UniqueViewMut<RNG>>().unwrap();
+            dp = Point {
+                x: rng.0.range(-1, 2),
+                y: rng.0.range(-1, 2),
+            };
+        }
+
         let canmove = can_move(&store, &map, entity, &pos, dp);
 
         if !is_camera && attack {
@@ -67,6 +77,14 @@ pub fn try_move_or_attack(store: &AllStoragesViewMut, effect: &EffectSpawner, at
                 map.blocked[idx] = true;
             }
 
+            // rough ground like trenches takes longer to cross
+            let cost = map.tiles[map.point_idx(pos.ps[0])].move_cost();
+            if !is_camera && cost > 1.0 {
+                if let Ok(energy) = (&mut store.borrow::<ViewMut<Energy>>().unwrap()).get(entity) {
+                    energy.value -= ((cost - 1.0) * ACTION_COST as f32) as i32;
+                }
+            }
+
             // If this is a player, change the position in resources according to first in pos.ps
             if is_player {
                 let mut ppos = store.borrow::<UniqueViewMut<PPoint>>().unwrap();
@@ -155,11 +173,11 @@ pub fn autoexplore(store: &AllStoragesViewMut, effect: &EffectSpawner) {
 
 pub fn skip_turn(store: &AllStoragesViewMut, effect: &EffectSpawner) {
     let mut vstats = store.borrow::<ViewMut<PhysicalStats>>().unwrap();
-    let vfire = store.borrow::<View<OnFire>>().unwrap();
+    let vstatus = store.borrow::<View<StatusEffects>>().unwrap();
 
     if let Some(id) = effect.creator {
         if let Ok(stats) = (&mut vstats).get(id) {
-            if let Err(_) = vfire.get(id) {
+            if !has_status(&vstatus, id, StatusKind::Burning) {
                 stats.hp = i32::min(stats.hp + stats.regen_rate, stats.max_hp);
             }
         }
@@ -214,6 +232,9 @@ pub fn get_target(
     dp: Point,
 ) -> Option<EntityId> {
     let vstats = store.borrow::<View<PhysicalStats>>().unwrap();
+    let vactor = store.borrow::<View<Actor>>().unwrap();
+    let factions = store.borrow::<UniqueView<FactionTable>>().unwrap();
+    let is_player = store.borrow::<View<Player>>().unwrap().get(entity).is_ok();
 
     // check for combat stats on entity
     if let Err(_) = vstats.get(entity) {
@@ -230,9 +251,15 @@ pub fn get_target(
             }
 
             // dbg!("does enetiy have stats");
-            match vstats.get(*potential_target) {
-                Ok(_cs) => return Some(*potential_target),
-                Err(_e) => {}
+            if vstats.get(*potential_target).is_err() {
+                continue;
+            }
+
+            // nobody hits their allies, and only the player starts fights with neutrals
+            match factions.relation_between(&vactor, entity, *potential_target) {
+                Relation::Hostile => return Some(*potential_target),
+                Relation::Neutral if is_player => return Some(*potential_target),
+                _ => {}
             }
         }
     }
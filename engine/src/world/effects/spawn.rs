@@ -1,7 +1,8 @@
 use shipyard::UniqueViewMut;
 
 use super::*;
-use crate::entity_factory::spawn_entity_type;
+use crate::entity_factory::{spawn_entity_type, EntitySpawnTypes};
+use crate::world::events::{EventStream, GameEvent};
 
 pub fn spawn(store: &mut AllStoragesViewMut, effect: &EffectSpawner) {
     if let EffectType::Spawn { etype, target } = &effect.effect_type {
@@ -16,7 +17,15 @@ pub fn spawn(store: &mut AllStoragesViewMut, effect: &EffectSpawner) {
             // }
         }
         for t in spawn_targets.iter() {
-            spawn_entity_type(store, *etype, *t, &None);
+            let e = match spawn_entity_type(store, *etype, *t, &None) {
+                Some(e) => e,
+                None => continue,
+            };
+
+            // villagers are only spawned as births from run_time_system
+            if *etype == EntitySpawnTypes::Villager {
+                store.borrow::<UniqueViewMut<EventStream>>().unwrap().push(GameEvent::VillagerBorn { entity: e });
+            }
         }
     }
 
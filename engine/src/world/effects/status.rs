@@ -0,0 +1,30 @@
+use shipyard::{AddComponent, EntitiesView, Get, ViewMut};
+
+use super::*;
+use crate::world::components::{StatusEffect, StatusEffects};
+
+pub fn inflict_status(store: &AllStoragesViewMut, effect: &EffectSpawner) {
+    if let EffectType::Status { status, target } = &effect.effect_type {
+        for entity in get_effected_entities(&store, &target) {
+            add_status(store, entity, *status);
+        }
+    }
+}
+
+/// Adds a status to an entity, stacking with one it already has
+pub fn add_status(store: &AllStoragesViewMut, entity: EntityId, status: StatusEffect) {
+    store.run(|entities: EntitiesView, mut vstatus: ViewMut<StatusEffects>| {
+        if !entities.is_alive(entity) || status.turns <= 0 {
+            return;
+        }
+
+        if let Ok(statuses) = (&mut vstatus).get(entity) {
+            statuses.add(status);
+            return;
+        }
+
+        let mut statuses = StatusEffects::default();
+        statuses.add(status);
+        vstatus.add_component_unchecked(entity, statuses);
+    });
+}
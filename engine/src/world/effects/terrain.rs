@@ -0,0 +1,96 @@
+use shipyard::{Get, UniqueView, UniqueViewMut, View, ViewMut};
+
+use super::*;
+use crate::entity_factory;
+use crate::tiles::TileType;
+use crate::world::components::{GameLog, Inventory, Item, ItemType, PlayerID};
+
+/// One turn of digging. Ground becomes trench and walls are worn down to floor, leaving what was dug out on the tile
+pub fn dig(store: &mut AllStoragesViewMut, effect: &EffectSpawner) {
+    if let (Some(digger), EffectType::Dig { tile_idx }) = (effect.creator, &effect.effect_type) {
+        let tile_idx = *tile_idx;
+        if !within_reach(store, digger, tile_idx) {
+            return;
+        }
+
+        let (msg, finished) = {
+            let mut map = store.borrow::<UniqueViewMut<Map>>().unwrap();
+            let (result, turns, material) = match map.tiles[tile_idx].dig() {
+                Some(dig) => dig,
+                None => {
+                    player_log(store, digger, "You can't dig there");
+                    return;
+                }
+            };
+
+            let progress = map.dig_progress.entry(tile_idx).or_insert(0);
+            *progress += 1;
+
+            if *progress < turns {
+                let msg = if result == TileType::Trench { "You dig into the ground" } else { "You chip away at the wall" };
+                (msg, None)
+            } else {
+                map.change_tile(tile_idx, result);
+                let msg = if result == TileType::Trench { "You dig a trench" } else { "You break through the wall" };
+                (msg, Some((map.idx_xy(tile_idx), material)))
+            }
+        };
+
+        player_log(store, digger, msg);
+
+        if let Some((xy, material)) = finished {
+            entity_factory::spawn_named(store, material, xy);
+        }
+    }
+}
+
+/// Shovels a pile of dirt from the filler's inventory back into a trench
+pub fn fill(store: &mut AllStoragesViewMut, effect: &EffectSpawner) {
+    if let (Some(filler), EffectType::Fill { tile_idx }) = (effect.creator, &effect.effect_type) {
+        let tile_idx = *tile_idx;
+        if !within_reach(store, filler, tile_idx) {
+            return;
+        }
+
+        if store.borrow::<UniqueView<Map>>().unwrap().tiles[tile_idx] != TileType::Trench {
+            player_log(store, filler, "There's nothing to fill in there");
+            return;
+        }
+
+        let dirt = {
+            let vitem = store.borrow::<View<Item>>().unwrap();
+            let mut vinv = store.borrow::<ViewMut<Inventory>>().unwrap();
+
+            match (&mut vinv).get(filler) {
+                Ok(inv) => inv.items.iter().position(|i| vitem.get(*i).map_or(false, |i| i.typ == ItemType::Dirt)).map(|i| inv.items.remove(i)),
+                Err(_) => None,
+            }
+        };
+
+        match dirt {
+            Some(dirt) => {
+                store.delete_entity(dirt);
+                store.borrow::<UniqueViewMut<Map>>().unwrap().change_tile(tile_idx, TileType::Dirt);
+                player_log(store, filler, "You fill in the trench");
+            }
+            None => player_log(store, filler, "You need some dirt to fill in the trench"),
+        }
+    }
+}
+
+// digging and filling only reach the tile you're on and the ones next to it
+fn within_reach(store: &AllStoragesViewMut, id: EntityId, tile_idx: usize) -> bool {
+    let map = store.borrow::<UniqueView<Map>>().unwrap();
+    let target = map.idx_point(tile_idx);
+
+    match store.borrow::<View<Position>>().unwrap().get(id) {
+        Ok(pos) => pos.ps.iter().any(|p| rltk::DistanceAlg::Pythagoras.distance2d(*p, target) < 1.5),
+        Err(_) => false,
+    }
+}
+
+fn player_log(store: &AllStoragesViewMut, id: EntityId, msg: &str) {
+    if id == store.borrow::<UniqueView<PlayerID>>().unwrap().0 {
+        store.borrow::<UniqueViewMut<GameLog>>().unwrap().messages.push(msg.to_string());
+    }
+}
@@ -0,0 +1,27 @@
+use shipyard::{EntityId, Unique};
+
+/// Things that happened in the world that code outside the engine may care about
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    EntityDied { entity: EntityId, name: Option<String> },
+    ItemPickedUp { entity: EntityId, item: EntityId },
+    DamageDealt { source: Option<EntityId>, target: EntityId, amount: i32 },
+    VillagerBorn { entity: EntityId },
+    TileBurned { tile_idx: usize },
+}
+
+/// Per world list of events. They pile up until someone drains them
+#[derive(Default, Unique)]
+pub struct EventStream {
+    events: Vec<GameEvent>,
+}
+
+impl EventStream {
+    pub fn push(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+
+    pub fn drain(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
@@ -0,0 +1,21 @@
+use std::collections::BTreeMap;
+
+use rltk::Point;
+use serde::{Deserialize, Serialize};
+use shipyard::Unique;
+
+use crate::world::{map::Map, save::SavedEntity};
+
+/// A level the player has left, kept so it can be restored as it was
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StoredLevel {
+    pub map: Map,
+    pub entities: Vec<SavedEntity>,
+    pub player_pos: Point, // where the player was standing when they left
+}
+
+/// Every level other than the current one, by depth
+#[derive(Clone, Default, Unique)]
+pub struct Levels {
+    pub stored: BTreeMap<usize, StoredLevel>,
+}
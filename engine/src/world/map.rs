@@ -1,8 +1,10 @@
+use std::collections::BTreeMap;
+
 use rltk::{Algorithm2D, Point, BaseMap, NavigationPath};
 use serde::{Serialize, Deserialize};
 use shipyard::{EntityId, View, Get, Unique, World};
 
-use crate::{config::GameSettings, player, world::components::{Position, Renderable}, tiles::{GasType, TileRenderable, TileType, STABLE_GAS_AMOUNT}, ui::colors::{ColorUtils, COLOR_BG, COLOR_FIRE, COLOR_RED, COLOR_WHITE}, utils::Target, RenderOrder, DISABLE_FOV};
+use crate::{config::GameSettings, player, world::components::{Position, Renderable}, tiles::{GasType, TileRenderable, TileType, CROP_RIPE, STABLE_GAS_AMOUNT}, ui::colors::{ColorUtils, COLOR_BG, COLOR_FIRE, COLOR_GRASS, COLOR_RED, COLOR_WHITE}, utils::Target, RenderOrder, DISABLE_FOV};
 
 pub type XY = (i32, i32);
 pub fn to_point(xy: XY) -> Point {
@@ -23,6 +25,17 @@ pub struct Map {
     pub gases: Vec<(Vec<GasType>, usize)>, // usize - last neighbor that flowed into this space
 
     pub history: Vec<Vec<TileType>>,
+
+    #[serde(default)]
+    pub depth: usize,
+
+    /// Turns of digging put into each tile so far, cleared once the dig is finished
+    #[serde(default)]
+    pub dig_progress: BTreeMap<usize, i32>,
+
+    // set when terrain changes, pathfinding rebuilds on the next tick instead of waiting for the next turn
+    #[serde(skip)]
+    pub paths_dirty: bool,
 }
 
 impl Map {
@@ -37,6 +50,9 @@ impl Map {
             history: Vec::new(),
             vegetation: vec![0; count],
             gases: vec![(vec![GasType::Air; 7], 0); count],
+            depth: 1,
+            dig_progress: BTreeMap::new(),
+            paths_dirty: false,
         }
     }
 
@@ -58,6 +74,22 @@ impl Map {
         self.tiles[idx] = value;
     }
 
+    /// Changes terrain during play, keeping `blocked` and the pathfinding maps in step
+    pub fn change_tile(&mut self, idx: usize, value: TileType) {
+        let was_blocking = self.blocks_movement(idx);
+        self.tiles[idx] = value;
+
+        // anything standing here still blocks it, only the terrain's part changes
+        if self.blocks_movement(idx) {
+            self.blocked[idx] = true;
+        } else if was_blocking {
+            self.blocked[idx] = false;
+        }
+
+        self.dig_progress.remove(&idx);
+        self.paths_dirty = true;
+    }
+
     pub fn xy_idx(&self, xy: XY) -> usize {
         (xy.1 as usize * self.size.0 as usize) + xy.0 as usize
     }
@@ -95,6 +127,16 @@ impl Map {
         return render;
     }
 
+    /// Tile renderable, with crops that aren't ripe yet drawn as shoots
+    fn tile_renderable(&self, idx: usize) -> TileRenderable {
+        if self.tiles[idx] == TileType::Wheat && self.vegetation[idx] < CROP_RIPE {
+            let glyph = if self.vegetation[idx] < CROP_RIPE / 2 { ',' } else { '"' };
+            return (glyph, COLOR_GRASS, COLOR_BG);
+        }
+
+        self.tiles[idx].renderable()
+    }
+
     pub fn get_renderable(&self, pos: XY, settings: &GameSettings, world: &World, historyidx: Option<usize>) -> TileRenderable {
         let idx: usize = self.xy_idx(pos);
 
@@ -117,7 +159,7 @@ impl Map {
 
             let vision = player::get_player_viewshed(world);
             if vision.visible_tiles.contains(&Point::new(pos.0, pos.1)) {
-                render = self.tiles[idx].renderable();
+                render = self.tile_renderable(idx);
                 if self.fire_turns[idx] > 0 {
                     render = (render.0, render.1, COLOR_FIRE);
                 }
@@ -127,7 +169,7 @@ impl Map {
                 } 
             }
         } else {
-            render = self.tiles[idx].renderable();
+            render = self.tile_renderable(idx);
             
             if let Some(renderable) = self.get_entity_renderable(&self.tile_content[idx], world) {
                 render = renderable;
@@ -175,7 +217,7 @@ impl Map {
     pub fn is_flammable(&self, idx: usize) -> bool {
         match self.tiles[idx] {
             TileType::Sand | TileType::Dirt | TileType::Stone | TileType::Floor | TileType::Grass | TileType::Wheat | TileType::WoodWall | 
-            TileType::WoodDoor | TileType::WoodFloor => true,
+            TileType::WoodDoor | TileType::WoodFloor | TileType::Farmland => true,
             _ => false
         }
     }
@@ -284,29 +326,29 @@ impl BaseMap for Map {
         let w = self.size.0 as usize;
 
         if self.is_exit_valid(x - 1, y) {
-            exits.push((idx - 1, 1.0))
+            exits.push((idx - 1, self.tiles[idx - 1].move_cost()))
         };
         if self.is_exit_valid(x + 1, y) {
-            exits.push((idx + 1, 1.0))
+            exits.push((idx + 1, self.tiles[idx + 1].move_cost()))
         };
         if self.is_exit_valid(x, y - 1) {
-            exits.push((idx - w, 1.0))
+            exits.push((idx - w, self.tiles[idx - w].move_cost()))
         };
         if self.is_exit_valid(x, y + 1) {
-            exits.push((idx + w, 1.0))
+            exits.push((idx + w, self.tiles[idx + w].move_cost()))
         };
 
         if self.is_exit_valid(x - 1, y - 1) {
-            exits.push((idx - w - 1, 1.45))
+            exits.push((idx - w - 1, 1.45 * self.tiles[idx - w - 1].move_cost()))
         };
         if self.is_exit_valid(x + 1, y - 1) {
-            exits.push((idx - w + 1, 1.45))
+            exits.push((idx - w + 1, 1.45 * self.tiles[idx - w + 1].move_cost()))
         };
         if self.is_exit_valid(x - 1, y + 1) {
-            exits.push((idx + w - 1, 1.45))
+            exits.push((idx + w - 1, 1.45 * self.tiles[idx + w - 1].move_cost()))
         };
         if self.is_exit_valid(x + 1, y + 1) {
-            exits.push((idx + w + 1, 1.45))
+            exits.push((idx + w + 1, 1.45 * self.tiles[idx + w + 1].move_cost()))
         };
 
         exits
@@ -2,9 +2,12 @@ pub mod effects;
 pub mod systems;
 pub mod components;
 pub mod map;
+pub mod save;
+pub mod events;
+pub mod levels;
 
-use crate::{config::{get_config, GameMode}, ui::{assets::Assets, screen::{console::ConsoleMode, menu_config::{MainMenuSelection, ModeSelectSelection}, RangedTargetResult, Screen}}, utils::InvalidPoint, world::{components::{FrameTime, PhysicalStats, WantsToUseItem}, map::XY, systems::system_particle}, world_sim::WorldSim, DISABLE_MAPGEN_ANIMATION, HEIGHT, WIDTH};
-use shipyard::{EntityId, Get, UniqueViewMut, View};
+use crate::{config::{get_config, get_mode_list, Keymap}, ui::{assets::Assets, input_handler, replay::{Recorder, Replay}, screen::{console::ConsoleMode, menu_config::MainMenuSelection, RangedTargetResult, Screen}}, utils::InvalidPoint, world::{components::{FrameTime, GameLog, PhysicalStats, Tick, Turn, WantsToUseItem, War}, map::XY, systems::{system_energy::ACTION_COST, system_particle}}, world_sim::WorldSim, DISABLE_MAPGEN_ANIMATION, HEIGHT, WIDTH};
+use shipyard::{EntityId, Get, UniqueView, UniqueViewMut, View};
 
 pub struct Game {
     pub world_sim: WorldSim,
@@ -16,6 +19,10 @@ pub struct Game {
     pub history_step: usize,
     pub autorun: bool,
     pub frame_time: i32,
+    pub seed: Option<u64>,
+    pub recorder: Option<Recorder>,
+    pub replay: Option<Replay>,
+    pub modes: Vec<(String, String)>, // (id, name) of the modes in the mode select menu
 }
 
 #[derive(PartialEq, Copy, Clone, Debug)]
@@ -29,9 +36,11 @@ pub enum GameState {
     PostTurn, // after systems have acted
 
     MainMenu{ selection: MainMenuSelection },
-    ModeSelect{ selection: ModeSelectSelection },
+    ModeSelect{ selection: usize },
     ShowMapHistory,
     ShowInventory{ selection: usize },
+    ShowSystems{ selection: usize },
+    ShowSpells{ selection: usize },
     ShowItemActions {
         item: EntityId,
     },
@@ -45,16 +54,24 @@ pub enum GameState {
 }
 
 impl Game {
-    pub fn new(mode: GameMode) -> Self {
+    pub fn new(mode: &str, seed: Option<u64>) -> Self {
+        let modes = match get_mode_list() {
+            Ok(modes) => modes,
+            Err(e) => {
+                eprintln!("Failed to load configuration: {}", e);
+                std::process::exit(1); // Exit the program with an error code
+            }
+        };
+
         let settings = match get_config(mode) {
-            Ok(config) => config,
+            Ok(config) => config.with_seed(seed),
             Err(e) => {
                 eprintln!("Failed to load configuration: {}", e);
                 std::process::exit(1); // Exit the program with an error code
             }
         };
 
-        let mut world = WorldSim::new(settings);
+        let mut world = WorldSim::new(settings.clone());
         world.reset_engine(settings);
 
         Self {
@@ -67,6 +84,74 @@ impl Game {
             history_step: 0,
             autorun: false,
             frame_time: 0,
+            seed,
+            recorder: None,
+            replay: None,
+            modes,
+        }
+    }
+
+    /// Record every command from here on, starting with the current world
+    pub fn start_recording(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut recorder = Recorder::create(path)?;
+        recorder.start(&self.world_sim.settings);
+        self.recorder = Some(recorder);
+
+        Ok(())
+    }
+
+    /// Regenerate the world from a replay file. Commands are fed in by `replay_input`
+    pub fn start_replay(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let replay = Replay::load(path)?;
+        let mode = match replay.mode() {
+            Some(mode) => mode,
+            None => return Err("Replay file doesn't start with a world".into()),
+        };
+
+        self.replay = Some(replay);
+        self.reset(Some(mode));
+
+        Ok(())
+    }
+
+    /// Feed recorded commands for this world tick back in, in place of the keyboard
+    pub fn replay_input(&mut self) {
+        // commands only ever come in while the world waits on the player
+        if matches!(self.state, GameState::PlayerActed | GameState::PostTurn) {
+            return;
+        }
+
+        loop {
+            let tick = self.world_sim.world.borrow::<UniqueView<Tick>>().unwrap().0;
+            let next = match &mut self.replay {
+                Some(replay) => replay.next_command(tick),
+                None => return,
+            };
+
+            let (turn, command) = match next {
+                Some(next) => next,
+                None => break,
+            };
+
+            // a desynced replay would quietly play out a different game, so stop instead
+            let current_turn = self.world_sim.world.borrow::<UniqueView<Turn>>().unwrap().0;
+            if turn != current_turn {
+                dbg!("Replay out of sync", turn, current_turn);
+                self.replay = None;
+                self.autorun = false;
+                self.world_sim.world.borrow::<UniqueViewMut<GameLog>>().unwrap().messages.push(format!("Replay out of sync, recorded on turn {} but the game is on turn {}. Stopped", turn, current_turn));
+                return;
+            }
+
+            let state = input_handler::run_command(command, self);
+            if state != GameState::None {
+                self.set_state(state);
+            }
+        }
+
+        if self.replay.as_ref().map_or(false, |r| r.is_finished()) {
+            self.replay = None;
+            self.world_sim.world.borrow::<UniqueViewMut<GameLog>>().unwrap().messages.push("Replay finished".to_string());
         }
     }
 
@@ -113,27 +198,40 @@ impl Game {
                     }
                 }
 
+                // trench war ends with a wizard's death instead
+                if self.world_sim.world.borrow::<UniqueView<War>>().map_or(false, |war| war.over) {
+                    gameover = true;
+                }
+
                 if gameover {
                     self.set_state(GameState::GameOver);
                 }
 
                 if self.autorun {
+                    // the player sits out a turn
+                    self.world_sim.spend_energy(player_id, ACTION_COST);
                     self.set_state(GameState::PlayerActed);
                 }
             },
             GameState::PlayerActed => {
                 self.set_state(GameState::PostTurn);
-                self.world_sim.run_systems();
+                self.world_sim.run_until_player_can_act();
             },
             GameState::PostTurn => {
                 self.set_state(GameState::PreTurn);
             },
+            GameState::ShowSystems { .. } => {
+                // keep the sim going so the timings stay live
+                if self.autorun {
+                    self.world_sim.run_turn();
+                }
+            },
             GameState::ShowMapHistory => {
                 self.history_timer += 1;
                 self.history_step = self.history_timer / 5;
                 let map = self.world_sim.get_map();
 
-                if self.history_step > map.history.len() + 20 || (DISABLE_MAPGEN_ANIMATION && self.world_sim.settings.mode != GameMode::MapDemo) {
+                if self.history_step > map.history.len() + 20 || (DISABLE_MAPGEN_ANIMATION && self.world_sim.settings.keymap != Keymap::MapDemo) {
                     self.state = GameState::PreTurn;
                 }
             },
@@ -157,7 +255,9 @@ impl Game {
                     RangedTargetResult::Cancel => self.state = GameState::PreTurn,
                     RangedTargetResult::NoResponse => {} ,
                     RangedTargetResult::Selected => {
-                        self.world_sim.world.add_component(item, WantsToUseItem { item, target: target });
+                        let player = self.world_sim.get_player_id().0;
+                        self.world_sim.world.add_component(player, WantsToUseItem { item, target: target });
+                        self.world_sim.spend_energy(player, ACTION_COST);
                         self.state = GameState::PlayerActed;    
                     },
                     RangedTargetResult::NewTarget { target } => self.state = GameState::ShowTargeting { range, item, target },
@@ -180,16 +280,31 @@ impl Game {
         self.state = state;
     }
 
-    pub fn reset(&mut self, mode: Option<GameMode>) {
+    pub fn reset(&mut self, mode: Option<String>) {
         self.state = GameState::ShowMapHistory;
 
+        // default to the first mode in the menu
         let mode = match mode {
             Some(m) => m,
-            None => GameMode::RL,
+            None => match self.modes.first() {
+                Some((id, _)) => id.clone(),
+                None => self.world_sim.settings.mode.clone(),
+            },
         };
 
-        let settings = match get_config(mode) {
-            Ok(config) => config,
+        // a replay carries the seed of every world it went through
+        let mut seed = self.seed;
+        if let Some(replay) = &mut self.replay {
+            if let Some((replay_seed, replay_mode)) = replay.next_start() {
+                if replay_mode != mode {
+                    dbg!("Replay out of sync", &replay_mode, &mode);
+                }
+                seed = Some(replay_seed);
+            }
+        }
+
+        let settings = match get_config(&mode) {
+            Ok(config) => config.with_seed(seed),
             Err(e) => {
                 eprintln!("Failed to load configuration: {}", e);
                 std::process::exit(1); // Exit the program with an error code
@@ -197,5 +312,9 @@ impl Game {
         };
 
         self.world_sim.reset_engine(settings);
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.start(&self.world_sim.settings);
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,376 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rltk::{DijkstraMap, Point, RandomNumberGenerator};
+use serde::{Deserialize, Serialize};
+use shipyard::{Component, EntityId, IntoIter, IntoWithId, UniqueView, View, World};
+
+use crate::{
+    ai::intent::{Intent, Task},
+    config::GameSettings,
+    tiles::TileType,
+    utils::Target,
+    world::{
+        components::{
+            Actor, Aging, AddsGas, AreaOfEffect, BlocksTile, CausesFire, ChiefHouse, ConstructionSite, Consumable,
+            DealsDamage, DijkstraMapToMe, Energy, Equipment, EquipmentSlot, Equippable, Equipped, Fish, FishCleaner, Granary,
+            Flammable, GameLog, Inventory, IsCamera, Item, Locomotive, LumberMill, MeleeDefenseBonus,
+            MeleePowerBonus, Name, Needs, InflictsStatus, Orc, PPoint, Raider, Particle, PhysicalStats, PlankHouse, Player, PlayerID, Position,
+            ProvidesHealing, Ranged, RemovesGas, Renderable, SpatialKnowledge, Sapling, Spawner, Spell, Spellbook, Mana, StatusEffects, Tick, Tree, Turn, Vision, War, RNG,
+        },
+        levels::{Levels, StoredLevel},
+        map::Map,
+    },
+};
+
+/// Everything needed to rebuild a `WorldSim`. Entity ids are stored as their raw u64 and remapped on load
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub settings: GameSettings,
+    pub turn: i32,
+    #[serde(default)]
+    pub tick: i32,
+    pub player_id: u64,
+    pub player_pos: Point,
+    pub log: Vec<String>,
+    pub rng: RandomNumberGenerator,
+    pub map: Map,
+    pub entities: Vec<SavedEntity>,
+    #[serde(default)]
+    pub levels: BTreeMap<usize, StoredLevel>, // levels the player has left, by depth
+    #[serde(default)]
+    pub war: War,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedEntity {
+    pub id: u64,
+    pub components: Vec<SavedComponent>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SavedTarget {
+    Location(Point),
+    Entity(u64),
+}
+
+/// Transient components (particles, WantsTo* intents) are not saved, they only live for a single turn.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SavedComponent {
+    Position(Position),
+    Renderable(Renderable),
+    Vision(Vision),
+    Name(Name),
+    Player,
+    Orc,
+    Raider,
+    Fish,
+    Actor(Actor),
+    PlankHouse { housing_cap: i32, villagers: Vec<u64> },
+    ChiefHouse,
+    LumberMill,
+    FishCleaner,
+    Granary,
+    Spawner(Spawner),
+    Tree,
+    Sapling(Sapling),
+    Locomotive(Locomotive),
+    Energy(Energy),
+    BlocksTile,
+    PhysicalStats(PhysicalStats),
+    SpatialKnowledge { tiles: Vec<(usize, TileType, Vec<u64>)> },
+    DijkstraMapToMe,
+    IsCamera,
+    Aging(Aging),
+    Needs(Needs),
+    ConstructionSite(ConstructionSite),
+    Intent { name: String, owner: u64, task: Task, target: Vec<SavedTarget>, turn: i32 },
+    AddsGas(AddsGas),
+    RemovesGas,
+    Inventory { capacity: i32, items: Vec<u64> },
+    Equipment { items: Vec<(EquipmentSlot, Option<u64>)> },
+    Equippable(Equippable),
+    Equipped { owner: u64, slot: EquipmentSlot },
+    Item(Item),
+    Consumable,
+    MeleePowerBonus(MeleePowerBonus),
+    MeleeDefenseBonus(MeleeDefenseBonus),
+    ProvidesHealing(ProvidesHealing),
+    Ranged(Ranged),
+    DealsDamage(DealsDamage),
+    InflictsStatus(InflictsStatus),
+    AreaOfEffect(AreaOfEffect),
+    StatusEffects(StatusEffects),
+    CausesFire(CausesFire),
+    Flammable,
+    Mana(Mana),
+    Spell(Spell),
+    Spellbook { spells: Vec<u64> },
+}
+
+fn collect<T: Component + Send + Sync>(
+    world: &World,
+    entities: &mut BTreeMap<u64, Vec<SavedComponent>>,
+    f: impl Fn(&T) -> SavedComponent,
+) {
+    let view = world.borrow::<View<T>>().unwrap();
+    for (id, c) in view.iter().with_id() {
+        entities.entry(id.inner()).or_insert_with(Vec::new).push(f(c));
+    }
+}
+
+fn ids_to_inner(ids: &Vec<EntityId>) -> Vec<u64> {
+    ids.iter().map(|id| id.inner()).collect()
+}
+
+fn remap_all(ids: &HashMap<u64, EntityId>, inner: &Vec<u64>) -> Vec<EntityId> {
+    inner.iter().filter_map(|i| ids.get(i).copied()).collect()
+}
+
+/// Every saved component in the world, grouped by entity
+fn collect_entities(world: &World) -> BTreeMap<u64, Vec<SavedComponent>> {
+    let mut entities: BTreeMap<u64, Vec<SavedComponent>> = BTreeMap::new();
+
+    collect::<Position>(world, &mut entities, |c| SavedComponent::Position(c.clone()));
+    collect::<Renderable>(world, &mut entities, |c| SavedComponent::Renderable(*c));
+    collect::<Vision>(world, &mut entities, |c| SavedComponent::Vision(c.clone()));
+    collect::<Name>(world, &mut entities, |c| SavedComponent::Name(c.clone()));
+    collect::<Player>(world, &mut entities, |_| SavedComponent::Player);
+    collect::<Orc>(world, &mut entities, |_| SavedComponent::Orc);
+    collect::<Raider>(world, &mut entities, |_| SavedComponent::Raider);
+    collect::<Fish>(world, &mut entities, |_| SavedComponent::Fish);
+    collect::<Actor>(world, &mut entities, |c| SavedComponent::Actor(c.clone()));
+    collect::<PlankHouse>(world, &mut entities, |c| SavedComponent::PlankHouse {
+        housing_cap: c.housing_cap,
+        villagers: ids_to_inner(&c.villagers),
+    });
+    collect::<ChiefHouse>(world, &mut entities, |_| SavedComponent::ChiefHouse);
+    collect::<LumberMill>(world, &mut entities, |_| SavedComponent::LumberMill);
+    collect::<FishCleaner>(world, &mut entities, |_| SavedComponent::FishCleaner);
+    collect::<Granary>(world, &mut entities, |_| SavedComponent::Granary);
+    collect::<Spawner>(world, &mut entities, |c| SavedComponent::Spawner(*c));
+    collect::<Tree>(world, &mut entities, |_| SavedComponent::Tree);
+    collect::<Sapling>(world, &mut entities, |c| SavedComponent::Sapling(*c));
+    collect::<Locomotive>(world, &mut entities, |c| SavedComponent::Locomotive(*c));
+    collect::<Energy>(world, &mut entities, |c| SavedComponent::Energy(*c));
+    collect::<BlocksTile>(world, &mut entities, |_| SavedComponent::BlocksTile);
+    collect::<PhysicalStats>(world, &mut entities, |c| SavedComponent::PhysicalStats(*c));
+    collect::<SpatialKnowledge>(world, &mut entities, |c| SavedComponent::SpatialKnowledge {
+        tiles: c.tiles.iter().map(|(idx, (tile, ids))| (*idx, *tile, ids_to_inner(ids))).collect(),
+    });
+    collect::<DijkstraMapToMe>(world, &mut entities, |_| SavedComponent::DijkstraMapToMe);
+    collect::<IsCamera>(world, &mut entities, |_| SavedComponent::IsCamera);
+    collect::<Aging>(world, &mut entities, |c| SavedComponent::Aging(*c));
+    collect::<Needs>(world, &mut entities, |c| SavedComponent::Needs(*c));
+    collect::<ConstructionSite>(world, &mut entities, |c| SavedComponent::ConstructionSite(c.clone()));
+    collect::<Intent>(world, &mut entities, |c| SavedComponent::Intent {
+        name: c.name.clone(),
+        owner: c.owner.inner(),
+        task: c.task,
+        target: c
+            .target
+            .iter()
+            .map(|t| match t {
+                Target::LOCATION(p) => SavedTarget::Location(*p),
+                Target::ENTITY(id) => SavedTarget::Entity(id.inner()),
+            })
+            .collect(),
+        turn: c.turn.0,
+    });
+    collect::<AddsGas>(world, &mut entities, |c| SavedComponent::AddsGas(c.clone()));
+    collect::<RemovesGas>(world, &mut entities, |_| SavedComponent::RemovesGas);
+    collect::<Inventory>(world, &mut entities, |c| SavedComponent::Inventory {
+        capacity: c.capacity,
+        items: ids_to_inner(&c.items),
+    });
+    collect::<Equipment>(world, &mut entities, |c| SavedComponent::Equipment {
+        items: c.items.iter().map(|(slot, item)| (*slot, item.map(|id| id.inner()))).collect(),
+    });
+    collect::<Equippable>(world, &mut entities, |c| SavedComponent::Equippable(*c));
+    collect::<Equipped>(world, &mut entities, |c| SavedComponent::Equipped {
+        owner: c.owner.inner(),
+        slot: c.slot,
+    });
+    collect::<Item>(world, &mut entities, |c| SavedComponent::Item(*c));
+    collect::<Consumable>(world, &mut entities, |_| SavedComponent::Consumable);
+    collect::<MeleePowerBonus>(world, &mut entities, |c| SavedComponent::MeleePowerBonus(*c));
+    collect::<MeleeDefenseBonus>(world, &mut entities, |c| SavedComponent::MeleeDefenseBonus(*c));
+    collect::<ProvidesHealing>(world, &mut entities, |c| SavedComponent::ProvidesHealing(*c));
+    collect::<Ranged>(world, &mut entities, |c| SavedComponent::Ranged(*c));
+    collect::<DealsDamage>(world, &mut entities, |c| SavedComponent::DealsDamage(*c));
+    collect::<InflictsStatus>(world, &mut entities, |c| SavedComponent::InflictsStatus(*c));
+    collect::<AreaOfEffect>(world, &mut entities, |c| SavedComponent::AreaOfEffect(*c));
+    collect::<StatusEffects>(world, &mut entities, |c| SavedComponent::StatusEffects(c.clone()));
+    collect::<CausesFire>(world, &mut entities, |c| SavedComponent::CausesFire(*c));
+    collect::<Flammable>(world, &mut entities, |_| SavedComponent::Flammable);
+    collect::<Mana>(world, &mut entities, |c| SavedComponent::Mana(*c));
+    collect::<Spell>(world, &mut entities, |c| SavedComponent::Spell(*c));
+    collect::<Spellbook>(world, &mut entities, |c| SavedComponent::Spellbook {
+        spells: ids_to_inner(&c.spells),
+    });
+
+    // particles are recreated by the systems that spawn them
+    for (id, _) in world.borrow::<View<Particle>>().unwrap().iter().with_id() {
+        entities.remove(&id.inner());
+    }
+
+    entities
+}
+
+/// Saves only the given entities, used to put a level away when the player leaves it
+pub fn save_entities(world: &World, ids: &[EntityId]) -> Vec<SavedEntity> {
+    let mut entities = collect_entities(world);
+
+    ids.iter()
+        .filter_map(|id| entities.remove(&id.inner()).map(|components| SavedEntity { id: id.inner(), components }))
+        .collect()
+}
+
+pub fn save_world(world: &World, settings: &GameSettings) -> SaveGame {
+    let entities = collect_entities(world);
+
+    SaveGame {
+        settings: settings.clone(),
+        turn: world.borrow::<UniqueView<Turn>>().unwrap().0,
+        tick: world.borrow::<UniqueView<Tick>>().unwrap().0,
+        player_id: world.borrow::<UniqueView<PlayerID>>().unwrap().0.inner(),
+        player_pos: world.borrow::<UniqueView<PPoint>>().unwrap().0,
+        log: world.borrow::<UniqueView<GameLog>>().unwrap().messages.clone(),
+        rng: world.borrow::<UniqueView<RNG>>().unwrap().0.clone(),
+        map: world.borrow::<UniqueView<Map>>().unwrap().clone(),
+        entities: entities
+            .into_iter()
+            .map(|(id, components)| SavedEntity { id, components })
+            .collect(),
+        levels: world.borrow::<UniqueView<Levels>>().unwrap().stored.clone(),
+        war: world.borrow::<UniqueView<War>>().unwrap().clone(),
+    }
+}
+
+/// Adds every saved entity to the world, returns a map of old ids to new ones
+pub fn load_entities(world: &mut World, entities: Vec<SavedEntity>) -> HashMap<u64, EntityId> {
+    load_entities_with_ids(world, entities, HashMap::new())
+}
+
+/// Same as `load_entities`, `ids` holds entities that are still alive so references to them are kept
+pub fn load_entities_with_ids(world: &mut World, entities: Vec<SavedEntity>, mut ids: HashMap<u64, EntityId>) -> HashMap<u64, EntityId> {
+    for e in entities.iter() {
+        ids.insert(e.id, world.add_entity(()));
+    }
+
+    for e in entities {
+        let id = ids[&e.id];
+        for c in e.components {
+            add_component(world, id, c, &ids);
+        }
+    }
+
+    ids
+}
+
+fn add_component(world: &mut World, id: EntityId, c: SavedComponent, ids: &HashMap<u64, EntityId>) {
+    match c {
+        SavedComponent::Position(c) => world.add_component(id, c),
+        SavedComponent::Renderable(c) => world.add_component(id, c),
+        SavedComponent::Vision(c) => world.add_component(id, Vision { dirty: true, ..c }),
+        SavedComponent::Name(c) => world.add_component(id, c),
+        SavedComponent::Player => world.add_component(id, Player {}),
+        SavedComponent::Orc => world.add_component(id, Orc {}),
+        SavedComponent::Raider => world.add_component(id, Raider {}),
+        SavedComponent::Fish => world.add_component(id, Fish {}),
+        SavedComponent::Actor(c) => world.add_component(id, c),
+        SavedComponent::PlankHouse { housing_cap, villagers } => world.add_component(
+            id,
+            PlankHouse {
+                housing_cap,
+                villagers: remap_all(ids, &villagers),
+            },
+        ),
+        SavedComponent::ChiefHouse => world.add_component(id, ChiefHouse {}),
+        SavedComponent::LumberMill => world.add_component(id, LumberMill {}),
+        SavedComponent::FishCleaner => world.add_component(id, FishCleaner {}),
+        SavedComponent::Granary => world.add_component(id, Granary {}),
+        SavedComponent::Spawner(c) => world.add_component(id, c),
+        SavedComponent::Tree => world.add_component(id, Tree {}),
+        SavedComponent::Sapling(c) => world.add_component(id, c),
+        SavedComponent::Locomotive(c) => world.add_component(id, c),
+        SavedComponent::Energy(c) => world.add_component(id, c),
+        SavedComponent::BlocksTile => world.add_component(id, BlocksTile {}),
+        SavedComponent::PhysicalStats(c) => world.add_component(id, c),
+        SavedComponent::SpatialKnowledge { tiles } => world.add_component(
+            id,
+            SpatialKnowledge {
+                tiles: tiles
+                    .into_iter()
+                    .map(|(idx, tile, entities)| (idx, (tile, remap_all(ids, &entities))))
+                    .collect(),
+            },
+        ),
+        SavedComponent::DijkstraMapToMe => world.add_component(
+            id,
+            DijkstraMapToMe {
+                map: DijkstraMap::new_empty(0, 0, 0.),
+            },
+        ),
+        SavedComponent::IsCamera => world.add_component(id, IsCamera {}),
+        SavedComponent::Aging(c) => world.add_component(id, c),
+        SavedComponent::Needs(c) => world.add_component(id, c),
+        SavedComponent::ConstructionSite(c) => world.add_component(id, c),
+        SavedComponent::Intent { name, owner, task, target, turn } => {
+            if let Some(owner) = ids.get(&owner) {
+                let target = target
+                    .into_iter()
+                    .filter_map(|t| match t {
+                        SavedTarget::Location(p) => Some(Target::from(p)),
+                        SavedTarget::Entity(e) => ids.get(&e).map(|e| Target::from(*e)),
+                    })
+                    .collect();
+
+                world.add_component(id, Intent { name, owner: *owner, task, target, turn: Turn(turn) });
+            }
+        }
+        SavedComponent::AddsGas(c) => world.add_component(id, c),
+        SavedComponent::RemovesGas => world.add_component(id, RemovesGas {}),
+        SavedComponent::Inventory { capacity, items } => world.add_component(
+            id,
+            Inventory {
+                capacity,
+                items: remap_all(ids, &items),
+            },
+        ),
+        SavedComponent::Equipment { items } => {
+            let mut equipment = Equipment::new();
+            for (slot, item) in items {
+                if let Some(item) = item.and_then(|i| ids.get(&i)) {
+                    equipment.equip(*item, slot);
+                }
+            }
+            world.add_component(id, equipment);
+        }
+        SavedComponent::Equippable(c) => world.add_component(id, c),
+        SavedComponent::Equipped { owner, slot } => {
+            if let Some(owner) = ids.get(&owner) {
+                world.add_component(id, Equipped { owner: *owner, slot });
+            }
+        }
+        SavedComponent::Item(c) => world.add_component(id, c),
+        SavedComponent::Consumable => world.add_component(id, Consumable {}),
+        SavedComponent::MeleePowerBonus(c) => world.add_component(id, c),
+        SavedComponent::MeleeDefenseBonus(c) => world.add_component(id, c),
+        SavedComponent::ProvidesHealing(c) => world.add_component(id, c),
+        SavedComponent::Ranged(c) => world.add_component(id, c),
+        SavedComponent::DealsDamage(c) => world.add_component(id, c),
+        SavedComponent::InflictsStatus(c) => world.add_component(id, c),
+        SavedComponent::AreaOfEffect(c) => world.add_component(id, c),
+        SavedComponent::StatusEffects(c) => world.add_component(id, c),
+        SavedComponent::CausesFire(c) => world.add_component(id, c),
+        SavedComponent::Flammable => world.add_component(id, Flammable {}),
+        SavedComponent::Mana(c) => world.add_component(id, c),
+        SavedComponent::Spell(c) => world.add_component(id, c),
+        SavedComponent::Spellbook { spells } => world.add_component(
+            id,
+            Spellbook {
+                spells: remap_all(ids, &spells),
+            },
+        ),
+    }
+}
@@ -1,13 +1,19 @@
-use shipyard::World;
+use std::time::Instant;
+
+use shipyard::{UniqueView, UniqueViewMut, Workload, World};
 
 use crate::DISABLE_AI;
 
-use super::effects;
+use super::{components::Tick, effects};
+use system_energy::TICKS_PER_TURN;
 
 pub mod system_ai;
 pub mod system_ai_fish;
+pub mod system_ai_wolf;
+pub mod system_ai_wizard;
 pub mod system_cleanup;
 pub mod system_dissasemble;
+pub mod system_energy;
 pub mod system_fire;
 pub mod system_map_indexing;
 pub mod system_melee_combat;
@@ -18,36 +24,185 @@ pub mod system_inventory;
 pub mod system_item_use;
 pub mod system_gas;
 pub mod system_time;
+pub mod system_status;
+pub mod system_spells;
+pub mod system_needs;
+pub mod system_stockpile;
+pub mod system_construction;
+pub mod system_farming;
+pub mod system_ecology;
+pub mod system_raids;
+pub mod system_war;
+
+/// Every system a schedule can refer to, in the order they run by default.
+/// Names can appear more than once, effects are applied between groups of systems
+pub const DEFAULT_SCHEDULE: &[&str] = &[
+    "energy",
+    "fire",
+    "status",
+    "spells",
+    "effects",
+    "map_indexing",
+    "pathfinding",
+    "fish_ai",
+    "wolf_ai",
+    "wizard_ai",
+    "stockpile",
+    "ai",
+    "time",
+    "needs",
+    "construction",
+    "farming",
+    "ecology",
+    "fish_population",
+    "raids",
+    "war",
+    "effects",
+    "melee_combat",
+    "inventory",
+    "dissasemble",
+    "drop_item",
+    "unequip_item",
+    "item_use",
+    "spawn_particles",
+    "effects",
+    "gas",
+    "map_indexing",
+    "visibility",
+    "cleanup",
+];
+
+// turned off at startup when DISABLE_AI is set
+const AI_SYSTEMS: &[&str] = &["pathfinding", "fish_ai", "wolf_ai", "wizard_ai", "ai", "time"];
+
+// the schedule runs every tick, these only run on the first tick of a turn. Pathfinding checks for itself since terrain changes can need it sooner
+const TURN_SYSTEMS: &[&str] = &["fire", "status", "spells", "time", "needs", "construction", "farming", "ecology", "fish_population", "raids", "war", "gas"];
+
+/// Registers one workload per system so a `Schedule` can run them by name
+pub fn register_workloads(world: &World) {
+    let workloads = vec![
+        Workload::new("energy").with_system(system_energy::run_energy_system),
+        Workload::new("fire").with_system(system_fire::run_fire_system),
+        Workload::new("status").with_system(system_status::run_status_system),
+        Workload::new("spells").with_system(system_spells::run_spell_system),
+        Workload::new("effects").with_system(effects::run_effects_queue),
+        Workload::new("map_indexing").with_system(system_map_indexing::run_map_indexing_system),
+        Workload::new("pathfinding").with_system(system_pathfinding::run_pathfinding_system),
+        Workload::new("fish_ai").with_system(system_ai_fish::run_fish_ai),
+        Workload::new("wolf_ai").with_system(system_ai_wolf::run_wolf_ai),
+        Workload::new("wizard_ai").with_system(system_ai_wizard::run_wizard_ai),
+        Workload::new("stockpile").with_system(system_stockpile::run_stockpile_system),
+        Workload::new("ai").with_system(system_ai::run_ai_system),
+        Workload::new("time").with_system(system_time::run_time_system),
+        Workload::new("needs").with_system(system_needs::run_needs_system),
+        Workload::new("construction").with_system(system_construction::run_construction_system),
+        Workload::new("farming").with_system(system_farming::run_farming_system),
+        Workload::new("ecology").with_system(system_ecology::run_ecology_system),
+        Workload::new("fish_population").with_system(system_ai_fish::run_fish_population),
+        Workload::new("raids").with_system(system_raids::run_raid_system),
+        Workload::new("war").with_system(system_war::run_war_system),
+        Workload::new("melee_combat").with_system(system_melee_combat::run_melee_combat_system),
+        Workload::new("inventory").with_system(system_inventory::run_inventory_system),
+        Workload::new("dissasemble").with_system(system_dissasemble::run_dissasemble_system),
+        Workload::new("drop_item").with_system(system_inventory::run_drop_item_system),
+        Workload::new("unequip_item").with_system(system_inventory::run_unequip_item_system),
+        Workload::new("item_use").with_system(system_item_use::run_item_use_system),
+        Workload::new("spawn_particles").with_system(system_particle::spawn_particles),
+        Workload::new("gas").with_system(system_gas::run_gas_system),
+        Workload::new("visibility").with_system(system_visibility::run_visibility_system),
+        Workload::new("cleanup").with_system(system_cleanup::run_cleanup_system),
+    ];
+
+    for workload in workloads {
+        if let Err(e) = workload.add_to_world(world) {
+            dbg!("Failed to register system", e);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScheduledSystem {
+    pub name: &'static str,
+    pub enabled: bool,
+    pub last_ms: f32, // wall time of the last run
+    pub avg_ms: f32,  // smoothed over recent runs
+}
+
+/// Ordered list of systems run each tick. Systems can be switched off at runtime
+#[derive(Clone, Debug)]
+pub struct Schedule {
+    pub systems: Vec<ScheduledSystem>,
+}
+
+impl Schedule {
+    /// Builds a schedule from system names, unknown names are skipped. An empty list gives the default schedule
+    pub fn new(names: &[String]) -> Self {
+        let mut systems = vec![];
 
-pub fn run_systems(world: &mut World, _player_turn: bool, ai_turn: bool) {
-    // if player_turn {
-    world.run(system_fire::run_fire_system);
-    // }
+        if names.is_empty() {
+            for name in DEFAULT_SCHEDULE.iter() {
+                systems.push(ScheduledSystem { name: *name, enabled: true, last_ms: 0., avg_ms: 0. });
+            }
+        } else {
+            for name in names.iter() {
+                match DEFAULT_SCHEDULE.iter().find(|n| **n == name.as_str()) {
+                    Some(name) => systems.push(ScheduledSystem { name: *name, enabled: true, last_ms: 0., avg_ms: 0. }),
+                    None => {
+                        dbg!("Unknown system in schedule", name);
+                    }
+                }
+            }
+        }
 
-    world.run(effects::run_effects_queue);
-    world.run(system_map_indexing::run_map_indexing_system);
+        let mut schedule = Self { systems };
 
-    if ai_turn && !DISABLE_AI {
-        world.run(system_pathfinding::run_pathfinding_system);
-        world.run(system_ai_fish::run_fish_ai);
-        world.run(system_ai::run_ai_system);
-        world.run(system_time::run_time_system);
+        if DISABLE_AI {
+            for name in AI_SYSTEMS.iter() {
+                schedule.set_enabled(name, false);
+            }
+        }
+
+        schedule
+    }
+
+    /// Runs one tick
+    pub fn run(&mut self, world: &World) {
+        let new_turn = match world.borrow::<UniqueView<Tick>>() {
+            Ok(tick) => tick.0 % TICKS_PER_TURN == 0,
+            Err(_) => true,
+        };
+
+        for system in self.systems.iter_mut() {
+            if !system.enabled || (!new_turn && TURN_SYSTEMS.contains(&system.name)) {
+                continue;
+            }
+
+            let start = Instant::now();
+            if let Err(e) = world.run_workload(system.name) {
+                dbg!("Failed to run system", system.name, e);
+            }
+
+            system.last_ms = start.elapsed().as_secs_f32() * 1000.;
+            system.avg_ms = system.avg_ms * 0.9 + system.last_ms * 0.1;
+        }
+
+        if let Ok(mut tick) = world.borrow::<UniqueViewMut<Tick>>() {
+            tick.0 += 1;
+        }
     }
 
-    world.run(effects::run_effects_queue);
-
-    world.run(system_melee_combat::run_melee_combat_system);
-    world.run(system_inventory::run_inventory_system);
-    world.run(system_dissasemble::run_dissasemble_system);
-    world.run(system_inventory::run_drop_item_system);
-    world.run(system_inventory::run_unequip_item_system);
-    world.run(system_item_use::run_item_use_system);
-    world.run(system_particle::spawn_particles);
-
-    world.run(effects::run_effects_queue);
-    world.run(system_gas::run_gas_system);
-    world.run(system_map_indexing::run_map_indexing_system);
-    
-    world.run(system_visibility::run_visibility_system);
-    world.run(system_cleanup::run_cleanup_system);
-}
\ No newline at end of file
+    /// Enables or disables every entry with this name
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        for system in self.systems.iter_mut() {
+            if system.name == name {
+                system.enabled = enabled;
+            }
+        }
+    }
+
+    pub fn toggle(&mut self, idx: usize) {
+        if let Some(system) = self.systems.get_mut(idx) {
+            system.enabled = !system.enabled;
+        }
+    }
+}
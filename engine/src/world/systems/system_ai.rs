@@ -2,13 +2,15 @@ use crate::ai::input::InputTargets;
 use crate::ai::intent::{Intent, Task};
 use crate::ai::labors::AIBehaviors;
 use crate::ai::AI;
-use crate::world::components::{Actor, ActorType, DijkstraMapToMe, Faction, Position, Spawner, SpawnerType, Turn, PlayerID, Vision, Item, ItemType};
-use crate::world::effects::{add_effect, EffectType};
+use crate::world::components::{Actor, ActorType, DijkstraMapToMe, Energy, Faction, PhysicalStats, Position, Raider, Spawner, SpawnerType, Turn, Vision, Item, ItemType};
+use crate::world::effects::{EffectQueue, EffectType, Targets};
 use crate::entity_factory;
+use crate::raws::factions::{FactionTable, Relation};
 use crate::world::map::Map;
+use crate::world::systems::{system_energy, system_farming, system_needs, system_raids, system_spells, system_stockpile, system_war};
+use crate::world::systems::system_fire::NEW_FIRE_TURNS;
 use crate::tiles::TileType;
-use crate::utils::vision::vision_contains;
-use crate::utils::{get_neighbors, Target, InvalidPoint};
+use crate::utils::{away_from, get_neighbors, Target, InvalidPoint};
 use rltk::{BaseMap, Point};
 use shipyard::{AddComponent, AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueView, View, ViewMut, UniqueViewMut};
 
@@ -16,45 +18,74 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
     let mut to_move_from_to: Vec<(EntityId, Point, Point)> = vec![];
     let mut to_fish: Vec<(EntityId, Point)> = vec![];
     let mut to_attack: Vec<(EntityId, Point)> = vec![];
-    let mut to_spawn_fish: Vec<Point> = vec![];
-    let mut to_spawn_orc: Vec<(Point, Faction)> = vec![];
+    let mut to_spawn: Vec<(&str, Point, Faction)> = vec![];
     let mut to_deposit_items: Vec<(EntityId, Intent)> = vec![];
+    let mut to_eat: Vec<EntityId> = vec![];
+    let mut to_sleep: Vec<EntityId> = vec![];
+    let mut to_take: Vec<(EntityId, ItemType)> = vec![];
+    let mut to_till: Vec<usize> = vec![];
+    let mut to_sow: Vec<usize> = vec![];
+    let mut to_harvest: Vec<(EntityId, usize)> = vec![];
+    let mut to_cast: Vec<Intent> = vec![];
 
-    store.run(
+    // wait until there's enough energy saved up
+    let ready: Vec<EntityId> = store.run(|venergy: View<Energy>| venergy.iter().with_id().filter(|(_, energy)| system_energy::can_act(energy)).map(|(id, _)| id).collect());
+
+    // everyone decides first, then the intents are acted on below
+    let decided: Vec<(EntityId, Intent)> = store.run(
         |map: UniqueView<Map>,
          turn: UniqueView<Turn>,
-         playerid: UniqueView<PlayerID>,
+         factions: UniqueView<FactionTable>,
          vactor: View<Actor>,
          vpos: View<Position>,
          vvision: View<Vision>,
-         vdijkstra: View<DijkstraMapToMe>,
-         mut vintent: ViewMut<Intent>,
-         vspawner: ViewMut<Spawner>| {
+         vstats: View<PhysicalStats>,
+         vraider: View<Raider>,
+         vspawner: View<Spawner>,
+         vintent: View<Intent>| {
+            let mut decided = vec![];
+
             for (id, (actor, pos)) in (&vactor, &vpos).iter().with_id() {
+                if !ready.contains(&id) {
+                    continue;
+                }
+
                 let new_intent = match actor.atype {
                     ActorType::Player => continue,
                     ActorType::Fish => continue,
-                    ActorType::Orc | ActorType::Wolf=> {
-                        if let Ok(vision) = vvision.get(id) {
-                            if vision_contains(&store, vision.clone(), playerid.0) { //todo vision.clone bad
-                                Intent {
-                                    name: "Attack player".to_string(),
-                                    owner: id,
-                                    task: Task::Attack(InputTargets::Player),
-                                    target: vec![Target::ENTITY(playerid.0)],
-                                    turn: *turn,
-                                }
-                            } else {
-                                continue;
+                    ActorType::Wolf => continue, // packs are run by system_ai_wolf
+                    ActorType::Wizard => continue, // run by system_ai_wizard
+                    ActorType::Orc => {
+                        let enemy = match vvision.get(id) {
+                            Ok(vision) => nearest_enemy(&map, &factions, &vactor, &vstats, vision, id, pos),
+                            Err(_) => None,
+                        };
+
+                        if let Some(enemy) = enemy {
+                            Intent {
+                                name: "Attack enemy".to_string(),
+                                owner: id,
+                                task: Task::Attack(InputTargets::Enemy),
+                                target: vec![Target::ENTITY(enemy)],
+                                turn: *turn,
+                            }
+                        } else if vraider.get(id).is_ok() {
+                            match system_raids::raider_intent(&map, &store, id, pos, *turn) {
+                                Some(intent) => intent,
+                                None => continue,
                             }
                         } else {
-                            continue;
+                            match system_war::minion_intent(&map, &store, id, actor.faction, pos, vintent.get(id).ok(), *turn) {
+                                Some(intent) => intent,
+                                None => continue,
+                            }
                         }
                     },
                     ActorType::Villager => AI::choose_intent(actor.actions.clone(), &store, id), //todo clone here is messy
                     ActorType::Spawner => {
                         if let Ok(spawner) = vspawner.get(id) {
-                            if turn.0 % spawner.rate == 0 {
+                            // fish stocks are handled by system_ai_fish
+                            if turn.0 % spawner.rate == 0 && spawner.typ != SpawnerType::Fish {
                                 Intent {
                                     name: "spawn".to_string(),
                                     owner: id,
@@ -94,7 +125,31 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
                     }
                 };
 
-                // let new_intent = labors::get_action(&store, id).intent;
+                decided.push((id, new_intent));
+            }
+
+            decided
+        },
+    );
+
+    store.run(
+        |map: UniqueView<Map>,
+         mut effects: UniqueViewMut<EffectQueue>,
+         vactor: View<Actor>,
+         vpos: View<Position>,
+         vdijkstra: View<DijkstraMapToMe>,
+         mut vintent: ViewMut<Intent>,
+         mut venergy: ViewMut<Energy>| {
+            for (id, new_intent) in decided {
+                let (actor, pos) = match (&vactor, &vpos).get(id) {
+                    Ok(found) => found,
+                    Err(_) => continue,
+                };
+
+                if let Ok(energy) = (&mut venergy).get(id) {
+                    energy.value -= system_energy::task_cost(&new_intent.task);
+                }
+
                 vintent.add_component_unchecked(id, new_intent.clone());
 
                 //world.query::<(&Villager, &mut Position, &mut Intent)>().iter() {
@@ -102,7 +157,7 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
                     Task::Fish => {
                         to_fish.push((id, pos.ps[0]));
                     }
-                    Task::Explore => add_effect(Some(id), EffectType::Explore {}),
+                    Task::Explore => effects.add_effect(Some(id), EffectType::Explore {}),
                     Task::ExchangeInfo => todo!(),
                     Task::MoveTo(_) => {
                         if let Target::ENTITY(target) = new_intent.target[0] {
@@ -129,12 +184,20 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
                             to_move_from_to.push((id, pos.ps[0], loc));
                         }
                     }
-                    Task::Destroy(_) => {
-                        // todo!() this should happen here and not in disassemble
+                    Task::Destroy(target) => {
+                        // tiles are dug out here, filling trenches back in. Entities are still taken apart in system_dissasemble
+                        if let Target::LOCATION(loc) = new_intent.target[0] {
+                            let tile_idx = map.point_idx(loc);
+                            if target == InputTargets::Trench {
+                                effects.add_effect(Some(id), EffectType::Fill { tile_idx });
+                            } else {
+                                effects.add_effect(Some(id), EffectType::Dig { tile_idx });
+                            }
+                        }
                     }
                     Task::PickUpItem(_) => {            
                         if let Target::ENTITY(e) = new_intent.target[0] {
-                            add_effect(Some(id), EffectType::PickUp { entity: e });
+                            effects.add_effect(Some(id), EffectType::PickUp { entity: e });
                         }
                     },
                     Task::DropItem => todo!(),
@@ -162,15 +225,55 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
                     Task::Spawn(target) => {
                         match target {
                             InputTargets::Orc => {
-                                to_spawn_orc.push((pos.ps[0], actor.faction));
+                                to_spawn.push(("Orc", pos.ps[0], actor.faction));
                             }
                             InputTargets::Fish => {
-                                to_spawn_fish.push(pos.ps[0]);
-                                // entity_factory::fish(&mut store, pos.ps[0].x, pos.ps[0].y);
+                                to_spawn.push(("Fish", pos.ps[0], actor.faction));
                             }
                             _ => {}
                         }
                     }
+                    Task::Eat(_) => {
+                        to_eat.push(id);
+                    }
+                    Task::Sleep(_) => {
+                        to_sleep.push(id);
+                    }
+                    Task::TakeFromStockpile(target) => {
+                        if let Some(typ) = target.item_type() {
+                            to_take.push((id, typ));
+                        }
+                    }
+                    Task::Till(_) => {
+                        if let Target::LOCATION(loc) = new_intent.target[0] {
+                            to_till.push(map.point_idx(loc));
+                        }
+                    }
+                    Task::Sow(_) => {
+                        if let Target::LOCATION(loc) = new_intent.target[0] {
+                            to_sow.push(map.point_idx(loc));
+                        }
+                    }
+                    Task::Harvest(_) => {
+                        if let Target::LOCATION(loc) = new_intent.target[0] {
+                            to_harvest.push((id, map.point_idx(loc)));
+                        }
+                    }
+                    Task::Flee(_) => {
+                        if let Target::ENTITY(target) = new_intent.target[0] {
+                            if let Ok(target_pos) = vpos.get(target) {
+                                to_move_from_to.push((id, pos.ps[0], away_from(&map, pos.ps[0], target_pos.ps[0])));
+                            }
+                        }
+                    }
+                    Task::Burn(_) => {
+                        if let Target::ENTITY(target) = new_intent.target[0] {
+                            effects.add_effect(Some(id), EffectType::Fire { turns: NEW_FIRE_TURNS, target: Targets::Single { target } });
+                        }
+                    }
+                    Task::Cast(_) => {
+                        to_cast.push(new_intent);
+                    }
                 }
             }
         },
@@ -178,9 +281,10 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
 
     for (e, from, to) in to_move_from_to {
         let map = store.borrow::<UniqueViewMut<Map>>().unwrap();
+        let mut effects = store.borrow::<UniqueViewMut<EffectQueue>>().unwrap();
 
         if map.get_pathing_distance(from.to_index(map.size.0), to.to_index(map.size.0)) <= 2.1 {
-            add_effect(
+            effects.add_effect(
                 Some(e),
                 EffectType::Move {
                     tile_idx: to.to_index(map.size.0),
@@ -192,7 +296,7 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
         let path = map.get_path(from, to);
 
         if path.success && path.steps.len() > 1 {
-            add_effect(
+            effects.add_effect(
                 Some(e),
                 EffectType::Move {
                     tile_idx: path.steps[1],
@@ -203,6 +307,7 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
 
     for (e, p) in to_fish {
         let map = store.borrow::<UniqueView<Map>>().unwrap();
+        let mut effects = store.borrow::<UniqueViewMut<EffectQueue>>().unwrap();
 
         let n = get_neighbors(p);
         let adj_water: Vec<&Point> = n
@@ -220,7 +325,7 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
                 if let Ok(actor) = vactor.get(*te) {
                     if actor.atype == ActorType::Fish {
                         //found a target
-                        add_effect(Some(e), EffectType::PickUp { entity: *te });
+                        effects.add_effect(Some(e), EffectType::PickUp { entity: *te });
                         break;
                     }
                 }
@@ -230,8 +335,9 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
 
     for (eid, point) in to_attack.iter() {
         let map = store.borrow::<UniqueView<Map>>().unwrap();
+        let mut effects = store.borrow::<UniqueViewMut<EffectQueue>>().unwrap();
 
-        add_effect(
+        effects.add_effect(
             Some(*eid),
             EffectType::MoveOrAttack {
                 tile_idx: map.point_idx(*point),
@@ -239,27 +345,47 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
         );
     }
 
-    for pos in to_spawn_fish.iter() {
-        entity_factory::fish(&mut store, pos.to_xy());
+    // spawned entities fight for whoever spawned them
+    for (name, pos, faction) in to_spawn.iter() {
+        entity_factory::spawn_for_faction(&mut store, name, pos.to_xy(), *faction);
     }
 
-    for (pos, faction) in to_spawn_orc.iter() {
-        let e = entity_factory::orc(&mut store, pos.to_xy());
-        store.run(|mut vactor: ViewMut<Actor>| {
-            if let Ok(spawned_actor) = (&mut vactor).get(e) {
-                spawned_actor.faction = *faction;
-            }
-        });
+    for id in to_eat.iter() {
+        system_needs::eat(&store, *id);
+    }
+
+    for id in to_sleep.iter() {
+        system_needs::sleep(&store, *id);
+    }
+
+    for (id, typ) in to_take.iter() {
+        system_stockpile::take(&store, *typ, *id);
+    }
+
+    for idx in to_till.iter() {
+        system_farming::till(&mut store.borrow::<UniqueViewMut<Map>>().unwrap(), *idx);
+    }
+
+    for idx in to_sow.iter() {
+        system_farming::sow(&mut store.borrow::<UniqueViewMut<Map>>().unwrap(), *idx);
+    }
+
+    for (id, idx) in to_harvest.iter() {
+        system_farming::harvest(&mut store, *id, *idx);
+    }
+
+    for intent in to_cast.iter() {
+        system_spells::cast(&store, intent);
     }
 
     for (id, _) in to_deposit_items.iter() {
-        store.run(|mut vactor: ViewMut<Actor>, vintent: View<Intent>, vitem: ViewMut<Item>| {
+        store.run(|mut effects: UniqueViewMut<EffectQueue>, mut vactor: ViewMut<Actor>, vintent: View<Intent>, vitem: ViewMut<Item>| {
             if let Ok((actor, intent)) = (&mut vactor, &vintent).get(*id) {
                 if let Target::ENTITY(item) = intent.target[0] {
                     if let Target::ENTITY(target) = intent.target[1] {
                         // TODO this looks like a race condition
-                        add_effect(Some(*id), EffectType::Drop { entity: item });
-                        add_effect(Some(target), EffectType::PickUp { entity: item });
+                        effects.add_effect(Some(*id), EffectType::Drop { entity: item });
+                        effects.add_effect(Some(target), EffectType::PickUp { entity: item });
 
                         // can this be exploited by the ai? 
                         for b in actor.behaviors.iter() {
@@ -270,6 +396,9 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
                                 if *b == AIBehaviors::GatherWood && item.typ == ItemType::Log {
                                     actor.score += 10;
                                 }
+                                if *b == AIBehaviors::GatherGrain && item.typ == ItemType::Grain {
+                                    actor.score += 10;
+                                }
                             }
                         }
                     }
@@ -278,3 +407,24 @@ pub fn run_ai_system(mut store: AllStoragesViewMut) {
         });
     }
 }
+
+/// Closest thing in view that can be fought and that the owner's faction is hostile to
+fn nearest_enemy(map: &Map, factions: &FactionTable, vactor: &View<Actor>, vstats: &View<PhysicalStats>, vision: &Vision, id: EntityId, pos: &Position) -> Option<EntityId> {
+    let mut nearest: Option<(EntityId, f32)> = None;
+
+    for p in vision.visible_tiles.iter() {
+        let dist = rltk::DistanceAlg::Pythagoras.distance2d(pos.ps[0], *p);
+        if nearest.map_or(false, |(_, d)| d <= dist) {
+            continue;
+        }
+
+        for e in map.tile_content[map.point_idx(*p)].iter() {
+            if *e != id && vstats.get(*e).is_ok() && factions.relation_between(vactor, id, *e) == Relation::Hostile {
+                nearest = Some((*e, dist));
+                break;
+            }
+        }
+    }
+
+    nearest.map(|(e, _)| e)
+}
@@ -1,76 +1,210 @@
-use crate::world::components::{Actor, ActorType, Position};
-use crate::world::effects::{add_effect, EffectType};
+use std::collections::HashSet;
+
+use crate::ai::intent::{Intent, Task};
+use crate::world::components::{Actor, ActorType, Energy, Position, Spawner, SpawnerType, Turn, RNG};
+use crate::world::effects::{EffectQueue, EffectType};
 use crate::world::map::Map;
+use crate::world::systems::system_energy::{self, ACTION_COST};
+use crate::entity_factory;
 use crate::tiles::TileType;
-use rand::prelude::SliceRandom;
-use rand::thread_rng;
-use rltk::Point;
-use shipyard::{EntityId, IntoIter, IntoWithId, UniqueView, View};
+use crate::utils::shuffle;
+use rltk::{DistanceAlg, Point};
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View, ViewMut};
 
 // Leaving this in a separate system for now as I suspect AI is going to change significantly
 
-// currently fish only move east
-pub fn run_fish_ai(map: UniqueView<Map>, vpos: View<Position>, vactor: View<Actor>) {
-    let mut to_try_move: Vec<(EntityId, Point)> = vec![];
-    let mut to_remove: Vec<EntityId> = vec![];
+/// Fish within this range of each other school together
+pub const SCHOOL_RANGE: f32 = 4.0;
+
+/// Fish keep at least this far from anything fishing
+pub const SCARE_RANGE: f32 = 1.5;
+
+/// Water tiles a region needs per fish it can support
+pub const WATER_PER_FISH: i32 = 10;
+
+/// Fraction of the stock born each spawn, before crowding slows it down
+pub const FISH_GROWTH: f32 = 0.05;
+
+// fish swim around their body of water, sticking to their school and away from anyone fishing
+pub fn run_fish_ai(
+    map: UniqueView<Map>,
+    mut rng: UniqueViewMut<RNG>,
+    mut effects: UniqueViewMut<EffectQueue>,
+    vpos: View<Position>,
+    vactor: View<Actor>,
+    vintent: View<Intent>,
+    mut venergy: ViewMut<Energy>,
+) {
+    let mut fish: Vec<(EntityId, Point)> = vec![];
+    let mut fishers: Vec<Point> = vec![];
 
     for (id, (pos, actor)) in (&vpos, &vactor).iter().with_id() {
-        if actor.atype != ActorType::Fish {
-            continue;
+        match actor.atype {
+            ActorType::Fish => {
+                if pos.ps.len() == 1 {
+                    fish.push((id, pos.ps[0]));
+                } else {
+                    dbg!("ERROR: multi-tile fish not supported");
+                }
+            }
+            // villagers with rods and wolves on the bank alike
+            _ => {
+                if let Ok(intent) = vintent.get(id) {
+                    if intent.task == Task::Fish {
+                        fishers.push(pos.ps[0]);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut taken: HashSet<Point> = fish.iter().map(|(_, p)| *p).collect();
+
+    for (id, pos) in fish.iter() {
+        match (&mut venergy).get(*id) {
+            Ok(energy) if system_energy::can_act(energy) => energy.value -= ACTION_COST,
+            _ => continue,
         }
 
-        if pos.ps.len() == 1 {
-            // if at edge of map, remove fish
+        // head for the middle of the school
+        let school: Vec<Point> = fish
+            .iter()
+            .filter(|(other, p)| other != id && DistanceAlg::Pythagoras.distance2d(*pos, *p) <= SCHOOL_RANGE)
+            .map(|(_, p)| *p)
+            .collect();
+        let center = if school.is_empty() {
+            None
+        } else {
+            Some(Point::new(
+                school.iter().map(|p| p.x).sum::<i32>() / school.len() as i32,
+                school.iter().map(|p| p.y).sum::<i32>() / school.len() as i32,
+            ))
+        };
 
-            let pos = pos.ps[0];
+        let mut potential_spaces: Vec<Point> = vec![];
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let p = Point::new(pos.x + dx, pos.y + dy);
+                if (dx, dy) == (0, 0) || !map.in_bounds((p.x, p.y)) || taken.contains(&p) {
+                    continue;
+                }
 
-            if pos.x >= map.size.0 - 2 {
-                to_remove.push(id);
-            } else {
-                to_try_move.push((id, pos));
+                let scared = fishers.iter().any(|f| DistanceAlg::Pythagoras.distance2d(*f, p) <= SCARE_RANGE);
+                if map.tiles[map.point_idx(p)] == TileType::Water && !scared {
+                    potential_spaces.push(p);
+                }
             }
-        } else {
-            dbg!("ERROR: multi-tile fish not supported");
         }
+
+        // random among equally good spaces
+        shuffle(&mut potential_spaces, &mut rng.0);
+
+        let best = match center {
+            Some(c) => potential_spaces.iter().min_by(|a, b| {
+                let da = DistanceAlg::Pythagoras.distance2d(**a, c);
+                let db = DistanceAlg::Pythagoras.distance2d(**b, c);
+                da.partial_cmp(&db).unwrap()
+            }),
+            None => potential_spaces.first(),
+        };
+
+        if let Some(p) = best {
+            taken.remove(pos);
+            taken.insert(*p);
+            effects.add_effect(
+                Some(*id),
+                EffectType::Move {
+                    tile_idx: map.point_idx(*p),
+                },
+            );
+        }
+    }
+}
+
+/// Fish spawners restock their body of water. Births follow the current stock so an overfished lake recovers slowly and an empty one not at all
+pub fn run_fish_population(mut store: AllStoragesViewMut) {
+    let mut to_spawn: Vec<(i32, i32)> = vec![];
+
+    store.run(
+        |map: UniqueView<Map>, turn: UniqueView<Turn>, mut rng: UniqueViewMut<RNG>, vpos: View<Position>, vactor: View<Actor>, vspawner: View<Spawner>| {
+            for (spawner, pos) in (&vspawner, &vpos).iter() {
+                if spawner.typ != SpawnerType::Fish || spawner.rate <= 0 || turn.0 % spawner.rate != 0 {
+                    continue;
+                }
+
+                let region = match water_near(&map, pos.ps[0]) {
+                    Some(start) => water_region(&map, start),
+                    None => continue,
+                };
+                let tiles: HashSet<usize> = region.iter().copied().collect();
+
+                let stock = (&vactor, &vpos)
+                    .iter()
+                    .filter(|(actor, pos)| actor.atype == ActorType::Fish && tiles.contains(&map.point_idx(pos.ps[0])))
+                    .count() as f32;
+                let capacity = (region.len() as i32 / WATER_PER_FISH) as f32;
+                if capacity <= 0. {
+                    continue;
+                }
+
+                let births = f32::max(FISH_GROWTH * stock * (1. - stock / capacity), 0.);
+                let mut count = births as i32;
+                if rng.0.range(0., 1.) < births.fract() {
+                    count += 1;
+                }
+
+                for _ in 0..count {
+                    let idx = region[rng.0.range(0, region.len() as i32) as usize];
+                    if map.tile_content[idx].is_empty() {
+                        to_spawn.push(map.idx_xy(idx));
+                    }
+                }
+            }
+        },
+    );
+
+    for xy in to_spawn {
+        entity_factory::spawn_named(&mut store, "Fish", xy);
     }
+}
 
-    for (e, pos) in to_try_move {
-        let mut potential_spaces = vec![
-            Point { x: pos.x + 1, y: pos.y },
-            Point {
-                x: pos.x + 1,
-                y: pos.y + 1,
-            },
-            Point {
-                x: pos.x + 1,
-                y: pos.y - 1,
-            },
-        ];
-
-        potential_spaces.shuffle(&mut thread_rng());
-
-        for ps in potential_spaces {
-            let canmove = {
-                let idx = map.point_idx(ps);
-                map.tiles[idx] == TileType::Water
-            };
-
-            if canmove {
-                add_effect(
-                    Some(e),
-                    EffectType::Move {
-                        tile_idx: map.point_idx(ps),
-                    },
-                );
-                // movement::try_move_entity(e, point_diff(pos, ps), gs);
-                break;
+/// Water tile at or next to `p`
+pub fn water_near(map: &Map, p: Point) -> Option<usize> {
+    for dx in [0, -1, 1] {
+        for dy in [0, -1, 1] {
+            let xy = (p.x + dx, p.y + dy);
+            if map.in_bounds(xy) && map.get_tile(xy) == TileType::Water {
+                return Some(map.xy_idx(xy));
             }
         }
     }
 
-    for e in to_remove.iter() {
-        add_effect(Some(*e), EffectType::Delete { entity: *e });
-        // gs.world.delete_entity(*e);
-        // dbg!("ERROR: Need to delete entity");
+    None
+}
+
+/// Every water tile connected to `start`
+pub fn water_region(map: &Map, start: usize) -> Vec<usize> {
+    let mut region = vec![start];
+    let mut seen: HashSet<usize> = region.iter().copied().collect();
+    let mut i = 0;
+
+    while i < region.len() {
+        let (x, y) = map.idx_xy(region[i]);
+        i += 1;
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if !map.in_bounds((x + dx, y + dy)) {
+                    continue;
+                }
+
+                let idx = map.xy_idx((x + dx, y + dy));
+                if map.tiles[idx] == TileType::Water && seen.insert(idx) {
+                    region.push(idx);
+                }
+            }
+        }
     }
+
+    region
 }
@@ -0,0 +1,96 @@
+use crate::ai::input::InputTargets;
+use crate::ai::intent::{Intent, Task};
+use crate::ai::AI;
+use crate::raws::factions::{FactionTable, Relation};
+use crate::utils::{away_from, Target};
+use crate::world::components::{Actor, ActorType, Energy, PhysicalStats, Position, Turn, Vision};
+use crate::world::effects::{EffectQueue, EffectType};
+use crate::world::map::Map;
+use crate::world::systems::{system_energy, system_spells};
+use rltk::{DistanceAlg, Point};
+use shipyard::{AddComponent, AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View, ViewMut};
+
+/// Wizards back off from enemies closer than this
+pub const SAFE_DISTANCE: f32 = 3.0;
+
+pub fn run_wizard_ai(store: AllStoragesViewMut) {
+    let mut to_cast: Vec<Intent> = vec![];
+
+    store.run(
+        |map: UniqueView<Map>,
+         turn: UniqueView<Turn>,
+         factions: UniqueView<FactionTable>,
+         mut effects: UniqueViewMut<EffectQueue>,
+         vactor: View<Actor>,
+         vpos: View<Position>,
+         vstats: View<PhysicalStats>,
+         vvision: View<Vision>,
+         mut vintent: ViewMut<Intent>,
+         mut venergy: ViewMut<Energy>| {
+            for (id, (actor, pos)) in (&vactor, &vpos).iter().with_id() {
+                if actor.atype != ActorType::Wizard {
+                    continue;
+                }
+
+                let energy = match (&mut venergy).get(id) {
+                    Ok(energy) if system_energy::can_act(energy) => energy,
+                    _ => continue,
+                };
+
+                let me = pos.ps[0];
+
+                let mut closest: Option<(EntityId, Point, f32)> = None;
+                if let Ok(vision) = vvision.get(id) {
+                    for p in vision.visible_tiles.iter() {
+                        let dist = DistanceAlg::Pythagoras.distance2d(me, *p);
+                        if closest.map_or(false, |(_, _, d)| d <= dist) {
+                            continue;
+                        }
+
+                        for e in map.tile_content[map.point_idx(*p)].iter() {
+                            if *e != id && vstats.get(*e).is_ok() && factions.relation_between(&vactor, id, *e) == Relation::Hostile {
+                                closest = Some((*e, *p, dist));
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                let intent = match closest {
+                    Some((enemy, ep, dist)) if dist < SAFE_DISTANCE => {
+                        let away = away_from(&map, me, ep);
+                        if away != me {
+                            effects.add_effect(Some(id), EffectType::Move { tile_idx: map.point_idx(away) });
+                        }
+
+                        Intent {
+                            name: "fall back".to_string(),
+                            owner: id,
+                            task: Task::Flee(InputTargets::Enemy),
+                            target: vec![Target::from(enemy)],
+                            turn: *turn,
+                        }
+                    }
+                    // which spell to throw at whom is up to the utility ai
+                    _ => {
+                        let mut intent = AI::choose_intent(actor.actions.clone(), &store, id);
+                        intent.owner = id;
+                        intent.turn = *turn;
+                        intent
+                    }
+                };
+
+                if let Task::Cast(_) = intent.task {
+                    to_cast.push(intent.clone());
+                }
+
+                energy.value -= system_energy::task_cost(&intent.task);
+                vintent.add_component_unchecked(id, intent);
+            }
+        },
+    );
+
+    for intent in to_cast.iter() {
+        system_spells::cast(&store, intent);
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use crate::ai::input::InputTargets;
+use crate::ai::intent::{Intent, Task};
+use crate::raws::factions::{FactionTable, Relation};
+use crate::utils::{away_from, Target};
+use crate::world::components::{Actor, ActorType, Energy, PhysicalStats, Position, SpatialKnowledge, Turn, Vision, RNG};
+use crate::world::effects::{EffectQueue, EffectType};
+use crate::world::map::Map;
+use crate::world::systems::system_energy;
+use rltk::{DistanceAlg, Point};
+use shipyard::{AddComponent, AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View, ViewMut};
+
+/// Wolves this close that know about each other run as one pack
+pub const PACK_RANGE: f32 = 12.0;
+
+/// Distance from the prey a wolf waits at until the pack has it surrounded
+pub const SURROUND_RANGE: f32 = 2.9;
+
+/// Wolves that need to be in position before the pack attacks, fewer if the pack is smaller
+pub const SURROUND_COUNT: usize = 3;
+
+/// The pack runs once its health drops below this fraction of its max
+pub const FLEE_HEALTH: f32 = 0.5;
+
+/// Followers stay about this close to the leader
+pub const FOLLOW_RANGE: f32 = 3.0;
+
+/// How far the leader wanders when there's nothing to hunt
+pub const ROAM_RANGE: i32 = 10;
+
+enum Prey {
+    Hunt(EntityId, Point),  // hostile and can be hurt
+    Catch(EntityId, Point), // fish close enough to the shore to snatch
+}
+
+pub fn run_wolf_ai(store: AllStoragesViewMut) {
+    let turn = *store.borrow::<UniqueView<Turn>>().unwrap();
+    let vspace = store.borrow::<View<SpatialKnowledge>>().unwrap();
+
+    store.run(
+        |map: UniqueView<Map>,
+         factions: UniqueView<FactionTable>,
+         mut rng: UniqueViewMut<RNG>,
+         mut effects: UniqueViewMut<EffectQueue>,
+         vactor: View<Actor>,
+         vpos: View<Position>,
+         vstats: View<PhysicalStats>,
+         vvision: View<Vision>,
+         mut vintent: ViewMut<Intent>,
+         mut venergy: ViewMut<Energy>| {
+            // flanking tiles and fish already spoken for this tick
+            let mut claimed: HashSet<usize> = HashSet::new();
+            let mut caught: HashSet<EntityId> = HashSet::new();
+
+            for (id, (actor, pos)) in (&vactor, &vpos).iter().with_id() {
+                if actor.atype != ActorType::Wolf {
+                    continue;
+                }
+
+                let energy = match (&mut venergy).get(id) {
+                    Ok(energy) if system_energy::can_act(energy) => energy,
+                    _ => continue,
+                };
+
+                let me = pos.ps[0];
+
+                // packmates are the wolves this one remembers seeing that are still close by
+                let mut pack = vec![id];
+                if let Ok(space) = vspace.get(id) {
+                    for t in space.get_targets(&store, id, InputTargets::Wolf) {
+                        if let Target::ENTITY(e) = t {
+                            if let Ok(p) = vpos.get(e) {
+                                if DistanceAlg::Pythagoras.distance2d(me, p.ps[0]) <= PACK_RANGE {
+                                    pack.push(e);
+                                }
+                            }
+                        }
+                    }
+                }
+                pack.sort_by_key(|e| e.inner());
+                pack.dedup();
+                let leader = pack[0];
+
+                let (hp, max_hp) = pack.iter().filter_map(|e| vstats.get(*e).ok()).fold((0, 0), |acc, s| (acc.0 + s.hp, acc.1 + s.max_hp));
+                let hurt = max_hp > 0 && (hp as f32) < FLEE_HEALTH * max_hp as f32;
+
+                // the pack shares what any of them can see
+                let prey = pack.iter().find_map(|e| {
+                    let vision = vvision.get(*e).ok()?;
+                    let p = vpos.get(*e).ok()?;
+                    find_prey(&map, &factions, &vactor, &vstats, vision, *e, p.ps[0])
+                });
+
+                let mut intent = Intent {
+                    name: "wait".to_string(),
+                    owner: id,
+                    task: Task::Idle,
+                    target: vec![],
+                    turn,
+                };
+
+                match prey {
+                    Some(Prey::Hunt(_, threat)) if hurt => {
+                        let away = away_from(&map, me, threat);
+                        intent.name = "flee".to_string();
+                        intent.task = Task::MoveTo(InputTargets::None);
+                        intent.target = vec![Target::from(away)];
+                        step_towards(&map, &mut effects, id, me, away);
+                    }
+                    Some(Prey::Hunt(target, tp)) => {
+                        let in_position = pack
+                            .iter()
+                            .filter_map(|e| vpos.get(*e).ok())
+                            .filter(|p| DistanceAlg::Pythagoras.distance2d(p.ps[0], tp) <= SURROUND_RANGE)
+                            .count();
+
+                        if in_position >= usize::min(pack.len(), SURROUND_COUNT) {
+                            intent.name = "hunt".to_string();
+                            intent.task = Task::Attack(InputTargets::Enemy);
+                            intent.target = vec![Target::from(target)];
+
+                            if DistanceAlg::Pythagoras.distance2d(me, tp) < 1.5 {
+                                effects.add_effect(Some(id), EffectType::MoveOrAttack { tile_idx: map.point_idx(tp) });
+                            } else if let Some(spot) = open_tile_near(&map, &claimed, me, tp, 1) {
+                                step_towards(&map, &mut effects, id, me, spot);
+                            }
+                        } else if DistanceAlg::Pythagoras.distance2d(me, tp) > SURROUND_RANGE {
+                            intent.name = "surround".to_string();
+                            intent.task = Task::MoveTo(InputTargets::Enemy);
+                            intent.target = vec![Target::from(target)];
+
+                            if let Some(spot) = open_tile_near(&map, &claimed, me, tp, 2) {
+                                claimed.insert(map.point_idx(spot));
+                                step_towards(&map, &mut effects, id, me, spot);
+                            }
+                        }
+                    }
+                    Some(Prey::Catch(fish, fp)) if !caught.contains(&fish) => {
+                        intent.name = "catch fish".to_string();
+                        intent.task = Task::Fish;
+                        intent.target = vec![Target::from(fish)];
+
+                        if DistanceAlg::Pythagoras.distance2d(me, fp) < 1.5 {
+                            caught.insert(fish);
+                            effects.add_effect(Some(id), EffectType::Delete { entity: fish });
+                        } else if let Some(spot) = open_tile_near(&map, &claimed, me, fp, 1) {
+                            step_towards(&map, &mut effects, id, me, spot);
+                        }
+                    }
+                    _ => {
+                        if id != leader {
+                            let lp = vpos.get(leader).unwrap().ps[0];
+                            if DistanceAlg::Pythagoras.distance2d(me, lp) > FOLLOW_RANGE {
+                                intent.name = "follow leader".to_string();
+                                intent.task = Task::MoveTo(InputTargets::Wolf);
+                                intent.target = vec![Target::from(leader)];
+                                if let Some(spot) = open_tile_near(&map, &claimed, me, lp, 1) {
+                                    step_towards(&map, &mut effects, id, me, spot);
+                                }
+                            }
+                        } else {
+                            // keep heading for the last spot picked until it's reached
+                            let mut dest = match vintent.get(id) {
+                                Ok(old) if old.name == "roam" => match old.target.first() {
+                                    Some(Target::LOCATION(l)) if *l != me => Some(*l),
+                                    _ => None,
+                                },
+                                _ => None,
+                            };
+
+                            if dest.is_none() {
+                                for _ in 0..10 {
+                                    let xy = (me.x + rng.0.range(-ROAM_RANGE, ROAM_RANGE + 1), me.y + rng.0.range(-ROAM_RANGE, ROAM_RANGE + 1));
+                                    if map.in_bounds(xy) && !map.blocked[map.xy_idx(xy)] {
+                                        dest = Some(Point::new(xy.0, xy.1));
+                                        break;
+                                    }
+                                }
+                            }
+
+                            if let Some(dest) = dest {
+                                // no way there, pick somewhere else next turn
+                                if step_towards(&map, &mut effects, id, me, dest) {
+                                    intent.name = "roam".to_string();
+                                    intent.task = Task::MoveTo(InputTargets::None);
+                                    intent.target = vec![Target::from(dest)];
+                                }
+                            }
+                        }
+                    }
+                }
+
+                energy.value -= system_energy::task_cost(&intent.task);
+                vintent.add_component_unchecked(id, intent);
+            }
+        },
+    );
+}
+
+/// Nearest thing in view the pack can eat
+fn find_prey(map: &Map, factions: &FactionTable, vactor: &View<Actor>, vstats: &View<PhysicalStats>, vision: &Vision, id: EntityId, me: Point) -> Option<Prey> {
+    let mut nearest: Option<(Prey, f32)> = None;
+
+    for p in vision.visible_tiles.iter() {
+        let dist = DistanceAlg::Pythagoras.distance2d(me, *p);
+        if nearest.as_ref().map_or(false, |(_, d)| *d <= dist) {
+            continue;
+        }
+
+        for e in map.tile_content[map.point_idx(*p)].iter() {
+            let actor = match vactor.get(*e) {
+                Ok(actor) => actor,
+                Err(_) => continue,
+            };
+
+            if actor.atype == ActorType::Fish && on_shore(map, *p) {
+                nearest = Some((Prey::Catch(*e, *p), dist));
+                break;
+            }
+            if actor.atype != ActorType::Wolf && vstats.get(*e).is_ok() && factions.relation_between(vactor, id, *e) == Relation::Hostile {
+                nearest = Some((Prey::Hunt(*e, *p), dist));
+                break;
+            }
+        }
+    }
+
+    nearest.map(|(prey, _)| prey)
+}
+
+/// Whether there's dry ground next to `p`
+fn on_shore(map: &Map, p: Point) -> bool {
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let xy = (p.x + dx, p.y + dy);
+            if map.in_bounds(xy) && !map.blocks_movement(map.xy_idx(xy)) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Closest free tile to `me` that is `range` tiles from `target`
+fn open_tile_near(map: &Map, claimed: &HashSet<usize>, me: Point, target: Point, range: i32) -> Option<Point> {
+    let mut best: Option<(Point, f32)> = None;
+
+    for dx in -range..=range {
+        for dy in -range..=range {
+            if i32::max(dx.abs(), dy.abs()) != range {
+                continue;
+            }
+
+            let p = Point::new(target.x + dx, target.y + dy);
+            if !map.in_bounds((p.x, p.y)) {
+                continue;
+            }
+
+            let idx = map.point_idx(p);
+            if p != me && (map.blocked[idx] || claimed.contains(&idx)) {
+                continue;
+            }
+
+            let dist = DistanceAlg::Pythagoras.distance2d(me, p);
+            if best.map_or(true, |(_, d)| dist < d) {
+                best = Some((p, dist));
+            }
+        }
+    }
+
+    best.map(|(p, _)| p)
+}
+
+/// Queues one step along the path to `to`, false if there's no way there
+fn step_towards(map: &Map, effects: &mut EffectQueue, id: EntityId, from: Point, to: Point) -> bool {
+    if from == to {
+        return true;
+    }
+
+    if DistanceAlg::Pythagoras.distance2d(from, to) < 1.5 {
+        effects.add_effect(Some(id), EffectType::Move { tile_idx: map.point_idx(to) });
+        return true;
+    }
+
+    let path = map.get_path(from, to);
+    if path.success && path.steps.len() > 1 {
+        effects.add_effect(Some(id), EffectType::Move { tile_idx: path.steps[1] });
+        return true;
+    }
+
+    false
+}
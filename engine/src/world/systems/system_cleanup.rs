@@ -1,9 +1,12 @@
 use crate::world::components::{PhysicalStats, Equipped, Inventory, Name, Player, GameLog};
-use crate::world::effects::{add_effect, EffectType};
+use crate::world::effects::{EffectQueue, EffectType};
+use crate::world::events::{EventStream, GameEvent};
 use shipyard::{Get, IntoIter, IntoWithId, Remove, UniqueViewMut, View, ViewMut};
 
 pub fn run_cleanup_system(
     mut log: UniqueViewMut<GameLog>,
+    mut effects: UniqueViewMut<EffectQueue>,
+    mut events: UniqueViewMut<EventStream>,
     vstats: View<PhysicalStats>,
     vplayer: View<Player>,
     vname: View<Name>,
@@ -23,11 +26,13 @@ pub fn run_cleanup_system(
                         inv.items.clear();
                     }
 
-                    add_effect(None, EffectType::Delete { entity: id });
+                    effects.add_effect(None, EffectType::Delete { entity: id });
 
                     if let Ok(name) = vname.get(id) {
                         log.messages.push(format!("{} is dead", &name.name));
                     }
+
+                    events.push(GameEvent::EntityDied { entity: id, name: vname.get(id).ok().map(|n| n.name.clone()) });
                 }
                 Ok(_p) => {
                     // game over handled in main loop
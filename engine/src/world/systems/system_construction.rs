@@ -0,0 +1,181 @@
+use crate::entity_factory;
+use crate::raws::RawMaster;
+use crate::tiles::TileType;
+use crate::world::components::{ConstructionSite, GameLog, Inventory, Item, ItemType, Needs, PlankHouse, Position};
+use crate::world::effects::{EffectQueue, EffectType};
+use crate::world::map::{Map, XY};
+use rltk::Point;
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View, ViewMut};
+
+/// What the village builds when it runs out of beds
+pub const HOUSE: &str = "Plank House";
+
+/// Tiles of a building put up each turn, as long as there are logs for them
+pub const BUILD_RATE: i32 = 2;
+
+/// The village starts a new house once it's down to this many free beds
+pub const SPARE_BEDS: i32 = 2;
+
+/// Builds with the logs dropped off at each site, finishes the ones that are done and plans new houses
+pub fn run_construction_system(mut store: AllStoragesViewMut) {
+    let mut finished: Vec<(EntityId, String, XY)> = vec![];
+
+    store.run(
+        |mut map: UniqueViewMut<Map>,
+         mut effects: UniqueViewMut<EffectQueue>,
+         vpos: View<Position>,
+         vitem: View<Item>,
+         mut vinv: ViewMut<Inventory>,
+         mut vsite: ViewMut<ConstructionSite>| {
+            for (id, (site, pos, inv)) in (&mut vsite, &vpos, &mut vinv).iter().with_id() {
+                while !site.is_supplied() {
+                    match inv.items.iter().position(|i| vitem.get(*i).map_or(false, |i| i.typ == ItemType::Log)) {
+                        Some(i) => {
+                            effects.add_effect(None, EffectType::Delete { entity: inv.items.remove(i) });
+                            site.logs_delivered += 1;
+                        }
+                        None => break,
+                    }
+                }
+
+                let tiles = building_tiles(pos);
+                let affordable = if site.logs_needed > 0 {
+                    tiles.len() as i32 * site.logs_delivered / site.logs_needed
+                } else {
+                    tiles.len() as i32
+                };
+                let target = i32::max(i32::min(affordable, site.tiles_built + BUILD_RATE), site.tiles_built);
+
+                for (p, tile) in tiles[site.tiles_built as usize..target as usize].iter() {
+                    map.set_tile((p.x, p.y), *tile);
+                }
+                site.tiles_built = target;
+
+                if site.is_supplied() && site.tiles_built >= tiles.len() as i32 {
+                    finished.push((id, site.building.clone(), (pos.ps[0].x, pos.ps[0].y)));
+                }
+            }
+        },
+    );
+
+    for (id, building, xy) in finished {
+        // logs nobody needed go with the site
+        let leftovers = store.borrow::<View<Inventory>>().unwrap().get(id).map_or(vec![], |inv| inv.items.clone());
+        for e in leftovers {
+            store.delete_entity(e);
+        }
+        store.delete_entity(id);
+
+        entity_factory::spawn_named(&mut store, &building, xy);
+        store.borrow::<UniqueViewMut<GameLog>>().unwrap().messages.push(format!("The villagers finished a {}", building));
+    }
+
+    plan_housing(&mut store);
+}
+
+/// Logs still wanted by every construction site, counting the ones already dropped off
+pub fn logs_wanted(vsite: &View<ConstructionSite>, vinv: &View<Inventory>) -> i32 {
+    let mut wanted = 0;
+    for (id, site) in vsite.iter().with_id() {
+        let waiting = vinv.get(id).map_or(0, |inv| inv.items.len() as i32);
+        wanted += i32::max(site.logs_needed - site.logs_delivered - waiting, 0);
+    }
+    wanted
+}
+
+/// Whether a building of `size` fits at `xy` with a free tile all around it
+pub fn site_is_clear(map: &Map, xy: XY, size: [i32; 2]) -> bool {
+    for x in xy.0 - 1..=xy.0 + size[0] {
+        for y in xy.1 - 1..=xy.1 + size[1] {
+            if !map.in_bounds((x, y)) {
+                return false;
+            }
+
+            let idx = map.xy_idx((x, y));
+            if map.blocks_movement(idx) || map.blocked[idx] || !map.tile_content[idx].is_empty() {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Tiles a building is made of in the order they go up. Floor, then walls, then a door in the bottom wall
+fn building_tiles(pos: &Position) -> Vec<(Point, TileType)> {
+    let x1 = pos.ps.iter().map(|p| p.x).min().unwrap_or(0);
+    let x2 = pos.ps.iter().map(|p| p.x).max().unwrap_or(0);
+    let y1 = pos.ps.iter().map(|p| p.y).min().unwrap_or(0);
+    let y2 = pos.ps.iter().map(|p| p.y).max().unwrap_or(0);
+    let door = Point::new((x1 + x2) / 2, y2);
+
+    let mut tiles: Vec<(Point, TileType)> = pos
+        .ps
+        .iter()
+        .map(|p| {
+            let tile = if *p == door {
+                TileType::WoodDoor
+            } else if p.x == x1 || p.x == x2 || p.y == y1 || p.y == y2 {
+                TileType::WoodWall
+            } else {
+                TileType::WoodFloor
+            };
+            (*p, tile)
+        })
+        .collect();
+
+    tiles.sort_by_key(|(_, tile)| match tile {
+        TileType::WoodFloor => 0,
+        TileType::WoodWall => 1,
+        _ => 2,
+    });
+
+    tiles
+}
+
+/// Marks out a new house next to an existing one when the village is running out of beds
+fn plan_housing(store: &mut AllStoragesViewMut) {
+    let spot = {
+        let map = store.borrow::<UniqueView<Map>>().unwrap();
+        let raws = store.borrow::<UniqueView<RawMaster>>().unwrap();
+        let vsite = store.borrow::<View<ConstructionSite>>().unwrap();
+        let vhouse = store.borrow::<View<PlankHouse>>().unwrap();
+        let vneeds = store.borrow::<View<Needs>>().unwrap();
+        let vpos = store.borrow::<View<Position>>().unwrap();
+
+        // one at a time
+        if vsite.iter().count() > 0 {
+            return;
+        }
+
+        let beds: i32 = vhouse.iter().map(|h| h.housing_cap).sum();
+        if beds - vneeds.iter().count() as i32 > SPARE_BEDS {
+            return;
+        }
+
+        let size = match raws.get(HOUSE).and_then(|r| r.size) {
+            Some(size) => size,
+            None => return,
+        };
+
+        let mut spot = None;
+        'search: for (_, pos) in (&vhouse, &vpos).iter() {
+            let anchor = pos.ps[0];
+            for (dx, dy) in [(0, -size[1] - 2), (0, size[1] + 2), (size[0] + 2, 0), (-size[0] - 2, 0)] {
+                let xy = (anchor.x + dx, anchor.y + dy);
+                if site_is_clear(&map, xy, size) {
+                    spot = Some(xy);
+                    break 'search;
+                }
+            }
+        }
+
+        spot
+    };
+
+    if let Some(xy) = spot {
+        if entity_factory::construction_site(store, xy, HOUSE).is_some() {
+            store.borrow::<UniqueViewMut<GameLog>>().unwrap().messages.push(format!("The villagers started a new {}", HOUSE));
+        }
+    }
+}
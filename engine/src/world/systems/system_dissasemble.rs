@@ -59,7 +59,7 @@ pub fn run_dissasemble_system(mut all_storages: AllStoragesViewMut) {
     }
 
     for (x, y) in to_spawn_log {
-        entity_factory::log(&mut all_storages, (x, y));
+        entity_factory::spawn_named(&mut all_storages, "Log", (x, y));
     }
 
     for e in to_delete {
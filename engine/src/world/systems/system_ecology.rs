@@ -0,0 +1,129 @@
+use crate::entity_factory;
+use crate::tiles::TileType;
+use crate::world::components::{Position, Sapling, Tree, RNG};
+use crate::world::map::{Map, XY};
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueViewMut, View, ViewMut};
+
+/// Growth bare dirt needs in `Map.vegetation` to turn back into grass
+pub const GRASS_REGROW: i32 = 20;
+
+/// Extra growth per turn for anything this close to water
+pub const WATER_RANGE: i32 = 4;
+pub const WATER_BONUS: i32 = 2;
+
+/// Each tree has a 1 in this chance each turn to drop a sapling nearby, twice as likely near water
+pub const SAPLING_CHANCE: i32 = 200;
+pub const SPREAD_RANGE: i32 = 2;
+
+/// Trees and saplings around a tile past which nothing new takes root
+pub const MAX_CROWDING: usize = 3;
+
+/// Age a sapling needs to become a tree
+pub const SAPLING_MATURE: i32 = 50;
+
+/// Regrows grass on bare dirt, spreads saplings around trees and grows saplings into trees
+pub fn run_ecology_system(mut store: AllStoragesViewMut) {
+    let mut to_plant: Vec<XY> = vec![];
+    let mut to_mature: Vec<(EntityId, XY)> = vec![];
+    let mut to_wither: Vec<EntityId> = vec![];
+
+    store.run(
+        |mut map: UniqueViewMut<Map>, mut rng: UniqueViewMut<RNG>, vtree: View<Tree>, vpos: View<Position>, mut vsapling: ViewMut<Sapling>| {
+            // bare ground grows back faster next to grass and water
+            for idx in 0..map.len() {
+                if map.tiles[idx] != TileType::Dirt || map.fire_turns[idx] > 0 {
+                    continue;
+                }
+
+                let growth = count_neighbors(&map, idx, |i| map.tiles[i] == TileType::Grass) as i32
+                    + if near_water(&map, idx) { WATER_BONUS } else { 0 };
+                map.vegetation[idx] += growth;
+
+                if map.vegetation[idx] >= GRASS_REGROW {
+                    map.tiles[idx] = TileType::Grass;
+                    map.vegetation[idx] = 0;
+                }
+            }
+
+            for (_, pos) in (&vtree, &vpos).iter() {
+                let idx = map.point_idx(pos.ps[0]);
+                let chance = if near_water(&map, idx) { SAPLING_CHANCE / 2 } else { SAPLING_CHANCE };
+                if rng.0.roll_dice(1, chance) != 1 {
+                    continue;
+                }
+
+                let xy = (
+                    pos.ps[0].x + rng.0.range(-SPREAD_RANGE, SPREAD_RANGE + 1),
+                    pos.ps[0].y + rng.0.range(-SPREAD_RANGE, SPREAD_RANGE + 1),
+                );
+                if !map.in_bounds(xy) || to_plant.contains(&xy) {
+                    continue;
+                }
+
+                let target = map.xy_idx(xy);
+                let crowding = count_neighbors(&map, target, |i| {
+                    map.tile_content[i].iter().any(|e| vtree.get(*e).is_ok() || (&vsapling).get(*e).is_ok())
+                });
+                if map.tiles[target] == TileType::Grass && map.tile_content[target].is_empty() && crowding < MAX_CROWDING {
+                    to_plant.push(xy);
+                }
+            }
+
+            for (id, (sapling, pos)) in (&mut vsapling, &vpos).iter().with_id() {
+                let idx = map.point_idx(pos.ps[0]);
+                if map.tiles[idx] != TileType::Grass {
+                    to_wither.push(id);
+                    continue;
+                }
+
+                sapling.age += if near_water(&map, idx) { 2 } else { 1 };
+                if sapling.age >= SAPLING_MATURE {
+                    to_mature.push((id, (pos.ps[0].x, pos.ps[0].y)));
+                }
+            }
+        },
+    );
+
+    for xy in to_plant {
+        entity_factory::spawn_named(&mut store, "Sapling", xy);
+    }
+
+    for (id, xy) in to_mature {
+        store.delete_entity(id);
+        entity_factory::spawn_named(&mut store, "Tree", xy);
+    }
+
+    for id in to_wither {
+        store.delete_entity(id);
+    }
+}
+
+/// How many of the 8 tiles around `idx` pass `f`
+fn count_neighbors(map: &Map, idx: usize, f: impl Fn(usize) -> bool) -> usize {
+    let (x, y) = map.idx_xy(idx);
+    let mut count = 0;
+
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            if (dx, dy) != (0, 0) && map.in_bounds((x + dx, y + dy)) && f(map.xy_idx((x + dx, y + dy))) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+fn near_water(map: &Map, idx: usize) -> bool {
+    let (x, y) = map.idx_xy(idx);
+
+    for dx in -WATER_RANGE..=WATER_RANGE {
+        for dy in -WATER_RANGE..=WATER_RANGE {
+            if map.in_bounds((x + dx, y + dy)) && map.tiles[map.xy_idx((x + dx, y + dy))] == TileType::Water {
+                return true;
+            }
+        }
+    }
+
+    false
+}
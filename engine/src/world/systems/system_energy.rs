@@ -0,0 +1,51 @@
+use crate::ai::intent::Task;
+use crate::world::components::{Energy, Locomotive, StatusEffects, StatusKind};
+use shipyard::{Get, IntoIter, IntoWithId, View, ViewMut};
+
+/// Energy needed before an entity can act. Most actions cost this much
+pub const ACTION_COST: i32 = 100;
+
+/// Picking things up and putting them down
+pub const QUICK_ACTION_COST: i32 = 50;
+
+/// Speed of entities without locomotion. At this speed an entity acts once per turn
+pub const NORMAL_SPEED: i32 = 25;
+
+pub const TICKS_PER_TURN: i32 = ACTION_COST / NORMAL_SPEED;
+
+pub fn run_energy_system(vloco: View<Locomotive>, vstatus: View<StatusEffects>, mut venergy: ViewMut<Energy>) {
+    for (id, energy) in (&mut venergy).iter().with_id() {
+        // entities that can already act don't bank any more
+        if energy.value >= ACTION_COST {
+            continue;
+        }
+
+        let mut speed = match vloco.get(id) {
+            Ok(loco) => loco.speed,
+            Err(_) => NORMAL_SPEED,
+        };
+
+        if let Ok(statuses) = vstatus.get(id) {
+            if statuses.has(StatusKind::Hasted) {
+                speed *= 2;
+            }
+            if statuses.has(StatusKind::Slowed) || statuses.has(StatusKind::Sick) {
+                speed /= 2;
+            }
+        }
+
+        energy.value += speed;
+    }
+}
+
+pub fn can_act(energy: &Energy) -> bool {
+    energy.value >= ACTION_COST
+}
+
+/// Energy an AI spends on a task
+pub fn task_cost(task: &Task) -> i32 {
+    match task {
+        Task::PickUpItem(_) | Task::DropItem | Task::EquipItem | Task::UnequipItem | Task::DepositItemToInventory(..) | Task::TakeFromStockpile(_) => QUICK_ACTION_COST,
+        _ => ACTION_COST,
+    }
+}
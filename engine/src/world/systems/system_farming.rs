@@ -0,0 +1,82 @@
+use crate::entity_factory;
+use crate::tiles::{TileType, CROP_RIPE};
+use crate::utils::get_neighbors;
+use crate::world::components::Granary;
+use crate::world::effects::{EffectQueue, EffectType};
+use crate::world::map::Map;
+use shipyard::{AllStorages, AllStoragesViewMut, EntityId, Get, UniqueViewMut, View};
+
+/// Growth a sown tile gains each turn
+pub const CROP_GROWTH: i32 = 1;
+
+/// Grows every wheat tile towards `CROP_RIPE`
+pub fn run_farming_system(mut map: UniqueViewMut<Map>) {
+    for idx in 0..map.len() {
+        if map.tiles[idx] == TileType::Wheat && map.vegetation[idx] < CROP_RIPE {
+            map.vegetation[idx] = i32::min(map.vegetation[idx] + CROP_GROWTH, CROP_RIPE);
+        }
+    }
+}
+
+pub fn can_till(map: &Map, idx: usize) -> bool {
+    matches!(map.tiles[idx], TileType::Grass | TileType::Dirt | TileType::Sand)
+}
+
+/// Turns open ground into farmland ready to sow
+pub fn till(map: &mut Map, idx: usize) -> bool {
+    if !can_till(map, idx) {
+        return false;
+    }
+
+    map.tiles[idx] = TileType::Farmland;
+    map.vegetation[idx] = 0;
+    true
+}
+
+/// Fields grow outwards from the granary and from each other
+pub fn borders_field(store: &AllStorages, map: &Map, idx: usize) -> bool {
+    let vgranary = store.borrow::<View<Granary>>().unwrap();
+
+    get_neighbors(map.idx_point(idx)).iter().any(|p| {
+        if !map.in_bounds((p.x, p.y)) {
+            return false;
+        }
+
+        let n = map.point_idx(*p);
+        map.tiles[n] == TileType::Farmland || map.tiles[n] == TileType::Wheat || map.tile_content[n].iter().any(|e| vgranary.get(*e).is_ok())
+    })
+}
+
+/// Plants wheat on farmland
+pub fn sow(map: &mut Map, idx: usize) -> bool {
+    if map.tiles[idx] != TileType::Farmland {
+        return false;
+    }
+
+    map.tiles[idx] = TileType::Wheat;
+    map.vegetation[idx] = 0;
+    true
+}
+
+pub fn is_ripe(map: &Map, idx: usize) -> bool {
+    map.tiles[idx] == TileType::Wheat && map.vegetation[idx] >= CROP_RIPE
+}
+
+/// Cuts ripe wheat back to farmland and hands the harvester the grain
+pub fn harvest(store: &mut AllStoragesViewMut, harvester: EntityId, idx: usize) -> Option<EntityId> {
+    let xy = {
+        let mut map = store.borrow::<UniqueViewMut<Map>>().unwrap();
+        if !is_ripe(&map, idx) {
+            return None;
+        }
+
+        map.tiles[idx] = TileType::Farmland;
+        map.vegetation[idx] = 0;
+        map.idx_xy(idx)
+    };
+
+    let grain = entity_factory::spawn_named(store, "Grain", xy)?;
+    store.borrow::<UniqueViewMut<EffectQueue>>().unwrap().add_effect(Some(harvester), EffectType::PickUp { entity: grain });
+
+    Some(grain)
+}
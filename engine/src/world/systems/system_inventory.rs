@@ -1,12 +1,12 @@
 use shipyard::{IntoIter, IntoWithId, Remove, View, ViewMut, UniqueViewMut, EntityId, Get};
 
 use crate::world::components::{Inventory, WantsToPickupItem, GameLog, Player, WantsToUnequipItem, Equipped, Name};
-use crate::world::effects::{add_effect, EffectType};
+use crate::world::effects::{EffectQueue, EffectType};
 use crate::world::components::WantsToDropItem;
 
-pub fn run_inventory_system(vinv: View<Inventory>, vwants: View<WantsToPickupItem>) {
+pub fn run_inventory_system(mut effects: UniqueViewMut<EffectQueue>, vinv: View<Inventory>, vwants: View<WantsToPickupItem>) {
     for (id, (_, wants_pickup)) in (&vinv, &vwants).iter().with_id() {
-        add_effect(
+        effects.add_effect(
             Some(id),
             EffectType::PickUp {
                 entity: wants_pickup.item,
@@ -48,6 +48,7 @@ pub fn run_unequip_item_system(
 
 pub fn run_drop_item_system(
     mut log: UniqueViewMut<GameLog>,
+    mut effects: UniqueViewMut<EffectQueue>,
     vplayer: View<Player>,
     mut vwants: ViewMut<WantsToDropItem>,
     vname: View<Name>,
@@ -66,6 +67,6 @@ pub fn run_drop_item_system(
 
     for (id, item) in to_drop.iter() {
         vwants.remove(*id);
-        add_effect(Some(*id), EffectType::Drop { entity: *item });
+        effects.add_effect(Some(*id), EffectType::Drop { entity: *item });
     }
 }
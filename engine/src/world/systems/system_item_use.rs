@@ -1,10 +1,9 @@
 use crate::ui::colors::{COLOR_UI_3, COLOR_BG};
 use crate::world::components::{
-    AreaOfEffect, PhysicalStats, Confusion, Consumable, DealsDamage, Equippable, Equipped, Inventory,
-    Name, ProvidesHealing, WantsToUseItem, GameLog, PlayerID, CausesFire, Equipment,
+    AreaOfEffect, PhysicalStats, InflictsStatus, Consumable, DealsDamage, Equippable, Equipped, Inventory,
+    Name, ProvidesHealing, WantsToUseItem, GameLog, PlayerID, CausesFire, Equipment, Mana, Spell,
 };
-use crate::world::effects::add_effect;
-use crate::world::effects::{EffectType, Targets};
+use crate::world::effects::{EffectQueue, EffectType, Targets};
 use crate::world::map::Map;
 use crate::{world::components::Position, world::systems::system_particle::ParticleBuilder};
 use shipyard::{
@@ -19,6 +18,7 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
     let player_id = store.borrow::<UniqueView<PlayerID>>().ok().unwrap(); //res.get::<EntityId>().unwrap();
     let map = store.borrow::<UniqueView<Map>>().unwrap(); //res.get::<Map>().unwrap();
     let mut p_builder = store.borrow::<UniqueViewMut<ParticleBuilder>>().unwrap(); //res.get_mut::<ParticleBuilder>().unwrap();
+    let mut effects = store.borrow::<UniqueViewMut<EffectQueue>>().unwrap();
     let mut to_remove: Vec<(EntityId, EntityId)> = Vec::new();
     let mut to_remove_wants_use: Vec<EntityId> = Vec::new();
     let mut to_unequip: Vec<(EntityId, Name, EntityId)> = Vec::new();
@@ -32,16 +32,41 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
     let vname = store.borrow::<View<Name>>().unwrap();
     let vpos = store.borrow::<View<Position>>().unwrap();
     let vdealsdamage = store.borrow::<View<DealsDamage>>().unwrap();
-    let vconfusion = store.borrow::<View<Confusion>>().unwrap();
+    let vinflicts = store.borrow::<View<InflictsStatus>>().unwrap();
     let vconsumable = store.borrow::<View<Consumable>>().unwrap();
     let vequippable = store.borrow::<View<Equippable>>().unwrap();
     let mut vequipped = store.borrow::<ViewMut<Equipped>>().unwrap();
     let mut vinv = store.borrow::<ViewMut<Inventory>>().unwrap();
     let mut vequipment = store.borrow::<ViewMut<Equipment>>().unwrap();
+    let mut vmana = store.borrow::<ViewMut<Mana>>().unwrap();
+    let mut vspell = store.borrow::<ViewMut<Spell>>().unwrap();
 
     for (id, use_item) in vwants.iter().with_id() {
         let mut used_item = false;
 
+        // spells are paid for in mana up front, a caster that can't pay fizzles
+        if let Ok(spell) = (&mut vspell).get(use_item.item) {
+            let paid = match (&mut vmana).get(id) {
+                Ok(mana) if spell.castable(&mana) => {
+                    mana.current -= spell.mana_cost;
+                    true
+                }
+                _ => false,
+            };
+
+            if !paid {
+                if id == player_id.0 {
+                    let name = vname.get(use_item.item).unwrap();
+                    let why = if spell.cooldown_left > 0 { "isn't ready yet" } else { "needs more mana" };
+                    log.messages.push(format!("{} {}", name.name, why));
+                }
+                to_remove_wants_use.push(id);
+                continue;
+            }
+
+            spell.cooldown_left = spell.cooldown;
+        }
+
         // Find all targets
         let mut targets: Vec<EntityId> = Vec::new();
         let mut target_tiles: Vec<usize> = Vec::new();
@@ -93,7 +118,7 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
 
         // Apply fire if it applies fire
         if let Ok(fire) = vcausesfire.get(use_item.item) {
-            add_effect(
+            effects.add_effect(
                 Some(id),
                 EffectType::Fire {
                     turns: fire.turns,
@@ -110,7 +135,7 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
                 match stats {
                     Err(_e) => {}
                     Ok(_stats) => {
-                        add_effect(
+                        effects.add_effect(
                             Some(id),
                             EffectType::Heal {
                                 amount: healer.heal,
@@ -148,10 +173,17 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
         // Apply damage to target if it deals damage
         if let Ok(dd) = vdealsdamage.get(use_item.item) {
             for target in targets.iter() {
-                add_effect(
+                // anyone dug in takes less from shots and blasts
+                let cover = match vpos.get(*target) {
+                    Ok(pos) if use_item.target.is_some() => map.tiles[map.point_idx(pos.ps[0])].cover(),
+                    _ => 0,
+                };
+                let amount = dd.damage * (100 - cover) / 100;
+
+                effects.add_effect(
                     Some(id),
                     EffectType::Damage {
-                        amount: dd.damage,
+                        amount,
                         target: Targets::Single { target: *target },
                     },
                 );
@@ -160,7 +192,7 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
                     let item_name = vname.get(use_item.item).unwrap();
                     log.messages.push(format!(
                         "You use {} on {}, dealing {} hp",
-                        item_name.name, monster_name.name, dd.damage
+                        item_name.name, monster_name.name, amount
                     ));
                 }
                 used_item = true;
@@ -182,23 +214,30 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
             }
         }
 
-        // Apply confusion
-        if let Ok(confusion) = vconfusion.get(use_item.item){
+        // Apply status effects
+        if let Ok(inflicts) = vinflicts.get(use_item.item) {
             for target in targets.iter() {
-                add_effect(
+                effects.add_effect(
                     Some(id),
-                    EffectType::Confusion {
-                        turns: confusion.turns,
+                    EffectType::Status {
+                        status: inflicts.status,
                         target: Targets::Single { target: *target },
                     },
                 );
                 if id == player_id.0 {
-                    let monster_name = vname.get(*target).unwrap();
                     let item_name = vname.get(use_item.item).unwrap();
-                    log.messages.push(format!(
-                        "You use {} on {}, confusing them",
-                        item_name.name, monster_name.name
-                    ));
+                    if *target == id {
+                        log.messages.push(format!(
+                            "You use {}, you are now {}",
+                            item_name.name, inflicts.status.kind.name()
+                        ));
+                    } else {
+                        let monster_name = vname.get(*target).unwrap();
+                        log.messages.push(format!(
+                            "You use {} on {}, they are now {}",
+                            item_name.name, monster_name.name, inflicts.status.kind.name()
+                        ));
+                    }
                 }
                 used_item = true;
 
@@ -250,7 +289,7 @@ pub fn run_item_use_system(store: AllStoragesViewMut) {
             }
         }
 
-        add_effect(None, EffectType::Delete { entity: item });
+        effects.add_effect(None, EffectType::Delete { entity: item });
     }
 
     for id in to_remove_wants_use {
@@ -1,9 +1,8 @@
 use crate::ui::colors::{COLOR_BG, COLOR_UI_4};
 use crate::world::components::GameLog;
-use crate::world::effects::{EffectType, Targets};
+use crate::world::effects::{EffectQueue, EffectType, Targets};
 use crate::{
     world::components::{PhysicalStats, Equipped, MeleeDefenseBonus, MeleePowerBonus, Name, Position, WantsToAttack},
-    world::effects::add_effect,
     world::systems::system_particle::ParticleBuilder,
 };
 use shipyard::{AllStoragesView, EntityId, Get, IntoIter, IntoWithId, Remove, UniqueViewMut, View, ViewMut};
@@ -13,6 +12,7 @@ use super::system_particle::PARTICLE_TIME;
 pub fn run_melee_combat_system(store: AllStoragesView) {
     let mut log = store.borrow::<UniqueViewMut<GameLog>>().unwrap();
     let mut particle_builder = store.borrow::<UniqueViewMut<ParticleBuilder>>().unwrap();
+    let mut effects = store.borrow::<UniqueViewMut<EffectQueue>>().unwrap();
 
     let mut vwants = store.borrow::<ViewMut<WantsToAttack>>().unwrap();
     let vname = store.borrow::<View<Name>>().unwrap();
@@ -54,7 +54,7 @@ pub fn run_melee_combat_system(store: AllStoragesView) {
                     } else {
                         log.messages
                             .push(format!("{} hits {} for {} hp", &name.name, &target_name.name, damage));
-                        add_effect(
+                        effects.add_effect(
                             Some(id),
                             EffectType::Damage {
                                 amount: damage,
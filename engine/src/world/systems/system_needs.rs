@@ -0,0 +1,124 @@
+use crate::world::components::{Inventory, Item, ItemType, Needs, PlankHouse, StatusEffect, StatusEffects, StatusKind, MAX_NEED};
+use crate::world::effects::{EffectQueue, EffectType, Targets};
+use crate::world::systems::system_status::has_status;
+use crate::world::systems::system_stockpile;
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueViewMut, View, ViewMut};
+
+// how much each need builds up per turn
+pub const HUNGER_PER_TURN: i32 = 1;
+pub const FATIGUE_PER_TURN: i32 = 1;
+pub const SHELTER_PER_TURN: i32 = 1;
+
+/// Hunger a single fish takes away
+pub const FISH_NOURISHMENT: i32 = 50;
+
+/// Hunger a single grain takes away
+pub const GRAIN_NOURISHMENT: i32 = 30;
+
+/// Fatigue taken away by each turn of sleep
+pub const SLEEP_RECOVERY: i32 = 10;
+
+/// Damage per turn while starving
+pub const STARVATION_DAMAGE: i32 = 1;
+
+/// How long exhaustion or exposure makes a villager sick for
+pub const SICK_TURNS: i32 = 10;
+
+/// Builds up needs, punishes the ones left at `MAX_NEED` and gives homeless villagers a bed
+pub fn run_needs_system(
+    mut effects: UniqueViewMut<EffectQueue>,
+    vstatus: View<StatusEffects>,
+    mut vneeds: ViewMut<Needs>,
+    mut vhouse: ViewMut<PlankHouse>,
+) {
+    for (id, needs) in (&mut vneeds).iter().with_id() {
+        needs.hunger = i32::min(needs.hunger + HUNGER_PER_TURN, MAX_NEED);
+        needs.fatigue = i32::min(needs.fatigue + FATIGUE_PER_TURN, MAX_NEED);
+        needs.shelter = i32::min(needs.shelter + SHELTER_PER_TURN, MAX_NEED);
+
+        if needs.hunger >= MAX_NEED {
+            effects.add_effect(
+                None,
+                EffectType::Damage {
+                    amount: STARVATION_DAMAGE,
+                    target: Targets::Single { target: id },
+                },
+            );
+        }
+
+        if (needs.fatigue >= MAX_NEED || needs.shelter >= MAX_NEED) && !has_status(&vstatus, id, StatusKind::Sick) {
+            effects.add_effect(
+                None,
+                EffectType::Status {
+                    status: StatusEffect { kind: StatusKind::Sick, turns: SICK_TURNS, magnitude: 1 },
+                    target: Targets::Single { target: id },
+                },
+            );
+        }
+    }
+
+    // free the beds of anyone who's gone, then fill them with whoever is homeless
+    let mut housed: Vec<EntityId> = vec![];
+    for house in (&mut vhouse).iter() {
+        house.villagers.retain(|v| (&vneeds).get(*v).is_ok());
+        housed.extend(house.villagers.iter());
+    }
+
+    let mut homeless = (&vneeds).iter().with_id().map(|(id, _)| id).filter(|id| !housed.contains(id));
+    for house in (&mut vhouse).iter() {
+        while (house.villagers.len() as i32) < house.housing_cap {
+            match homeless.next() {
+                Some(id) => house.villagers.push(id),
+                None => return,
+            }
+        }
+    }
+}
+
+/// Eats food the eater is carrying, or some from the village stockpile
+pub fn eat(store: &AllStoragesViewMut, eater: EntityId) {
+    let carried = {
+        let mut vinv = store.borrow::<ViewMut<Inventory>>().unwrap();
+        let vitem = store.borrow::<View<Item>>().unwrap();
+
+        let food = (&mut vinv).get(eater).ok().and_then(|inv| {
+            let i = inv.items.iter().position(|item| vitem.get(*item).map_or(false, |item| nourishment(item.typ) > 0))?;
+            let typ = vitem.get(inv.items[i]).unwrap().typ;
+            Some((inv.items.remove(i), typ))
+        });
+        food
+    };
+
+    let ate = match carried {
+        Some((food, typ)) => {
+            store.borrow::<UniqueViewMut<EffectQueue>>().unwrap().add_effect(Some(eater), EffectType::Delete { entity: food });
+            Some(typ)
+        }
+        None => [ItemType::Fish, ItemType::Grain].iter().copied().find(|typ| system_stockpile::withdraw(store, *typ, 1)),
+    };
+
+    if let Some(typ) = ate {
+        if let Ok(needs) = (&mut store.borrow::<ViewMut<Needs>>().unwrap()).get(eater) {
+            needs.hunger = i32::max(needs.hunger - nourishment(typ), 0);
+        }
+    }
+}
+
+/// Hunger a piece of food takes away, 0 if it isn't food
+pub fn nourishment(typ: ItemType) -> i32 {
+    match typ {
+        ItemType::Fish => FISH_NOURISHMENT,
+        ItemType::Grain => GRAIN_NOURISHMENT,
+        _ => 0,
+    }
+}
+
+/// A turn of sleep in a bed
+pub fn sleep(store: &AllStoragesViewMut, sleeper: EntityId) {
+    let mut vneeds = store.borrow::<ViewMut<Needs>>().unwrap();
+
+    if let Ok(needs) = (&mut vneeds).get(sleeper) {
+        needs.fatigue = i32::max(needs.fatigue - SLEEP_RECOVERY, 0);
+        needs.shelter = 0;
+    }
+}
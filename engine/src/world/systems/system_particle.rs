@@ -5,7 +5,7 @@ use shipyard::{
 
 use crate::{
     world::components::{Lifetime, Particle, Position, Renderable, Velocity, FrameTime},
-    world::effects::{add_effect, EffectType},
+    world::effects::{EffectQueue, EffectType},
     RenderOrder, ui::colors::Color,
 };
 
@@ -96,6 +96,7 @@ pub fn spawn_particles(mut store: AllStoragesViewMut) {
 
 pub fn update_particles(
     frametime: UniqueView<FrameTime>,
+    effects: UniqueViewMut<EffectQueue>,
     mut vpart: ViewMut<Particle>,
     mut vlifetime: ViewMut<Lifetime>,
     vvel: View<Velocity>,
@@ -117,10 +118,10 @@ pub fn update_particles(
         }
     }
 
-    remove_dead_particles(vlifetime);
+    remove_dead_particles(effects, vlifetime);
 }
 
-pub fn remove_dead_particles(vlifetime: ViewMut<Lifetime>) {
+pub fn remove_dead_particles(mut effects: UniqueViewMut<EffectQueue>, vlifetime: ViewMut<Lifetime>) {
     let mut particles_to_remove: Vec<EntityId> = Vec::new();
     for (id, lifetime) in vlifetime.iter().with_id() {
         if lifetime.ms <= 0.0 {
@@ -129,6 +130,6 @@ pub fn remove_dead_particles(vlifetime: ViewMut<Lifetime>) {
     }
 
     for id in particles_to_remove {
-        add_effect(None, EffectType::Delete { entity: id });
+        effects.add_effect(None, EffectType::Delete { entity: id });
     }
 }
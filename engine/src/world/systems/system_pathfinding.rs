@@ -1,9 +1,19 @@
-use crate::world::components::{DijkstraMapToMe, Position};
+use crate::world::components::{DijkstraMapToMe, Position, Tick};
 use crate::world::map::Map;
-use shipyard::{IntoIter, IntoWithId, UniqueView, View, ViewMut};
+use crate::world::systems::system_energy::TICKS_PER_TURN;
+use shipyard::{IntoIter, IntoWithId, UniqueView, UniqueViewMut, View, ViewMut};
+
+/// Rebuilds every dijkstra map once a turn, or straight away when the terrain changed
+pub fn run_pathfinding_system(mut map: UniqueViewMut<Map>, tick: UniqueView<Tick>, vpos: View<Position>, mut vmaps: ViewMut<DijkstraMapToMe>) {
+    let rebuild_all = tick.0 % TICKS_PER_TURN == 0 || map.paths_dirty;
+    map.paths_dirty = false;
 
-pub fn run_pathfinding_system(map: UniqueView<Map>, vpos: View<Position>, mut vmaps: ViewMut<DijkstraMapToMe>) {
     for (_, (pos, dijkstra)) in (&vpos, &mut vmaps).iter().with_id() {
+        // maps from the factory or a save start out empty and can't wait for the next turn
+        if !rebuild_all && dijkstra.map.map.len() == map.len() {
+            continue;
+        }
+
         let mut starts: Vec<usize> = vec![];
         for pos in pos.ps.iter() {
             starts.push(map.point_idx(*pos));
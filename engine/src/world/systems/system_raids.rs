@@ -0,0 +1,184 @@
+use crate::ai::input::InputTargets;
+use crate::ai::intent::{Intent, Task};
+use crate::entity_factory;
+use crate::utils::Target;
+use crate::world::components::{ChiefHouse, GameLog, PlankHouse, Position, Raider, StatusEffects, StatusKind, Stockpile, Turn, RNG};
+use crate::world::map::{Map, XY};
+use crate::world::systems::system_status::has_status;
+use rltk::{DistanceAlg, Point, RandomNumberGenerator};
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View};
+
+/// Turns between raids
+pub const RAID_INTERVAL: i32 = 300;
+
+/// Raiders even a poor village gets
+pub const RAID_BASE: i32 = 2;
+
+/// Each this much wealth brings one more raider
+pub const WEALTH_PER_RAIDER: i32 = 25;
+pub const MAX_RAIDERS: i32 = 12;
+
+/// What a house counts for on top of the stockpile
+pub const HOUSE_WEALTH: i32 = 10;
+
+pub fn village_wealth(stockpile: &Stockpile, houses: usize) -> i32 {
+    stockpile.counts.values().sum::<i32>() + HOUSE_WEALTH * houses as i32
+}
+
+pub fn raid_size(wealth: i32) -> i32 {
+    i32::min(RAID_BASE + wealth / WEALTH_PER_RAIDER, MAX_RAIDERS)
+}
+
+/// Sends a wave of orcs at the village every `RAID_INTERVAL` turns
+pub fn run_raid_system(mut store: AllStoragesViewMut) {
+    let turn = store.borrow::<UniqueView<Turn>>().unwrap().0;
+    if turn > 0 && turn % RAID_INTERVAL == 0 {
+        launch_raid(&mut store);
+    }
+}
+
+/// Spawns a raid at a random map edge, sized by how rich the village is. Nothing happens without a chief house to raid
+pub fn launch_raid(store: &mut AllStoragesViewMut) -> Vec<EntityId> {
+    let spots = store.run(
+        |map: UniqueView<Map>,
+         mut rng: UniqueViewMut<RNG>,
+         stockpile: UniqueView<Stockpile>,
+         vpos: View<Position>,
+         vchief: View<ChiefHouse>,
+         vhouse: View<PlankHouse>| {
+            let chief = match (&vchief, &vpos).iter().next() {
+                Some((_, pos)) => pos,
+                None => return vec![],
+            };
+
+            let size = raid_size(village_wealth(&stockpile, vhouse.iter().count())) as usize;
+            let start = match edge_tile(&map, &mut rng.0, &chief.ps) {
+                Some(start) => start,
+                None => return vec![],
+            };
+
+            let mut spots: Vec<XY> = vec![];
+            for dx in -3..=3 {
+                for dy in -3..=3 {
+                    let xy = (start.0 + dx, start.1 + dy);
+                    if spots.len() < size && is_open(&map, xy) {
+                        spots.push(xy);
+                    }
+                }
+            }
+
+            spots
+        },
+    );
+
+    if spots.is_empty() {
+        return vec![];
+    }
+
+    store.borrow::<UniqueViewMut<GameLog>>().unwrap().messages.push(format!("{} orcs are raiding the village!", spots.len()));
+
+    let mut raiders = vec![];
+    for xy in spots {
+        if let Some(e) = entity_factory::spawn_named(store, "Orc", xy) {
+            store.add_component(e, (Raider {},));
+            raiders.push(e);
+        }
+    }
+
+    raiders
+}
+
+/// What a raider does with no one to fight: burn the chief house, then whichever house is closest
+pub fn raider_intent(map: &Map, store: &AllStoragesViewMut, id: EntityId, pos: &Position, turn: Turn) -> Option<Intent> {
+    let vpos = store.borrow::<View<Position>>().unwrap();
+    let vchief = store.borrow::<View<ChiefHouse>>().unwrap();
+    let vhouse = store.borrow::<View<PlankHouse>>().unwrap();
+    let vstatus = store.borrow::<View<StatusEffects>>().unwrap();
+
+    let me = pos.ps[0];
+    let standing = |e: &EntityId| !has_status(&vstatus, *e, StatusKind::Burning);
+
+    let mut target = vchief.iter().with_id().map(|(e, _)| e).find(standing);
+    if target.is_none() {
+        target = vhouse
+            .iter()
+            .with_id()
+            .map(|(e, _)| e)
+            .filter(standing)
+            .filter_map(|e| Some((e, DistanceAlg::Pythagoras.distance2d(me, vpos.get(e).ok()?.ps[0]))))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(e, _)| e);
+    }
+    let target = target?;
+    let footprint = &vpos.get(target).ok()?.ps;
+
+    if footprint.iter().any(|p| DistanceAlg::Pythagoras.distance2d(me, *p) < 1.5) {
+        return Some(Intent {
+            name: "set fire".to_string(),
+            owner: id,
+            task: Task::Burn(InputTargets::House),
+            target: vec![Target::from(target)],
+            turn,
+        });
+    }
+
+    open_tile_next_to(map, footprint, me).map(|p| Intent {
+        name: "march on village".to_string(),
+        owner: id,
+        task: Task::MoveTo(InputTargets::None),
+        target: vec![Target::from(p)],
+        turn,
+    })
+}
+
+/// Open tile next to a building closest to `me`
+fn open_tile_next_to(map: &Map, footprint: &[Point], me: Point) -> Option<Point> {
+    let mut spot: Option<(Point, f32)> = None;
+
+    for p in footprint.iter() {
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let n = Point::new(p.x + dx, p.y + dy);
+                if !is_open(map, (n.x, n.y)) {
+                    continue;
+                }
+
+                let dist = DistanceAlg::Pythagoras.distance2d(me, n);
+                if spot.map_or(true, |(_, d)| dist < d) {
+                    spot = Some((n, dist));
+                }
+            }
+        }
+    }
+
+    spot.map(|(p, _)| p)
+}
+
+fn is_open(map: &Map, xy: XY) -> bool {
+    map.in_bounds(xy) && !map.blocked[map.xy_idx(xy)] && !map.blocks_movement(map.xy_idx(xy))
+}
+
+/// Random open tile on one of the map edges with a way to the chief house
+fn edge_tile(map: &Map, rng: &mut RandomNumberGenerator, chief: &[Point]) -> Option<XY> {
+    for _ in 0..50 {
+        let xy = match rng.range(0, 4) {
+            0 => (1, rng.range(1, map.size.1 - 1)),
+            1 => (map.size.0 - 2, rng.range(1, map.size.1 - 1)),
+            2 => (rng.range(1, map.size.0 - 1), 1),
+            _ => (rng.range(1, map.size.0 - 1), map.size.1 - 2),
+        };
+
+        if !is_open(map, xy) {
+            continue;
+        }
+
+        let start = Point::new(xy.0, xy.1);
+        if let Some(goal) = open_tile_next_to(map, chief, start) {
+            if map.get_path(start, goal).success {
+                return Some(xy);
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,68 @@
+use crate::raws::factions::{FactionTable, Relation};
+use crate::ai::intent::Intent;
+use crate::utils::Target;
+use crate::world::components::{Actor, AreaOfEffect, Mana, PhysicalStats, Position, Spell, WantsToUseItem};
+use crate::world::map::Map;
+use rltk::{DistanceAlg, Point};
+use shipyard::{AddComponent, AllStorages, EntityId, Get, IntoIter, UniqueView, View, ViewMut};
+
+/// Refills mana and counts down spell cooldowns, once a turn
+pub fn run_spell_system(mut vmana: ViewMut<Mana>, mut vspell: ViewMut<Spell>) {
+    for mana in (&mut vmana).iter() {
+        mana.current = i32::min(mana.current + mana.regen, mana.max);
+    }
+
+    for spell in (&mut vspell).iter() {
+        if spell.cooldown_left > 0 {
+            spell.cooldown_left -= 1;
+        }
+    }
+}
+
+/// Enemies and friends of `caster` that `spell` would catch if cast at `target`. The caster counts as a friend
+pub fn blast(store: &AllStorages, caster: EntityId, spell: EntityId, target: Point) -> (usize, usize) {
+    let map = store.borrow::<UniqueView<Map>>().unwrap();
+    let factions = store.borrow::<UniqueView<FactionTable>>().unwrap();
+    let vactor = store.borrow::<View<Actor>>().unwrap();
+    let vstats = store.borrow::<View<PhysicalStats>>().unwrap();
+    let vaoe = store.borrow::<View<AreaOfEffect>>().unwrap();
+
+    let radius = vaoe.get(spell).map_or(0, |aoe| aoe.radius);
+
+    let mut enemies = 0;
+    let mut friends = 0;
+    for x in target.x - radius..=target.x + radius {
+        for y in target.y - radius..=target.y + radius {
+            let p = Point::new(x, y);
+            if !map.in_bounds((x, y)) || DistanceAlg::Pythagoras.distance2d(p, target) > radius as f32 {
+                continue;
+            }
+
+            for e in map.tile_content[map.point_idx(p)].iter() {
+                if vstats.get(*e).is_err() {
+                    continue;
+                }
+
+                if *e != caster && factions.relation_between(&vactor, caster, *e) == Relation::Hostile {
+                    enemies += 1;
+                } else {
+                    friends += 1;
+                }
+            }
+        }
+    }
+
+    (enemies, friends)
+}
+
+/// Queues the spell picked by a `Task::Cast` intent, which targets [victim, spell]
+pub fn cast(store: &AllStorages, intent: &Intent) {
+    if let (Some(Target::ENTITY(victim)), Some(Target::ENTITY(spell))) = (intent.target.get(0), intent.target.get(1)) {
+        let p = match store.borrow::<View<Position>>().unwrap().get(*victim) {
+            Ok(pos) => pos.ps[0],
+            Err(_) => return,
+        };
+
+        store.borrow::<ViewMut<WantsToUseItem>>().unwrap().add_component_unchecked(intent.owner, WantsToUseItem { item: *spell, target: Some(p) });
+    }
+}
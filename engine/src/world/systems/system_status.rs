@@ -0,0 +1,84 @@
+use crate::world::components::{PhysicalStats, Position, StatusEffects, StatusKind};
+use crate::world::effects::{EffectQueue, EffectType, Targets};
+use crate::world::map::Map;
+use crate::utils::InvalidPoint;
+use shipyard::{EntityId, Get, IntoIter, IntoWithId, Remove, UniqueViewMut, View, ViewMut};
+
+/// Runs the per turn hook of every status effect, then counts them down
+pub fn run_status_system(
+    mut map: UniqueViewMut<Map>,
+    mut effects: UniqueViewMut<EffectQueue>,
+    vpos: View<Position>,
+    vstats: View<PhysicalStats>,
+    mut vstatus: ViewMut<StatusEffects>,
+) {
+    let mut to_remove: Vec<EntityId> = vec![];
+
+    for (id, statuses) in (&mut vstatus).iter().with_id() {
+        let has_stats = vstats.get(id).is_ok();
+
+        for status in statuses.effects.iter_mut() {
+            match status.kind {
+                StatusKind::Burning => {
+                    if has_stats {
+                        effects.add_effect(
+                            None,
+                            EffectType::Damage {
+                                amount: status.magnitude,
+                                target: Targets::Single { target: id },
+                            },
+                        );
+                    }
+
+                    // set fire to whatever they're standing on
+                    if let Ok(pos) = vpos.get(id) {
+                        for p in pos.ps.iter() {
+                            let idx = map.xy_idx(p.to_xy());
+                            if map.is_flammable(idx) && map.fire_turns[idx] == 0 {
+                                map.fire_turns[idx] = status.turns;
+                            }
+                        }
+                    }
+                }
+                StatusKind::Poisoned if has_stats => {
+                    effects.add_effect(
+                        None,
+                        EffectType::Damage {
+                            amount: status.magnitude,
+                            target: Targets::Single { target: id },
+                        },
+                    );
+                }
+                StatusKind::Regenerating if has_stats => {
+                    effects.add_effect(
+                        None,
+                        EffectType::Heal {
+                            amount: status.magnitude,
+                            target: Targets::Single { target: id },
+                        },
+                    );
+                }
+                // the rest change how other systems treat the entity
+                _ => {}
+            }
+
+            status.turns -= 1;
+        }
+
+        statuses.effects.retain(|s| s.turns > 0);
+        if statuses.effects.is_empty() {
+            to_remove.push(id);
+        }
+    }
+
+    for id in to_remove {
+        vstatus.remove(id);
+    }
+}
+
+pub fn has_status(vstatus: &View<StatusEffects>, entity: EntityId, kind: StatusKind) -> bool {
+    match vstatus.get(entity) {
+        Ok(statuses) => statuses.has(kind),
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,111 @@
+use crate::world::components::{Actor, ConstructionSite, Inventory, Item, ItemType, Stockpile};
+use crate::world::effects::{EffectQueue, EffectType};
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueViewMut, View, ViewMut};
+
+/// Recounts what the village has stored. Anything with an inventory that isn't an actor or a construction site counts as storage
+pub fn run_stockpile_system(
+    mut stockpile: UniqueViewMut<Stockpile>,
+    vactor: View<Actor>,
+    vsite: View<ConstructionSite>,
+    vinv: View<Inventory>,
+    vitem: View<Item>,
+) {
+    stockpile.counts.clear();
+
+    for (id, inv) in vinv.iter().with_id() {
+        if !is_storage(&vactor, &vsite, id) {
+            continue;
+        }
+
+        for item in inv.items.iter() {
+            if let Ok(item) = vitem.get(*item) {
+                *stockpile.counts.entry(item.typ).or_insert(0) += 1;
+            }
+        }
+    }
+}
+
+fn is_storage(vactor: &View<Actor>, vsite: &View<ConstructionSite>, id: EntityId) -> bool {
+    vactor.get(id).is_err() && vsite.get(id).is_err()
+}
+
+/// Takes items out of storage and destroys them. Returns false without taking anything if the village doesn't have enough
+pub fn withdraw(store: &AllStoragesViewMut, typ: ItemType, amount: i32) -> bool {
+    let mut stockpile = store.borrow::<UniqueViewMut<Stockpile>>().unwrap();
+    if stockpile.count(typ) < amount {
+        return false;
+    }
+
+    let mut effects = store.borrow::<UniqueViewMut<EffectQueue>>().unwrap();
+    let vactor = store.borrow::<View<Actor>>().unwrap();
+    let vsite = store.borrow::<View<ConstructionSite>>().unwrap();
+    let vitem = store.borrow::<View<Item>>().unwrap();
+    let mut vinv = store.borrow::<ViewMut<Inventory>>().unwrap();
+
+    // the ledger is only recounted once a tick, check what's really there before taking any of it
+    let stored: i32 = vinv
+        .iter()
+        .with_id()
+        .filter(|(id, _)| is_storage(&vactor, &vsite, *id))
+        .map(|(_, inv)| inv.count_type(&vitem, typ))
+        .sum();
+    if stored < amount {
+        return false;
+    }
+
+    let mut taken = 0;
+    for (id, inv) in (&mut vinv).iter().with_id() {
+        if !is_storage(&vactor, &vsite, id) {
+            continue;
+        }
+
+        while taken < amount {
+            match inv.items.iter().position(|i| vitem.get(*i).map_or(false, |i| i.typ == typ)) {
+                Some(i) => {
+                    effects.add_effect(None, EffectType::Delete { entity: inv.items.remove(i) });
+                    taken += 1;
+                }
+                None => break,
+            }
+        }
+    }
+
+    *stockpile.counts.entry(typ).or_insert(0) -= taken;
+
+    taken == amount
+}
+
+/// Moves one item out of storage into `to`'s inventory, if it has room
+pub fn take(store: &AllStoragesViewMut, typ: ItemType, to: EntityId) -> bool {
+    let mut stockpile = store.borrow::<UniqueViewMut<Stockpile>>().unwrap();
+    let vactor = store.borrow::<View<Actor>>().unwrap();
+    let vsite = store.borrow::<View<ConstructionSite>>().unwrap();
+    let vitem = store.borrow::<View<Item>>().unwrap();
+    let mut vinv = store.borrow::<ViewMut<Inventory>>().unwrap();
+
+    match vinv.get(to) {
+        Ok(inv) if (inv.items.len() as i32) < inv.capacity => {}
+        _ => return false,
+    }
+
+    let mut item = None;
+    for (id, inv) in (&mut vinv).iter().with_id() {
+        if !is_storage(&vactor, &vsite, id) || id == to {
+            continue;
+        }
+
+        if let Some(i) = inv.items.iter().position(|i| vitem.get(*i).map_or(false, |i| i.typ == typ)) {
+            item = Some(inv.items.remove(i));
+            break;
+        }
+    }
+
+    match item {
+        Some(item) => {
+            (&mut vinv).get(to).unwrap().items.push(item);
+            *stockpile.counts.entry(typ).or_insert(0) -= 1;
+            true
+        }
+        None => false,
+    }
+}
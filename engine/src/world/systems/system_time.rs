@@ -1,5 +1,5 @@
 use crate::world::components::{Aging, Actor, ActorType, Turn, RNG, PlankHouse, Position};
-use crate::world::effects::{add_effect, EffectType, Targets};
+use crate::world::effects::{EffectQueue, EffectType, Targets};
 use crate::entity_factory::EntitySpawnTypes;
 use crate::world::map::Map;
 use shipyard::{IntoIter, IntoWithId, UniqueViewMut, View, ViewMut};
@@ -8,6 +8,7 @@ pub fn run_time_system(
     map: UniqueViewMut<Map>,
     mut turn: UniqueViewMut<Turn>,
     mut rng: UniqueViewMut<RNG>,
+    mut effects: UniqueViewMut<EffectQueue>,
     vactor: View<Actor>,
     mut vaging: ViewMut<Aging>,
     vhouse: ViewMut<PlankHouse>,
@@ -22,22 +23,28 @@ pub fn run_time_system(
 
         if actor.atype == ActorType::Villager {
             if aging.turns > 250 && rng.0.roll_dice(1, 3) < 2{
-                add_effect(None, EffectType::Delete { entity: id });
+                effects.add_effect(None, EffectType::Delete { entity: id });
             } else {
                 num_villagers += 1;
             }
         }
     }
 
-    if num_villagers > 0 && num_villagers < 20 {
-        let mut houses = vec![];
-        for (_, (_, pos)) in (&vhouse, &vpos).iter().with_id() {
+    // the village only grows into free beds
+    let mut beds = 0;
+    let mut houses = vec![];
+    for (house, pos) in (&vhouse, &vpos).iter() {
+        beds += house.housing_cap;
+
+        if (house.villagers.len() as i32) < house.housing_cap {
             let mut point = pos.ps[0];
             point.y -= 1;
             houses.push(map.point_idx(point));
         }
+    }
 
+    if num_villagers > 0 && num_villagers < beds && !houses.is_empty() {
         let idx = rng.0.roll_dice(1, houses.len() as i32) as usize - 1;
-        add_effect(None, EffectType::Spawn { etype: EntitySpawnTypes::Villager, target: Targets::Tile { tile_idx: houses[idx] } })
+        effects.add_effect(None, EffectType::Spawn { etype: EntitySpawnTypes::Villager, target: Targets::Tile { tile_idx: houses[idx] } })
     }
 }
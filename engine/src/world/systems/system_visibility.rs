@@ -1,4 +1,4 @@
-use crate::world::components::{Position, SpatialKnowledge, Vision};
+use crate::world::components::{Position, SpatialKnowledge, StatusEffects, StatusKind, Vision};
 use crate::world::map::Map;
 use crate::utils::InvalidPoint;
 use rltk;
@@ -8,6 +8,7 @@ use shipyard::{Get, IntoIter, IntoWithId, UniqueView, View, ViewMut};
 pub fn run_visibility_system(
     map: UniqueView<Map>,
     vpos: View<Position>,
+    vstatus: View<StatusEffects>,
     mut vvs: ViewMut<Vision>,
     mut vspace: ViewMut<SpatialKnowledge>,
 ) {
@@ -16,9 +17,15 @@ pub fn run_visibility_system(
         let pos = pos.ps.first().unwrap();
 
         vs.dirty = false;
-        vs.visible_tiles = rltk::field_of_view(Point::new(pos.x, pos.y), vs.range, &*map);
+
+        // blind entities only know what's right next to them
+        let range = if super::system_status::has_status(&vstatus, id, StatusKind::Blind) { 1 } else { vs.range };
+
+        vs.visible_tiles = rltk::field_of_view(Point::new(pos.x, pos.y), range, &*map);
         vs.visible_tiles
             .retain(|p| p.x >= 0 && p.x < map.size.0 && p.y >= 0 && p.y < map.size.1);
+        // field_of_view comes out of a HashSet, keep the order fixed so ties break the same way every run
+        vs.visible_tiles.sort_by_key(|p| (p.y, p.x));
 
         if let Ok(space) = (&mut vspace).get(id) {
             for vis in vs.visible_tiles.iter() {
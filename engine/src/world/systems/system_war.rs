@@ -0,0 +1,132 @@
+use crate::ai::input::InputTargets;
+use crate::ai::intent::{Intent, Task};
+use crate::ai::AI;
+use crate::raws::behaviors::BehaviorProfiles;
+use crate::tiles::TileType;
+use crate::utils::Target;
+use crate::world::components::{Actor, ActorType, Faction, GameLog, PhysicalStats, Position, Turn, War};
+use crate::world::map::Map;
+use rltk::{DistanceAlg, Point};
+use shipyard::{AllStoragesViewMut, EntityId, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View};
+
+/// Minions a side keeps in its trench, any more go over the top
+pub const GARRISON_SIZE: usize = 8;
+
+/// Ends the war once a side has lost its wizard
+pub fn run_war_system(mut war: UniqueViewMut<War>, mut log: UniqueViewMut<GameLog>, vactor: View<Actor>, vstats: View<PhysicalStats>) {
+    if war.sides.is_empty() || war.over {
+        return;
+    }
+
+    let alive: Vec<Faction> = war
+        .sides
+        .iter()
+        .copied()
+        .filter(|side| {
+            (&vactor, &vstats)
+                .iter()
+                .any(|(actor, stats)| actor.atype == ActorType::Wizard && actor.faction == *side && stats.hp > 0)
+        })
+        .collect();
+
+    if alive.len() > 1 {
+        return;
+    }
+
+    war.over = true;
+    war.winner = alive.first().copied();
+    match war.winner {
+        Some(side) => log.messages.push(format!("{:?} wins, the enemy wizard is dead!", side)),
+        None => log.messages.push("Both wizards are dead, nobody wins".to_string()),
+    }
+}
+
+/// What a minion does with no one to fight: man its side's trench and dig it out, or charge the enemy wizard once the trench is full
+pub fn minion_intent(map: &Map, store: &AllStoragesViewMut, id: EntityId, faction: Faction, pos: &Position, last: Option<&Intent>, turn: Turn) -> Option<Intent> {
+    let war = store.borrow::<UniqueView<War>>().unwrap();
+    if war.over || !war.sides.contains(&faction) {
+        return None;
+    }
+
+    let vactor = store.borrow::<View<Actor>>().unwrap();
+    let vpos = store.borrow::<View<Position>>().unwrap();
+
+    let mut own_wizard: Option<Point> = None;
+    let mut enemy_wizard: Option<EntityId> = None;
+    for (e, (actor, wpos)) in (&vactor, &vpos).iter().with_id() {
+        if actor.atype != ActorType::Wizard {
+            continue;
+        }
+
+        if actor.faction == faction {
+            own_wizard = Some(wpos.ps[0]);
+        } else if war.sides.contains(&actor.faction) {
+            enemy_wizard = Some(e);
+        }
+    }
+
+    // leaderless minions just fight whatever they see
+    let own_wizard = own_wizard?;
+    let me = pos.ps[0];
+    let left = own_wizard.x < map.size.0 / 2;
+    let on_our_side = |p: Point| (p.x < map.size.0 / 2) == left;
+    let in_our_trench = |p: Point| on_our_side(p) && map.tiles[map.point_idx(p)] == TileType::Trench;
+
+    let mut intent = Intent {
+        name: "hold the trench".to_string(),
+        owner: id,
+        task: Task::Idle,
+        target: vec![],
+        turn,
+    };
+
+    // once over the top there's no going back
+    let charging = last.map_or(false, |i| i.name == "go over the top");
+
+    if in_our_trench(me) && !charging {
+        // widen the trench while it's quiet, see the dig_in profile in raws/behaviors.json
+        if let Some(actions) = store.borrow::<UniqueView<BehaviorProfiles>>().unwrap().get("dig_in") {
+            let dig = AI::choose_intent(actions.clone(), store, id);
+            if dig.task != Task::Idle {
+                return Some(dig);
+            }
+        }
+
+        return Some(intent);
+    }
+
+    let garrison = (&vactor, &vpos)
+        .iter()
+        .filter(|(actor, p)| actor.atype == ActorType::Orc && actor.faction == faction && in_our_trench(p.ps[0]))
+        .count();
+
+    if garrison < GARRISON_SIZE && !charging {
+        let mut spot: Option<(Point, f32)> = None;
+        for idx in 0..map.tiles.len() {
+            let p = map.idx_point(idx);
+            if !in_our_trench(p) || map.blocked[idx] {
+                continue;
+            }
+
+            let dist = DistanceAlg::Pythagoras.distance2d(me, p);
+            if spot.map_or(true, |(_, d)| dist < d) {
+                spot = Some((p, dist));
+            }
+        }
+
+        if let Some((p, _)) = spot {
+            intent.name = "man the trench".to_string();
+            intent.task = Task::MoveTo(InputTargets::None);
+            intent.target = vec![Target::from(p)];
+            return Some(intent);
+        }
+    }
+
+    // wizards keep a dijkstra map so minions can find a way to them
+    let enemy_wizard = enemy_wizard?;
+    intent.name = "go over the top".to_string();
+    intent.task = Task::MoveTo(InputTargets::Enemy);
+    intent.target = vec![Target::from(enemy_wizard)];
+
+    Some(intent)
+}
@@ -1,25 +1,37 @@
-use crate::config::{GameMode, GameSettings};
-use crate::world::components::{Equipped, Player, Position, Vision, PlayerID, GameLog, FrameTime, PPoint, Turn, RNG, Inventory};
-use crate::world::effects::{add_effect, EffectType};
+use crate::config::GameSettings;
+use crate::world::components::{Equipped, Player, Position, Vision, PlayerID, GameLog, FrameTime, PPoint, Turn, Tick, RNG, Inventory, SpawnTable, Stockpile, War, Actor, Energy, Spellbook};
+use crate::world::effects::{EffectQueue, EffectType};
+use crate::world::events::{EventStream, GameEvent};
 use crate::generators::map_builders;
-use crate::world::map::Map;
-use crate::world::systems;
-use crate::world::systems::system_particle;
+use crate::raws::{self, behaviors::BehaviorProfiles, RawMaster};
+use crate::world::levels::{Levels, StoredLevel};
+use crate::world::map::{Map, XY};
+use crate::tiles::TileType;
+use crate::world::save::{self, SaveGame};
+use crate::world::systems::{self, Schedule};
+use crate::world::systems::{system_construction, system_energy, system_map_indexing, system_particle};
+
+use std::collections::HashMap;
 
 use rltk::Point;
 use shipyard::{
-    EntitiesView, EntityId, Get, UniqueView, UniqueViewMut, View, ViewMut, World, AllStoragesViewMut,
+    EntitiesView, EntityId, Get, IntoIter, UniqueView, UniqueViewMut, View, ViewMut, World, AllStoragesViewMut,
 };
 
 pub struct WorldSim {
     pub world: World,
     pub settings: GameSettings,
+    pub schedule: Schedule,
 }
 
 impl WorldSim {
     pub fn new(settings: GameSettings) -> Self {
+        let world = World::new();
+        systems::register_workloads(&world);
+
         Self {
-            world: World::new(),
+            world,
+            schedule: Schedule::new(&settings.systems),
             settings,
         }
     }
@@ -44,8 +56,128 @@ impl WorldSim {
         self.world.borrow::<UniqueView<PPoint>>().unwrap()
     }
 
+    /// Queue an effect on this world, applied the next time the effects queue runs
+    pub fn add_effect(&self, creator: Option<EntityId>, effect_type: EffectType) {
+        self.world.borrow::<UniqueViewMut<EffectQueue>>().unwrap().add_effect(creator, effect_type);
+    }
+
+    /// Marks out a site for villagers to build `building` on, if it fits there
+    pub fn designate_construction(&mut self, xy: XY, building: &str) -> Option<EntityId> {
+        self.world.run(|mut store: AllStoragesViewMut| {
+            let size = store.borrow::<UniqueView<RawMaster>>().unwrap().get(building)?.size.unwrap_or([1, 1]);
+            if !system_construction::site_is_clear(&store.borrow::<UniqueView<Map>>().unwrap(), xy, size) {
+                return None;
+            }
+
+            crate::entity_factory::construction_site(&mut store, xy, building)
+        })
+    }
+
+    /// Take every event published since the last drain
+    pub fn drain_events(&self) -> Vec<GameEvent> {
+        self.world.borrow::<UniqueViewMut<EventStream>>().unwrap().drain()
+    }
+
+    /// Re-read raws/behaviors.json and rebuild the actions of every actor that has behaviors
+    pub fn reload_behaviors(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let profiles = BehaviorProfiles::load(raws::BEHAVIOR_RAWS_PATH)?;
+
+        let mut vactor = self.world.borrow::<ViewMut<Actor>>().unwrap();
+        for actor in (&mut vactor).iter() {
+            if !actor.behaviors.is_empty() {
+                actor.actions = profiles.actions_for(&actor.behaviors);
+            }
+        }
+        drop(vactor);
+
+        *self.world.borrow::<UniqueViewMut<BehaviorProfiles>>().unwrap() = profiles;
+
+        Ok(())
+    }
+
+    /// Run the schedule once. This is a single tick, see `run_turn` for a whole turn
     pub fn run_systems(&mut self) {
-        systems::run_systems(&mut self.world, true, true);
+        self.schedule.run(&self.world);
+    }
+
+    pub fn run_turn(&mut self) {
+        for _ in 0..system_energy::TICKS_PER_TURN {
+            self.run_systems();
+        }
+    }
+
+    pub fn spend_energy(&self, entity: EntityId, cost: i32) {
+        if let Ok(energy) = (&mut self.world.borrow::<ViewMut<Energy>>().unwrap()).get(entity) {
+            energy.value -= cost;
+        }
+    }
+
+    /// Run ticks until the player has the energy to act again
+    pub fn run_until_player_can_act(&mut self) {
+        let player_id = self.get_player_id().0;
+
+        // stop eventually in case the player can't gain energy
+        for _ in 0..system_energy::ACTION_COST {
+            let can_act = self.world.borrow::<View<Energy>>().unwrap().get(player_id).map(|e| system_energy::can_act(e));
+
+            match can_act {
+                Ok(true) => return,
+                Ok(false) => self.run_systems(),
+                Err(_) => {
+                    // no energy to wait on, just let a turn pass
+                    self.run_turn();
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Write every entity, component and unique to a json file at `path`
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let save = save::save_world(&self.world, &self.settings);
+        std::fs::write(path, serde_json::to_string(&save)?)?;
+
+        Ok(())
+    }
+
+    /// Rebuild a world written by `save`. Entity ids are reassigned, so references between entities are remapped
+    pub fn load(path: &str) -> Result<WorldSim, Box<dyn std::error::Error>> {
+        let save: SaveGame = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+        let mut world = World::new();
+        systems::register_workloads(&world);
+
+        let mut map = save.map;
+        map.tile_content = vec![Vec::new(); map.len()];
+        world.add_unique(map);
+        world.add_unique(PPoint(save.player_pos));
+        world.add_unique(Turn(save.turn));
+        world.add_unique(Tick(save.tick));
+        world.add_unique(RNG(save.rng));
+        world.add_unique(GameLog { messages: save.log });
+        world.add_unique(system_particle::ParticleBuilder::new());
+        world.add_unique(FrameTime(0.));
+        world.add_unique(EffectQueue::default());
+        world.add_unique(EventStream::default());
+        world.add_unique(SpawnTable(save.settings.spawn_table.clone()));
+        world.add_unique(Stockpile::default());
+        world.add_unique(save.war);
+        world.add_unique(raws::load_raws());
+        world.add_unique(raws::load_behaviors());
+        world.add_unique(raws::load_factions());
+        world.add_unique(Levels { stored: save.levels });
+
+        let ids = save::load_entities(&mut world, save.entities);
+        let player_id = *ids.get(&save.player_id).ok_or("Player not found in save")?;
+        world.add_unique(PlayerID(player_id));
+
+        world.run(system_map_indexing::run_map_indexing_system);
+
+        Ok(WorldSim {
+            world,
+            schedule: Schedule::new(&save.settings.systems),
+            settings: save.settings,
+        })
     }
 
     pub fn entities_to_delete_on_level_change(world: &mut World) -> Vec<EntityId> {
@@ -57,12 +189,15 @@ impl WorldSim {
         let vplayer = world.borrow::<View<Player>>().unwrap();
         let vinv = world.borrow::<View<Inventory>>().unwrap();
         let vequipped = world.borrow::<View<Equipped>>().unwrap();
+        let vspellbook = world.borrow::<View<Spellbook>>().unwrap();
 
         for id in entities.iter() {
             let mut to_delete = true;
 
             if let Ok(_) = vplayer.get(id) {
                 to_delete = false;
+            } else if vspellbook.get(player_id).map_or(false, |book| book.spells.contains(&id)) {
+                to_delete = false;
             } else if let Ok(inventory) = vinv.get(player_id) {
                 for e in inventory.items.iter() {
                     if *e == id {
@@ -92,34 +227,45 @@ impl WorldSim {
         }
 
         // Generate map
-        let mut map_builder = match self.settings.mode {
-            GameMode::VillageSim => map_builders::village_builder(new_depth, self.settings.mapsize),
-            GameMode::RL => map_builders::rl_builder(new_depth, self.settings.mapsize),
-            GameMode::OrcHalls => map_builders::orc_halls_builder(new_depth, self.settings.mapsize),
-            GameMode::MapDemo => map_builders::random_builder(new_depth, self.settings.mapsize),
-            GameMode::OrcArena => map_builders::arena_builder(new_depth, self.settings.mapsize),
-            GameMode::TestMode => map_builders::village_builder(new_depth, self.settings.mapsize),
-        };
+        let mut map_builder = {
+            let mut rng = self.world.borrow::<UniqueViewMut<RNG>>().unwrap();
+
+            let mut map_builder = match map_builders::named_builder(&self.settings.map_builder, new_depth, self.settings.mapsize, &mut rng.0) {
+                Some(b) => b,
+                None => {
+                    dbg!("Unknown map builder, using simple", &self.settings.map_builder);
+                    map_builders::named_builder("simple", new_depth, self.settings.mapsize, &mut rng.0).unwrap()
+                }
+            };
 
-        map_builder.build_map();
+            map_builder.build_map(&mut rng.0);
+            map_builder
+        };
 
         let start_pos;
         {
             let mut map = self.world.borrow::<UniqueViewMut<Map>>().unwrap();
             *map = map_builder.get_map();
+            map.depth = new_depth;
             start_pos = map_builder.get_starting_position().ps.first().unwrap().clone();
         }
 
         // Spawn monsters and items
         map_builder.spawn_entities(&mut self.world);
 
-        // Update player position unique
+        self.place_player(start_pos);
+
+        return start_pos;
+    }
+
+    /// Update the player position unique and the player's position
+    fn place_player(&mut self, point: Point) {
         self.world.run(
             |mut ppos: UniqueViewMut<PPoint>,
              player_id: UniqueView<PlayerID>,
              mut vpos: ViewMut<Position>,
              mut vvs: ViewMut<Vision>| {
-                *ppos = PPoint(Point::new(start_pos.x, start_pos.y));
+                *ppos = PPoint(Point::new(point.x, point.y));
                 if let Ok(pos) = (&mut vpos).get(player_id.0) {
                     pos.ps[0] = ppos.0;
                 }
@@ -129,8 +275,54 @@ impl WorldSim {
                 }
             },
         );
+    }
 
-        return start_pos;
+    /// Move the player to another depth. The level being left is stored and comes back as it was
+    pub fn change_depth(&mut self, new_depth: usize) {
+        let current_depth = self.get_map().depth;
+        if new_depth < 1 || new_depth == current_depth {
+            return;
+        }
+
+        // put the current level away
+        let ids = Self::entities_to_delete_on_level_change(&mut self.world);
+        let stored = StoredLevel {
+            map: (*self.get_map()).clone(),
+            entities: save::save_entities(&self.world, &ids),
+            player_pos: self.get_player_pos().0,
+        };
+        self.world.borrow::<UniqueViewMut<Levels>>().unwrap().stored.insert(current_depth, stored);
+        for id in ids {
+            self.world.delete_entity(id);
+        }
+
+        let restored = self.world.borrow::<UniqueViewMut<Levels>>().unwrap().stored.remove(&new_depth);
+        match restored {
+            Some(level) => {
+                let mut map = level.map;
+                map.tile_content = vec![Vec::new(); map.len()];
+                *self.world.borrow::<UniqueViewMut<Map>>().unwrap() = map;
+
+                // the player and what they carry are still around, keep references to them
+                let alive: HashMap<u64, EntityId> = self.world.borrow::<EntitiesView>().unwrap().iter().map(|id| (id.inner(), id)).collect();
+                save::load_entities_with_ids(&mut self.world, level.entities, alive);
+
+                self.place_player(level.player_pos);
+            }
+            None => {
+                let start_pos = self.generate_map(new_depth);
+
+                // a way back up
+                let mut map = self.world.borrow::<UniqueViewMut<Map>>().unwrap();
+                let idx = map.point_idx(start_pos);
+                map.tiles[idx] = TileType::StairsUp;
+            }
+        }
+
+        self.world.run(system_map_indexing::run_map_indexing_system);
+
+        let msg = if new_depth > current_depth { "You descend the stairs" } else { "You climb the stairs" };
+        self.get_log_mut().messages.push(format!("{} to level {}", msg, new_depth));
     }
 
     // pub fn next_level(world: &mut World) {
@@ -148,17 +340,29 @@ impl WorldSim {
     // }
 
     pub fn reset_engine(&mut self, settings: GameSettings) {
-        self.settings = settings;
+        self.settings = settings.clone();
         
         // Delete everything
         // world.clear();
         self.world = World::new();
+        systems::register_workloads(&self.world);
+        self.schedule = Schedule::new(&settings.systems);
 
         // Re-add defaults for all uniques
         self.world.add_unique(Map::new(settings.mapsize));
         self.world.add_unique(PPoint(Point::new(0, 0)));
         self.world.add_unique(Turn(0));
-        self.world.add_unique(RNG(rltk::RandomNumberGenerator::new()));
+        self.world.add_unique(Tick(0));
+        self.world.add_unique(RNG(rltk::RandomNumberGenerator::seeded(settings.seed)));
+        self.world.add_unique(EffectQueue::default());
+        self.world.add_unique(EventStream::default());
+        self.world.add_unique(SpawnTable(settings.spawn_table.clone()));
+        self.world.add_unique(Stockpile::default());
+        self.world.add_unique(War::default());
+        self.world.add_unique(raws::load_raws());
+        self.world.add_unique(raws::load_behaviors());
+        self.world.add_unique(raws::load_factions());
+        self.world.add_unique(Levels::default());
 
         // make a player entity
         let player_id = self.world.run(|mut store: AllStoragesViewMut| 
@@ -166,35 +370,26 @@ impl WorldSim {
         );
         self.world.add_unique(PlayerID(player_id));
 
-        self.world.add_unique(GameLog { messages: vec![] });
+        self.world.add_unique(GameLog { messages: vec![format!("Seed: {}", settings.seed)] });
         self.world.add_unique(system_particle::ParticleBuilder::new());
         self.world.add_unique(FrameTime(0.));
 
-        match settings.mode {
-            GameMode::VillageSim => {
-                // self.world.add_component(player_id, IsCamera {});
-            }
-            _ => {}
-        }
-
         // Generate new map
         self.generate_map( 1);
 
-        // give the player some items
-        let e = self.world.run(|mut store: AllStoragesViewMut| {
-            crate::entity_factory::magic_missile_scroll(&mut store, (0, 0))
-        });
-        add_effect(Some(player_id), EffectType::PickUp { entity: e });
-
-        let e = self.world.run(|mut store: AllStoragesViewMut| {
-            crate::entity_factory::dagger(&mut store, (0, 0))
-        });
-        add_effect(Some(player_id), EffectType::PickUp { entity: e });
-
-        let e = self.world.run(|mut store: AllStoragesViewMut| {
-            crate::entity_factory::fireball_scroll(&mut store, (0, 0))
-        });
-        add_effect(Some(player_id), EffectType::PickUp { entity: e });
+        // give the player their starting items
+        for name in settings.starting_inventory.iter() {
+            let e = self.world.run(|mut store: AllStoragesViewMut| {
+                crate::entity_factory::spawn_named(&mut store, name, (0, 0))
+            });
+            if let Some(e) = e {
+                self.add_effect(Some(player_id), EffectType::PickUp { entity: e });
+            }
+        }
+
+        for name in settings.starting_spells.iter() {
+            self.world.run(|mut store: AllStoragesViewMut| crate::entity_factory::learn_spell(&mut store, player_id, name));
+        }
 
         // run the gas system for a while to get the level nice and steamy
         // for _ in 0..3000 {
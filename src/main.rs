@@ -1,13 +1,15 @@
-use engine::{Engine, EngineDelegate};
+use engine::{world::events::GameEvent, Engine, EngineDelegate, EngineOptions};
 use pixels::Error;
 
 use winit::event_loop::EventLoop;
 
 fn main() -> Result<(), Error> {
-    let delegate = VillageModeDelegate;
+    let delegate = VillageModeDelegate::default();
+
+    let options = parse_args();
 
     let event_loop = EventLoop::new();
-    let mut engine = Engine::new(&event_loop, Box::new(delegate));
+    let mut engine = Engine::new(&event_loop, Box::new(delegate), options);
 
     // let mut last_time = Instant::now();
     // main event loop
@@ -16,7 +18,36 @@ fn main() -> Result<(), Error> {
     });
 }
 
-struct VillageModeDelegate;
+// usage: trenchwizard [--mode MODE] [--seed N] [--record FILE] [--replay FILE]
+fn parse_args() -> EngineOptions {
+    let mut options = EngineOptions::default();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => match args.next().and_then(|s| s.parse::<u64>().ok()) {
+                Some(seed) => options.seed = Some(seed),
+                None => eprintln!("--seed expects a number"),
+            },
+            "--mode" => options.mode = args.next(),
+            "--record" => options.record = args.next(),
+            "--replay" => options.replay = args.next(),
+            _ => eprintln!("Unknown argument {}", arg),
+        }
+    }
+
+    options
+}
+
+// collects stats about the village from world events
+#[derive(Default)]
+struct VillageModeDelegate {
+    deaths: usize,
+    births: usize,
+    pickups: usize,
+    damage: i32,
+    tiles_burned: usize,
+}
 
 impl EngineDelegate for VillageModeDelegate {
     fn update(&mut self) {
@@ -24,6 +55,19 @@ impl EngineDelegate for VillageModeDelegate {
     }
 
     fn time_advanced(&mut self) {
-        println!("time_advanced");
+        println!(
+            "deaths: {} births: {} pickups: {} damage: {} tiles burned: {}",
+            self.deaths, self.births, self.pickups, self.damage, self.tiles_burned
+        );
+    }
+
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::EntityDied { .. } => self.deaths += 1,
+            GameEvent::VillagerBorn { .. } => self.births += 1,
+            GameEvent::ItemPickedUp { .. } => self.pickups += 1,
+            GameEvent::DamageDealt { amount, .. } => self.damage += amount,
+            GameEvent::TileBurned { .. } => self.tiles_burned += 1,
+        }
     }
 }
\ No newline at end of file
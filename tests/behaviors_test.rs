@@ -0,0 +1,68 @@
+mod common;
+
+use common::{actors, intent};
+use engine::{
+    ai::{intent::Task, labors::AIBehaviors},
+    raws::{behaviors::BehaviorProfiles, BEHAVIOR_RAWS_PATH},
+    simulation::Simulation,
+    world::components::{Actor, ActorType},
+};
+use shipyard::{IntoIter, View, ViewMut};
+
+#[test]
+fn every_behavior_has_a_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let profiles = BehaviorProfiles::load(BEHAVIOR_RAWS_PATH)?;
+    assert!(profiles.get("idle").is_some());
+
+    for b in [AIBehaviors::GatherWood, AIBehaviors::GatherFish, AIBehaviors::GatherGrain, AIBehaviors::AttackEnemies, AIBehaviors::MeetNeeds, AIBehaviors::Build, AIBehaviors::Defend, AIBehaviors::Flee, AIBehaviors::CastSpells] {
+        let name = b.profile_name().unwrap();
+        assert!(!profiles.get(name).unwrap().is_empty(), "empty profile {}", name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn villagers_get_actions_from_profiles() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 11)?;
+    let profiles = BehaviorProfiles::load(BEHAVIOR_RAWS_PATH)?;
+    let expected = profiles.actions_for(&[AIBehaviors::GatherWood, AIBehaviors::GatherFish, AIBehaviors::GatherGrain, AIBehaviors::MeetNeeds, AIBehaviors::Build, AIBehaviors::Defend, AIBehaviors::Flee]);
+
+    let check = |sim: &Simulation| {
+        let vactor = sim.world_sim.world.borrow::<View<Actor>>().unwrap();
+        let mut villagers = 0;
+        for actor in vactor.iter() {
+            if actor.atype == ActorType::Villager {
+                assert_eq!(actor.actions, expected);
+                villagers += 1;
+            }
+        }
+        assert!(villagers > 0);
+    };
+
+    check(&sim);
+
+    sim.world_sim.reload_behaviors()?;
+    check(&sim);
+    sim.step_n(10);
+
+    Ok(())
+}
+
+#[test]
+fn missing_profiles_leave_villagers_idle() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 11)?;
+    for actor in (&mut sim.world_sim.world.borrow::<ViewMut<Actor>>().unwrap()).iter() {
+        actor.actions.clear();
+    }
+    let villagers = actors(&sim, ActorType::Villager);
+    assert!(!villagers.is_empty());
+
+    sim.step_n(3);
+
+    for villager in villagers {
+        assert_eq!(intent(&sim, villager).map(|i| i.task), Some(Task::Idle));
+    }
+
+    Ok(())
+}
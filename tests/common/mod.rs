@@ -0,0 +1,44 @@
+// helpers shared by the integration tests, not every test file uses all of them
+#![allow(dead_code)]
+
+use engine::{
+    ai::intent::Intent,
+    entity_factory,
+    simulation::Simulation,
+    world::{
+        components::{Actor, ActorType, Faction, Mana},
+        map::XY,
+    },
+};
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, View};
+
+pub fn player(sim: &Simulation) -> EntityId {
+    sim.world_sim.get_player_id().0
+}
+
+pub fn actors(sim: &Simulation, atype: ActorType) -> Vec<EntityId> {
+    let vactor = sim.world_sim.world.borrow::<View<Actor>>().unwrap();
+    vactor.iter().with_id().filter(|(_, actor)| actor.atype == atype).map(|(id, _)| id).collect()
+}
+
+pub fn spawn(sim: &Simulation, name: &str, xy: XY) -> EntityId {
+    sim.world_sim.world.run(|mut store: AllStoragesViewMut| entity_factory::spawn_named(&mut store, name, xy)).unwrap()
+}
+
+pub fn spawn_for(sim: &Simulation, name: &str, xy: XY, faction: Faction) -> EntityId {
+    sim.world_sim.world.run(|mut store: AllStoragesViewMut| entity_factory::spawn_for_faction(&mut store, name, xy, faction)).unwrap()
+}
+
+pub fn remove_actors(sim: &mut Simulation, atype: ActorType) {
+    for id in actors(sim, atype) {
+        sim.world_sim.world.delete_entity(id);
+    }
+}
+
+pub fn intent(sim: &Simulation, id: EntityId) -> Option<Intent> {
+    sim.world_sim.world.borrow::<View<Intent>>().unwrap().get(id).ok().cloned()
+}
+
+pub fn mana(sim: &Simulation, id: EntityId) -> Mana {
+    *sim.world_sim.world.borrow::<View<Mana>>().unwrap().get(id).unwrap()
+}
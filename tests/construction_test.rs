@@ -0,0 +1,84 @@
+use engine::{
+    entity_factory,
+    simulation::Simulation,
+    tiles::TileType,
+    world::{
+        components::{ConstructionSite, Inventory, Needs, PlankHouse, Position},
+        map::XY,
+        systems::system_construction::{self, HOUSE},
+    },
+};
+use shipyard::{AllStoragesViewMut, Get, IntoIter, View, ViewMut};
+
+fn clear_spot(sim: &Simulation) -> XY {
+    let map = sim.get_map();
+    for y in 0..map.size.1 {
+        for x in 0..map.size.0 {
+            if system_construction::site_is_clear(&map, (x, y), [4, 4]) {
+                return (x, y);
+            }
+        }
+    }
+    panic!("nowhere to build");
+}
+
+#[test]
+fn delivered_logs_become_a_house() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let xy = clear_spot(&sim);
+
+    let site = sim.world_sim.designate_construction(xy, HOUSE).expect("site should fit");
+    let logs_needed = sim.world_sim.world.borrow::<View<ConstructionSite>>().unwrap().get(site).unwrap().logs_needed;
+    assert!(logs_needed > 0);
+
+    sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        let logs: Vec<_> = (0..logs_needed).map(|_| entity_factory::spawn_named(&mut store, "Log", (1, 1)).unwrap()).collect();
+        (&mut store.borrow::<ViewMut<Inventory>>().unwrap()).get(site).unwrap().items.extend(logs);
+    });
+
+    sim.step_n(20);
+
+    let world = &sim.world_sim.world;
+    assert!(world.borrow::<View<ConstructionSite>>().unwrap().get(site).is_err());
+
+    let vhouse = world.borrow::<View<PlankHouse>>().unwrap();
+    let vpos = world.borrow::<View<Position>>().unwrap();
+    assert!((&vhouse, &vpos).iter().any(|(_, pos)| pos.ps[0].x == xy.0 && pos.ps[0].y == xy.1));
+
+    let map = sim.get_map();
+    assert_eq!(map.tiles[map.xy_idx(xy)], TileType::WoodWall);
+    assert_eq!(map.tiles[map.xy_idx((xy.0 + 1, xy.1 + 1))], TileType::WoodFloor);
+    assert_eq!(map.tiles[map.xy_idx((xy.0 + 1, xy.1 + 3))], TileType::WoodDoor);
+
+    Ok(())
+}
+
+#[test]
+fn sites_need_room() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    assert!(sim.world_sim.designate_construction((-5, -5), HOUSE).is_none());
+
+    let xy = clear_spot(&sim);
+    assert!(sim.world_sim.designate_construction(xy, HOUSE).is_some());
+
+    // the site takes up its footprint once it's been indexed
+    sim.step();
+    assert!(sim.world_sim.designate_construction(xy, HOUSE).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn villagers_only_grow_into_beds() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+
+    for _ in 0..50 {
+        sim.step();
+
+        let world = &sim.world_sim.world;
+        let beds: i32 = world.borrow::<View<PlankHouse>>().unwrap().iter().map(|h| h.housing_cap).sum();
+        assert!(world.borrow::<View<Needs>>().unwrap().iter().count() as i32 <= beds);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use engine::{simulation::Simulation, world::save};
+
+fn snapshot(sim: &Simulation) -> String {
+    let save = save::save_world(&sim.world_sim.world, &sim.world_sim.settings);
+    serde_json::to_string(&save).unwrap()
+}
+
+#[test]
+fn same_seed_same_world() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim1 = Simulation::with_seed("village_sim", 1234)?;
+    let mut sim2 = Simulation::with_seed("village_sim", 1234)?;
+
+    assert_eq!(snapshot(&sim1), snapshot(&sim2));
+
+    for _ in 0..100 {
+        sim1.step();
+        sim2.step();
+    }
+
+    assert_eq!(snapshot(&sim1), snapshot(&sim2));
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use common::{player, spawn};
+use engine::{
+    simulation::Simulation,
+    tiles::TileType,
+    world::{
+        components::{DijkstraMapToMe, Energy, Inventory, Item, ItemType, Name, PhysicalStats, Position, Spellbook, WantsToUseItem},
+        effects::{self, EffectType},
+        map::Map,
+    },
+};
+use rltk::Point;
+use shipyard::{AddComponent, Get, IntoIter, UniqueViewMut, View, ViewMut};
+
+/// Tile east of the player, cleared out so tests can shape it
+fn east_of_player(sim: &Simulation) -> usize {
+    let p = sim.world_sim.get_player_pos().0;
+    let mut map = sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap();
+    let idx = map.xy_idx((p.x + 1, p.y));
+    map.change_tile(idx, TileType::Floor);
+    idx
+}
+
+fn items_at(sim: &Simulation, idx: usize, typ: ItemType) -> usize {
+    let map = sim.get_map();
+    let vitem = sim.world_sim.world.borrow::<View<Item>>().unwrap();
+    let vpos = sim.world_sim.world.borrow::<View<Position>>().unwrap();
+    (&vitem, &vpos).iter().filter(|(item, pos)| item.typ == typ && map.point_idx(pos.ps[0]) == idx).count()
+}
+
+fn dig(sim: &mut Simulation, tile_idx: usize) {
+    sim.world_sim.add_effect(Some(player(sim)), EffectType::Dig { tile_idx });
+    sim.step();
+}
+
+#[test]
+fn digging_ground_leaves_a_trench_and_dirt() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 1)?;
+    let idx = east_of_player(&sim);
+    let (_, turns, _) = TileType::Floor.dig().unwrap();
+
+    for _ in 0..turns - 1 {
+        dig(&mut sim, idx);
+    }
+    assert_eq!(sim.get_map().tiles[idx], TileType::Floor);
+
+    dig(&mut sim, idx);
+    assert_eq!(sim.get_map().tiles[idx], TileType::Trench);
+    assert!(sim.get_map().dig_progress.is_empty());
+    assert_eq!(items_at(&sim, idx, ItemType::Dirt), 1);
+
+    Ok(())
+}
+
+#[test]
+fn digging_through_walls_opens_a_path() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 1)?;
+    let idx = east_of_player(&sim);
+    sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap().change_tile(idx, TileType::Wall);
+    sim.step();
+
+    let distance = |sim: &Simulation| sim.world_sim.world.borrow::<View<DijkstraMapToMe>>().unwrap().get(player(sim)).unwrap().map.map[idx];
+    assert!(sim.get_map().blocked[idx]);
+    assert_eq!(distance(&sim), f32::MAX);
+
+    let (floor, turns, _) = TileType::Wall.dig().unwrap();
+    for _ in 0..turns {
+        dig(&mut sim, idx);
+    }
+
+    assert_eq!(sim.get_map().tiles[idx], floor);
+    assert!(!sim.get_map().blocked[idx]);
+    assert!(distance(&sim) < 2.0);
+    assert_eq!(items_at(&sim, idx, ItemType::Rubble), 1);
+
+    Ok(())
+}
+
+#[test]
+fn terrain_changes_update_blocked_tiles() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("roguelike", 1)?;
+    let idx = east_of_player(&sim);
+    let mut map = sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap();
+    map.paths_dirty = false;
+
+    map.change_tile(idx, TileType::Wall);
+    assert!(map.blocked[idx]);
+    assert!(map.paths_dirty);
+
+    map.change_tile(idx, TileType::Trench);
+    assert!(!map.blocked[idx]);
+
+    Ok(())
+}
+
+#[test]
+fn filling_a_trench_uses_up_dirt() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 1)?;
+    let idx = east_of_player(&sim);
+    sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap().change_tile(idx, TileType::Trench);
+
+    sim.world_sim.add_effect(Some(player(&sim)), EffectType::Fill { tile_idx: idx });
+    sim.step();
+    assert_eq!(sim.get_map().tiles[idx], TileType::Trench);
+    assert!(sim.get_log().messages.iter().any(|m| m.contains("need some dirt")));
+
+    let p = sim.world_sim.get_player_pos().0;
+    let dirt = spawn(&sim, "Dirt", (p.x, p.y));
+    sim.world_sim.add_effect(Some(player(&sim)), EffectType::PickUp { entity: dirt });
+    sim.step();
+
+    sim.world_sim.add_effect(Some(player(&sim)), EffectType::Fill { tile_idx: idx });
+    sim.step();
+    assert_eq!(sim.get_map().tiles[idx], TileType::Dirt);
+    assert!(!sim.world_sim.world.borrow::<View<Inventory>>().unwrap().get(player(&sim)).unwrap().items.contains(&dirt));
+
+    Ok(())
+}
+
+#[test]
+fn trenches_slow_movement() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("roguelike", 1)?;
+    let idx = east_of_player(&sim);
+    sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap().change_tile(idx, TileType::Trench);
+
+    let energy = |sim: &Simulation| sim.world_sim.world.borrow::<View<Energy>>().unwrap().get(player(sim)).unwrap().value;
+    let before = energy(&sim);
+
+    sim.world_sim.add_effect(Some(player(&sim)), EffectType::Move { tile_idx: idx });
+    sim.world_sim.world.run(effects::run_effects_queue);
+
+    assert_eq!(sim.get_map().point_idx(sim.world_sim.get_player_pos().0), idx);
+    assert!(energy(&sim) < before);
+
+    Ok(())
+}
+
+#[test]
+fn trenches_give_cover() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 1)?;
+    let idx = east_of_player(&sim);
+    sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap().change_tile(idx, TileType::Trench);
+
+    let p = sim.world_sim.get_player_pos().0;
+    let orc = spawn(&sim, "Orc", (p.x + 1, p.y));
+
+    let missile = {
+        let book = sim.world_sim.world.borrow::<View<Spellbook>>().unwrap();
+        let vname = sim.world_sim.world.borrow::<View<Name>>().unwrap();
+        book.get(player(&sim)).unwrap().spells.iter().copied().find(|s| vname.get(*s).unwrap().name == "Magic Missile").unwrap()
+    };
+    sim.world_sim.world.borrow::<ViewMut<WantsToUseItem>>().unwrap().add_component_unchecked(player(&sim), WantsToUseItem { item: missile, target: Some(Point::new(p.x + 1, p.y)) });
+    sim.step();
+
+    // a missile kills an orc in the open, half of it gets through to one in a trench
+    let stats = *sim.world_sim.world.borrow::<View<PhysicalStats>>().unwrap().get(orc).unwrap();
+    assert!(stats.hp > 0 && stats.hp < stats.max_hp);
+
+    Ok(())
+}
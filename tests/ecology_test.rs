@@ -0,0 +1,88 @@
+use engine::{
+    entity_factory,
+    simulation::Simulation,
+    tiles::TileType,
+    world::{
+        components::{Position, Sapling, Tree},
+        map::{Map, XY},
+        systems::system_ecology::{GRASS_REGROW, SAPLING_MATURE},
+    },
+};
+use shipyard::{AllStoragesViewMut, Get, IntoIter, IntoWithId, UniqueViewMut, View, ViewMut};
+
+// an empty grass tile in the middle of a meadow
+fn meadow(sim: &Simulation) -> XY {
+    let map = sim.get_map();
+    for y in 1..map.size.1 - 1 {
+        for x in 1..map.size.0 - 1 {
+            let all_grass = (-1..=1).all(|dx| (-1..=1).all(|dy| map.get_tile((x + dx, y + dy)) == TileType::Grass));
+            if all_grass && map.tile_content[map.xy_idx((x, y))].is_empty() {
+                return (x, y);
+            }
+        }
+    }
+    panic!("no meadow");
+}
+
+#[test]
+fn burned_ground_grows_back() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let xy = meadow(&sim);
+    let idx = sim.get_map().xy_idx(xy);
+
+    {
+        let mut map = sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap();
+        map.tiles[idx] = TileType::Dirt;
+        map.vegetation[idx] = 0;
+    }
+
+    sim.step();
+    assert_eq!(sim.get_map().tiles[idx], TileType::Dirt);
+    assert!(sim.get_map().vegetation[idx] > 0);
+
+    // surrounded by grass it should take a few turns at most
+    sim.step_n(GRASS_REGROW as usize / 8 + 1);
+    assert_eq!(sim.get_map().tiles[idx], TileType::Grass);
+
+    Ok(())
+}
+
+#[test]
+fn saplings_grow_into_trees() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let xy = meadow(&sim);
+
+    let sapling = sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        let e = entity_factory::spawn_named(&mut store, "Sapling", xy).unwrap();
+        (&mut store.borrow::<ViewMut<Sapling>>().unwrap()).get(e).unwrap().age = SAPLING_MATURE - 1;
+        e
+    });
+
+    sim.step();
+
+    let world = &sim.world_sim.world;
+    assert!(world.borrow::<View<Sapling>>().unwrap().get(sapling).is_err());
+
+    let vtree = world.borrow::<View<Tree>>().unwrap();
+    let vpos = world.borrow::<View<Position>>().unwrap();
+    assert!((&vtree, &vpos).iter().any(|(_, pos)| pos.ps[0].x == xy.0 && pos.ps[0].y == xy.1));
+
+    Ok(())
+}
+
+#[test]
+fn forests_spread() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+
+    // the setup pass is a turn start too, so trees may already have dropped a few
+    let planted: Vec<_> = sim.world_sim.world.borrow::<View<Sapling>>().unwrap().iter().with_id().map(|(id, _)| id).collect();
+    for id in planted {
+        sim.world_sim.world.delete_entity(id);
+    }
+    assert_eq!(sim.count::<Sapling>(), 0);
+
+    sim.step_n(5);
+    assert!(sim.count::<Sapling>() > 0);
+
+    Ok(())
+}
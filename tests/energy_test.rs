@@ -0,0 +1,75 @@
+use engine::{
+    entity_factory,
+    simulation::Simulation,
+    world::{
+        components::{Energy, Locomotive, Tick},
+        systems::system_energy::{ACTION_COST, TICKS_PER_TURN},
+    },
+};
+use shipyard::{AllStoragesViewMut, Get, UniqueView, View, ViewMut};
+
+fn tick(sim: &Simulation) -> i32 {
+    sim.world_sim.world.borrow::<UniqueView<Tick>>().unwrap().0
+}
+
+#[test]
+fn player_speed_sets_time_between_turns() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 3)?;
+    let player_id = sim.world_sim.get_player_id().0;
+
+    // the player starts out able to act, so no time passes
+    let start = tick(&sim);
+    sim.world_sim.run_until_player_can_act();
+    assert_eq!(tick(&sim), start);
+
+    sim.world_sim.spend_energy(player_id, ACTION_COST);
+    let start = tick(&sim);
+    sim.world_sim.run_until_player_can_act();
+    assert_eq!(tick(&sim) - start, TICKS_PER_TURN);
+
+    // hasted
+    {
+        let mut vloco = sim.world_sim.world.borrow::<ViewMut<Locomotive>>().unwrap();
+        (&mut vloco).get(player_id).unwrap().speed *= 2;
+    }
+    sim.world_sim.spend_energy(player_id, ACTION_COST);
+    let start = tick(&sim);
+    sim.world_sim.run_until_player_can_act();
+    assert_eq!(tick(&sim) - start, TICKS_PER_TURN / 2);
+
+    Ok(())
+}
+
+#[test]
+fn wolves_outpace_orcs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 3)?;
+
+    let (wolf, orc) = sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        (
+            entity_factory::spawn_named(&mut store, "Wolf", (1, 1)).unwrap(),
+            entity_factory::spawn_named(&mut store, "Orc", (1, 2)).unwrap(),
+        )
+    });
+
+    // not enough for either to act yet
+    sim.world_sim.run_systems();
+    sim.world_sim.run_systems();
+
+    let venergy = sim.world_sim.world.borrow::<View<Energy>>().unwrap();
+    assert!(venergy.get(wolf).unwrap().value > venergy.get(orc).unwrap().value);
+
+    Ok(())
+}
+
+#[test]
+fn steps_are_whole_turns() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 3)?;
+    let (start_turn, start_tick) = (sim.turn(), tick(&sim));
+
+    sim.step_n(10);
+
+    assert_eq!(sim.turn() - start_turn, 10);
+    assert_eq!(tick(&sim) - start_tick, 10 * TICKS_PER_TURN);
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use engine::{simulation::Simulation, world::events::GameEvent};
+
+#[test]
+fn village_publishes_events() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 3)?;
+    sim.step_n(300);
+
+    let events = sim.drain_events();
+    assert!(events.iter().any(|e| matches!(e, GameEvent::ItemPickedUp { .. })));
+
+    // draining empties the stream
+    assert!(sim.drain_events().is_empty());
+
+    Ok(())
+}
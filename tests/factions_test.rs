@@ -0,0 +1,65 @@
+mod common;
+
+use common::spawn_for;
+use engine::{
+    ai::intent::Intent,
+    raws::{
+        factions::{FactionTable, Relation},
+        FACTION_RAWS_PATH,
+    },
+    simulation::Simulation,
+    utils::Target,
+    world::components::{Actor, ActorType, Faction},
+};
+use shipyard::{Get, IntoIter, View};
+
+#[test]
+fn relations_from_raws() -> Result<(), Box<dyn std::error::Error>> {
+    let mut table = FactionTable::load(FACTION_RAWS_PATH)?;
+
+    assert_eq!(table.relation(Faction::Wizard1, Faction::Wizard2), Relation::Hostile);
+    assert_eq!(table.relation(Faction::Wizard2, Faction::Wizard1), Relation::Hostile);
+    assert_eq!(table.relation(Faction::Player, Faction::Orcs), Relation::Hostile);
+    assert_eq!(table.relation(Faction::Player, Faction::Villager), Relation::Allied);
+    assert_eq!(table.relation(Faction::Orcs, Faction::Orcs), Relation::Allied);
+
+    // unlisted pairs are neutral
+    assert_eq!(table.relation(Faction::Nuetral, Faction::Wizard1), Relation::Neutral);
+
+    table.set_standing(Faction::Nuetral, Faction::Wizard1, table.hostile_at);
+    assert!(table.is_hostile(Faction::Wizard1, Faction::Nuetral));
+
+    Ok(())
+}
+
+#[test]
+fn spawned_orcs_fight_for_their_wizard() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("orc_arena", 1)?;
+    sim.step_n(25);
+
+    let vactor = sim.world_sim.world.borrow::<View<Actor>>().unwrap();
+    let orcs: Vec<Faction> = vactor.iter().filter(|a| a.atype == ActorType::Orc).map(|a| a.faction).collect();
+
+    assert!(orcs.contains(&Faction::Wizard1));
+    assert!(orcs.contains(&Faction::Wizard2));
+    assert!(!orcs.contains(&Faction::Orcs));
+
+    Ok(())
+}
+
+#[test]
+fn orcs_go_for_the_nearest_enemy() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("orc_arena", 1)?;
+
+    let orc = spawn_for(&sim, "Orc", (60, 30), Faction::Wizard1);
+    let _friend = spawn_for(&sim, "Orc", (61, 30), Faction::Wizard1);
+    let near_enemy = spawn_for(&sim, "Orc", (63, 30), Faction::Wizard2);
+    let _far_enemy = spawn_for(&sim, "Orc", (60, 34), Faction::Wizard2);
+
+    sim.step_n(2);
+
+    let vintent = sim.world_sim.world.borrow::<View<Intent>>().unwrap();
+    assert_eq!(vintent.get(orc).unwrap().target[0], Target::ENTITY(near_enemy));
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use engine::{
+    ai::action::Action,
+    raws::{behaviors::BehaviorProfiles, BEHAVIOR_RAWS_PATH},
+    simulation::Simulation,
+    tiles::{TileType, CROP_RIPE},
+    world::{
+        components::{Actor, ActorType, Inventory, Item, ItemType, PlayerID, Position},
+        map::Map,
+        systems::system_farming,
+    },
+};
+use shipyard::{AllStoragesViewMut, Get, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View, ViewMut};
+
+fn open_tile(sim: &Simulation) -> usize {
+    let map = sim.get_map();
+    (0..map.len()).find(|idx| map.tiles[*idx] == TileType::Floor && map.tile_content[*idx].is_empty()).unwrap()
+}
+
+#[test]
+fn crops_grow_and_get_harvested() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 4)?;
+    let idx = open_tile(&sim);
+
+    {
+        let mut map = sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap();
+        assert!(!system_farming::till(&mut map, idx));
+        map.tiles[idx] = TileType::Dirt;
+        assert!(system_farming::till(&mut map, idx));
+        assert!(system_farming::sow(&mut map, idx));
+        assert!(!system_farming::sow(&mut map, idx));
+    }
+
+    sim.step();
+    assert_eq!(sim.get_map().vegetation[idx], system_farming::CROP_GROWTH);
+    assert!(!system_farming::is_ripe(&sim.get_map(), idx));
+
+    // skip most of the growing season
+    sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap().vegetation[idx] = CROP_RIPE - system_farming::CROP_GROWTH;
+    sim.step();
+    assert!(system_farming::is_ripe(&sim.get_map(), idx));
+
+    let player_id = sim.world_sim.world.borrow::<UniqueView<PlayerID>>().unwrap().0;
+    let grain = sim.world_sim.world.run(|mut store: AllStoragesViewMut| system_farming::harvest(&mut store, player_id, idx));
+    let grain = grain.expect("ripe wheat should give grain");
+
+    sim.step();
+
+    let world = &sim.world_sim.world;
+    assert!(world.borrow::<View<Inventory>>().unwrap().get(player_id).unwrap().items.contains(&grain));
+    assert_eq!(world.borrow::<View<Item>>().unwrap().get(grain).unwrap().typ, ItemType::Grain);
+    assert_eq!(sim.get_map().tiles[idx], TileType::Farmland);
+
+    Ok(())
+}
+
+#[test]
+fn fire_burns_crops() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 4)?;
+    let idx = open_tile(&sim);
+
+    {
+        let mut map = sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap();
+        map.tiles[idx] = TileType::Wheat;
+        map.vegetation[idx] = CROP_RIPE;
+        map.fire_turns[idx] = 1;
+    }
+
+    sim.step();
+
+    let map = sim.get_map();
+    assert_eq!(map.tiles[idx], TileType::Dirt);
+    assert_eq!(map.vegetation[idx], 0);
+
+    Ok(())
+}
+
+#[test]
+fn villagers_till_new_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 11)?;
+    let profiles = BehaviorProfiles::load(BEHAVIOR_RAWS_PATH)?;
+    let till: Vec<Action> = profiles.get("gather_grain").unwrap().iter().filter(|a| a.intent.name == "till field").cloned().collect();
+    assert_eq!(till.len(), 1);
+
+    // a villager with nothing to do but till, standing next to a young crop
+    let p = {
+        let mut vactor = sim.world_sim.world.borrow::<ViewMut<Actor>>().unwrap();
+        let vpos = sim.world_sim.world.borrow::<View<Position>>().unwrap();
+        let (_, (actor, pos)) = (&mut vactor, &vpos).iter().with_id().find(|(_, (a, _))| a.atype == ActorType::Villager).unwrap();
+        actor.actions = till;
+        pos.ps[0]
+    };
+
+    let around: Vec<usize> = {
+        let mut map = sim.world_sim.world.borrow::<UniqueViewMut<Map>>().unwrap();
+        let around: Vec<usize> = (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (p.x + dx, p.y + dy))).map(|xy| map.xy_idx(xy)).collect();
+        for idx in around.iter() {
+            map.change_tile(*idx, TileType::Grass);
+        }
+
+        let crop = map.xy_idx((p.x + 1, p.y));
+        map.change_tile(crop, TileType::Wheat);
+        map.vegetation[crop] = 0;
+        around
+    };
+
+    sim.step_n(3);
+
+    let map = sim.get_map();
+    let fields = around.iter().filter(|idx| map.tiles[**idx] == TileType::Farmland || map.tiles[**idx] == TileType::Wheat).count();
+    assert!(fields > 1);
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::remove_actors;
+use engine::{
+    ai::intent::{Intent, Task},
+    entity_factory,
+    simulation::Simulation,
+    tiles::TileType,
+    world::{
+        components::{Actor, ActorType, Energy, Position, Turn},
+        map::XY,
+        systems::system_ai_fish::{self, SCARE_RANGE, WATER_PER_FISH},
+    },
+};
+use rltk::{DistanceAlg, Point};
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, UniqueView, View, ViewMut};
+
+fn fish(sim: &Simulation) -> Vec<(EntityId, Point)> {
+    let world = &sim.world_sim.world;
+    let vactor = world.borrow::<View<Actor>>().unwrap();
+    let vpos = world.borrow::<View<Position>>().unwrap();
+
+    (&vactor, &vpos).iter().with_id().filter(|(_, (actor, _))| actor.atype == ActorType::Fish).map(|(id, (_, pos))| (id, pos.ps[0])).collect()
+}
+
+fn river(sim: &Simulation) -> XY {
+    let map = sim.get_map();
+    (map.size.0 / 2, map.size.1 - 6)
+}
+
+#[test]
+fn stock_grows_up_to_capacity() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    remove_actors(&mut sim, ActorType::Villager);
+
+    let capacity = {
+        let map = sim.get_map();
+        system_ai_fish::water_region(&map, map.xy_idx(river(&sim))).len() / WATER_PER_FISH as usize
+    };
+    let start = fish(&sim).len();
+    assert!(start > 0);
+
+    sim.step_n(30);
+
+    let end = fish(&sim).len();
+    assert!(end > start);
+    assert!(end <= capacity);
+
+    Ok(())
+}
+
+#[test]
+fn empty_water_stays_empty() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    remove_actors(&mut sim, ActorType::Fish);
+
+    sim.step_n(20);
+    assert!(fish(&sim).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn fish_swim_anywhere_in_water() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let edge = {
+        let map = sim.get_map();
+        (map.size.0 - 2, map.size.1 - 6)
+    };
+
+    let e = sim.world_sim.world.run(|mut store: AllStoragesViewMut| entity_factory::spawn_named(&mut store, "Fish", edge)).unwrap();
+
+    for _ in 0..10 {
+        sim.step();
+
+        let map = sim.get_map();
+        for (_, p) in fish(&sim) {
+            assert_eq!(map.tiles[map.point_idx(p)], TileType::Water);
+        }
+    }
+
+    // used to swim off the east edge
+    assert!(fish(&sim).iter().any(|(id, _)| *id == e));
+
+    Ok(())
+}
+
+#[test]
+fn fish_avoid_fishers() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    remove_actors(&mut sim, ActorType::Villager);
+
+    let (x, y) = river(&sim);
+    let bank = (x, sim.get_map().size.1 - 10);
+
+    let fisher = sim.world_sim.world.run(|mut store: AllStoragesViewMut| entity_factory::spawn_named(&mut store, "Villager", bank)).unwrap();
+    let turn = *sim.world_sim.world.borrow::<UniqueView<Turn>>().unwrap();
+    sim.world_sim.world.add_component(fisher, Intent { name: "fish".to_string(), owner: fisher, task: Task::Fish, target: vec![], turn });
+
+    // keep the fisher from deciding to do something else
+    (&mut sim.world_sim.world.borrow::<ViewMut<Energy>>().unwrap()).get(fisher).unwrap().value = -100000;
+
+    let e = sim.world_sim.world.run(|mut store: AllStoragesViewMut| entity_factory::spawn_named(&mut store, "Fish", (x, y))).unwrap();
+
+    for _ in 0..10 {
+        sim.step();
+
+        if let Some((_, p)) = fish(&sim).iter().find(|(id, _)| *id == e) {
+            assert!(DistanceAlg::Pythagoras.distance2d(*p, Point::new(bank.0, bank.1)) > SCARE_RANGE);
+        }
+    }
+
+    Ok(())
+}
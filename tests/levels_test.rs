@@ -0,0 +1,53 @@
+use engine::{
+    simulation::Simulation,
+    tiles::TileType,
+    world::{components::Name, levels::Levels, map::Map},
+    world_sim::WorldSim,
+};
+use shipyard::{IntoIter, UniqueView, View};
+
+fn names(sim: &Simulation) -> Vec<String> {
+    let vname = sim.world_sim.world.borrow::<View<Name>>().unwrap();
+    let mut names: Vec<String> = vname.iter().map(|n| n.name.clone()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn levels_are_kept_when_leaving() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 21)?;
+
+    let tiles = sim.world_sim.get_map().tiles.clone();
+    let level_one = names(&sim);
+
+    sim.world_sim.change_depth(2);
+    {
+        let map = sim.world_sim.get_map();
+        assert_eq!(map.depth, 2);
+
+        // arriving by stairs down leaves a way back up
+        let ppos = sim.world_sim.get_player_pos().0;
+        assert_eq!(map.tiles[map.point_idx(ppos)], TileType::StairsUp);
+    }
+    sim.step_n(5);
+    let level_two = names(&sim);
+
+    sim.world_sim.change_depth(1);
+    assert_eq!(sim.world_sim.get_map().depth, 1);
+    assert_eq!(sim.world_sim.get_map().tiles, tiles);
+    assert_eq!(names(&sim), level_one);
+
+    // level two is stored now, and survives a save
+    let path = std::env::temp_dir().join("trenchwizard_levels_test.json");
+    let path = path.to_str().unwrap();
+    sim.world_sim.save(path)?;
+    let mut loaded = Simulation { world_sim: WorldSim::load(path)? };
+    std::fs::remove_file(path)?;
+    assert!(loaded.world_sim.world.borrow::<UniqueView<Levels>>().unwrap().stored.contains_key(&2));
+
+    loaded.world_sim.change_depth(2);
+    assert_eq!(loaded.world_sim.world.borrow::<UniqueView<Map>>().unwrap().depth, 2);
+    assert_eq!(names(&loaded), level_two);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use engine::{
+    config::{get_config, get_mode_list, Keymap},
+    simulation::Simulation,
+    world::components::{Inventory, PlayerID},
+};
+use shipyard::{Get, UniqueView, View};
+
+#[test]
+fn modes_come_from_config() -> Result<(), Box<dyn std::error::Error>> {
+    let modes = get_mode_list()?;
+    let ids: Vec<&str> = modes.iter().map(|(id, _)| id.as_str()).collect();
+
+    assert!(ids.contains(&"roguelike"));
+    assert!(ids.contains(&"village_sim"));
+    assert!(!ids.contains(&"orc_halls")); // hidden
+
+    let settings = get_config("roguelike")?;
+    assert_eq!(settings.map_builder, "drunkards");
+    assert_eq!(settings.keymap, Keymap::Roguelike);
+
+    assert!(get_config("not_a_mode").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn every_mode_builds() -> Result<(), Box<dyn std::error::Error>> {
+    for (id, _) in get_mode_list()? {
+        let mut sim = Simulation::with_seed(&id, 5)?;
+        sim.step();
+
+        // starting inventory comes from the config too
+        let settings = sim.world_sim.settings.clone();
+        let player_id = sim.world_sim.world.borrow::<UniqueView<PlayerID>>().unwrap().0;
+        let vinv = sim.world_sim.world.borrow::<View<Inventory>>().unwrap();
+        assert_eq!(vinv.get(player_id).unwrap().items.len(), settings.starting_inventory.len());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use engine::{
+    entity_factory,
+    simulation::Simulation,
+    world::{
+        components::{Inventory, Needs, PhysicalStats, PlankHouse, StatusEffects, StatusKind, MAX_NEED},
+        systems::system_needs::{self, FISH_NOURISHMENT, STARVATION_DAMAGE},
+    },
+};
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, IntoWithId, View, ViewMut};
+
+fn spawn_villager(sim: &Simulation, needs: Needs) -> EntityId {
+    sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        let e = entity_factory::spawn_named(&mut store, "Villager", (1, 1)).unwrap();
+        *(&mut store.borrow::<ViewMut<Needs>>().unwrap()).get(e).unwrap() = needs;
+        e
+    })
+}
+
+#[test]
+fn unmet_needs_hurt() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let villager = spawn_villager(&sim, Needs { hunger: MAX_NEED, fatigue: MAX_NEED, shelter: 0 });
+    let hp = sim.world_sim.world.borrow::<View<PhysicalStats>>().unwrap().get(villager).unwrap().hp;
+
+    sim.step();
+
+    let world = &sim.world_sim.world;
+    assert!(world.borrow::<View<PhysicalStats>>().unwrap().get(villager).unwrap().hp <= hp - STARVATION_DAMAGE);
+    assert!(world.borrow::<View<StatusEffects>>().unwrap().get(villager).unwrap().has(StatusKind::Sick));
+    assert_eq!(world.borrow::<View<Needs>>().unwrap().get(villager).unwrap().shelter, 1);
+
+    Ok(())
+}
+
+#[test]
+fn eating_and_sleeping() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("village_sim", 2)?;
+    let villager = spawn_villager(&sim, Needs { hunger: 80, fatigue: 80, shelter: 80 });
+
+    sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        let fish = entity_factory::spawn_named(&mut store, "Fish", (2, 2)).unwrap();
+        (&mut store.borrow::<ViewMut<Inventory>>().unwrap()).get(villager).unwrap().items.push(fish);
+
+        system_needs::eat(&store, villager);
+        system_needs::sleep(&store, villager);
+    });
+
+    let world = &sim.world_sim.world;
+    let needs = *world.borrow::<View<Needs>>().unwrap().get(villager).unwrap();
+    assert_eq!(needs.hunger, 80 - FISH_NOURISHMENT);
+    assert!(needs.fatigue < 80);
+    assert_eq!(needs.shelter, 0);
+    assert!(world.borrow::<View<Inventory>>().unwrap().get(villager).unwrap().items.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn villagers_get_beds() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let villagers: Vec<EntityId> = sim.world_sim.world.borrow::<View<Needs>>().unwrap().iter().with_id().map(|(id, _)| id).collect();
+    assert!(!villagers.is_empty());
+
+    sim.step();
+
+    let vhouse = sim.world_sim.world.borrow::<View<PlankHouse>>().unwrap();
+    let beds: Vec<EntityId> = vhouse.iter().flat_map(|h| h.villagers.iter().copied()).collect();
+    for id in villagers {
+        assert_eq!(beds.iter().filter(|v| **v == id).count(), 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use std::thread;
+
+use engine::{simulation::Simulation, world::save};
+
+fn run(seed: u64) -> String {
+    // village_sim takes minutes per run in a debug build
+    let mut sim = Simulation::with_seed("roguelike", seed).unwrap();
+    sim.step_n(50);
+
+    let save = save::save_world(&sim.world_sim.world, &sim.world_sim.settings);
+    serde_json::to_string(&save).unwrap()
+}
+
+#[test]
+fn parallel_sims_keep_their_own_effects() {
+    // each world owns its effect queue, so running side by side can't change the outcome
+    let handles: Vec<_> = (0..4).map(|_| thread::spawn(|| run(99))).collect();
+    let results: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    for r in results.iter() {
+        assert_eq!(r, &results[0]);
+    }
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{actors, spawn};
+use engine::{
+    ai::{
+        input::InputTargets,
+        intent::{Intent, Task},
+    },
+    simulation::Simulation,
+    world::{
+        components::{Actor, ActorType, ChiefHouse, Faction, ItemType, Position, Raider, Stockpile},
+        systems::system_raids::{self, MAX_RAIDERS, RAID_BASE},
+    },
+};
+use rltk::DistanceAlg;
+use shipyard::{AllStoragesViewMut, EntityId, Get, IntoIter, View};
+
+fn raid(sim: &Simulation) -> Vec<EntityId> {
+    sim.world_sim.world.run(|mut store: AllStoragesViewMut| system_raids::launch_raid(&mut store))
+}
+
+fn distance_to_chief(sim: &Simulation, id: EntityId) -> f32 {
+    let vpos = sim.world_sim.world.borrow::<View<Position>>().unwrap();
+    let vchief = sim.world_sim.world.borrow::<View<ChiefHouse>>().unwrap();
+    let (_, chief) = (&vchief, &vpos).iter().next().unwrap();
+    let me = vpos.get(id).unwrap().ps[0];
+    chief.ps.iter().map(|p| DistanceAlg::Pythagoras.distance2d(me, *p)).fold(f32::MAX, f32::min)
+}
+
+#[test]
+fn raids_grow_with_wealth() {
+    let mut stockpile = Stockpile::default();
+    assert_eq!(system_raids::village_wealth(&stockpile, 0), 0);
+    assert_eq!(system_raids::raid_size(0), RAID_BASE);
+
+    stockpile.counts.insert(ItemType::Log, 100);
+    stockpile.counts.insert(ItemType::Grain, 50);
+    let rich = system_raids::village_wealth(&stockpile, 5);
+    assert_eq!(rich, 200);
+    assert!(system_raids::raid_size(rich) > RAID_BASE);
+
+    assert_eq!(system_raids::raid_size(100000), MAX_RAIDERS);
+}
+
+#[test]
+fn raiders_come_from_the_edge() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("village_sim", 2)?;
+    let (w, h) = sim.get_map().size;
+
+    let raiders = raid(&sim);
+    assert!(!raiders.is_empty());
+
+    let vpos = sim.world_sim.world.borrow::<View<Position>>().unwrap();
+    let vraider = sim.world_sim.world.borrow::<View<Raider>>().unwrap();
+    let vactor = sim.world_sim.world.borrow::<View<Actor>>().unwrap();
+    for r in raiders.iter() {
+        assert!(vraider.get(*r).is_ok());
+        assert_eq!(vactor.get(*r).unwrap().faction, Faction::Orcs);
+
+        let p = vpos.get(*r).unwrap().ps[0];
+        assert!(p.x <= 4 || p.x >= w - 5 || p.y <= 4 || p.y >= h - 5);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn no_raid_without_a_village() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("roguelike", 2)?;
+    assert!(raid(&sim).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn raiders_march_on_the_chief_house() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let raiders = raid(&sim);
+    let start: Vec<f32> = raiders.iter().map(|r| distance_to_chief(&sim, *r)).collect();
+
+    sim.step_n(20);
+
+    let vintent = sim.world_sim.world.borrow::<View<Intent>>().unwrap();
+    assert!(raiders.iter().any(|r| vintent.get(*r).is_ok_and(|i| i.name == "march on village")));
+    drop(vintent);
+
+    let closer = raiders.iter().zip(start.iter()).any(|(r, d)| distance_to_chief(&sim, *r) < *d);
+    assert!(closer);
+
+    Ok(())
+}
+
+#[test]
+fn villagers_stand_or_run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let villager = actors(&sim, ActorType::Villager)[0];
+    let me = sim.world_sim.world.borrow::<View<Position>>().unwrap().get(villager).unwrap().ps[0];
+    spawn(&sim, "Orc", (me.x + 2, me.y));
+
+    let mut responded = false;
+    for _ in 0..5 {
+        sim.step();
+
+        if let Ok(i) = sim.world_sim.world.borrow::<View<Intent>>().unwrap().get(villager) {
+            if matches!(i.task, Task::Attack(InputTargets::Enemy) | Task::MoveTo(InputTargets::Enemy) | Task::Flee(InputTargets::Enemy)) {
+                responded = true;
+                break;
+            }
+        }
+    }
+    assert!(responded);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use engine::{
+    config::{get_config, get_mode_list},
+    entity_factory,
+    raws::{RawMaster, ENTITY_RAWS_PATH},
+    simulation::Simulation,
+    world::components::{AreaOfEffect, Name, PlankHouse, Position, Ranged},
+};
+use shipyard::{AllStoragesViewMut, Get, View};
+
+#[test]
+fn spawn_tables_use_known_raws() -> Result<(), Box<dyn std::error::Error>> {
+    let raws = RawMaster::load(ENTITY_RAWS_PATH)?;
+
+    for (id, _) in get_mode_list()? {
+        let settings = get_config(&id)?;
+        for entry in settings.spawn_table.iter() {
+            assert!(raws.get(&entry.name).is_some(), "{} spawns unknown {}", id, entry.name);
+        }
+        for name in settings.starting_inventory.iter() {
+            assert!(raws.get(name).is_some(), "{} starts with unknown {}", id, name);
+        }
+        for name in settings.starting_spells.iter() {
+            assert!(raws.get(name).is_some_and(|raw| raw.spell.is_some()), "{} knows unknown spell {}", id, name);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn spawn_from_raws() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("roguelike", 1)?;
+
+    let (scroll, house, unknown) = sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        (
+            entity_factory::spawn_named(&mut store, "Fireball Scroll", (1, 1)),
+            entity_factory::spawn_named(&mut store, "Plank House", (2, 2)),
+            entity_factory::spawn_named(&mut store, "Not A Thing", (1, 1)),
+        )
+    });
+    let scroll = scroll.unwrap();
+    let house = house.unwrap();
+    assert!(unknown.is_none());
+
+    let world = &sim.world_sim.world;
+    assert_eq!(world.borrow::<View<Name>>().unwrap().get(scroll).unwrap().name, "Fireball scroll");
+    assert_eq!(world.borrow::<View<Ranged>>().unwrap().get(scroll).unwrap().range, 6);
+    assert_eq!(world.borrow::<View<AreaOfEffect>>().unwrap().get(scroll).unwrap().radius, 3);
+
+    // multi tile entities cover their whole footprint
+    assert_eq!(world.borrow::<View<Position>>().unwrap().get(house).unwrap().ps.len(), 16);
+    assert_eq!(world.borrow::<View<PlankHouse>>().unwrap().get(house).unwrap().housing_cap, 5);
+
+    Ok(())
+}
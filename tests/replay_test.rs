@@ -0,0 +1,115 @@
+use engine::{
+    config::get_config,
+    tiles::TileType,
+    ui::{input_handler::{self, InputCommand}, replay::{Recorder, Replay}},
+    world::{
+        components::{Name, PhysicalStats, Position, Tick, Turn},
+        Game, GameState,
+    },
+};
+use shipyard::{IntoIter, UniqueView, View};
+
+type WorldState = (i32, i32, Vec<TileType>, Vec<(String, i32, i32, i32)>);
+
+/// Turn, tick, terrain and where everything is. Entity ids are left out, they depend on how often the world was reset
+fn world_state(game: &Game) -> WorldState {
+    let world = &game.world_sim.world;
+    let turn = world.borrow::<UniqueView<Turn>>().unwrap().0;
+    let tick = world.borrow::<UniqueView<Tick>>().unwrap().0;
+
+    let vname = world.borrow::<View<Name>>().unwrap();
+    let vpos = world.borrow::<View<Position>>().unwrap();
+    let vstats = world.borrow::<View<PhysicalStats>>().unwrap();
+    let mut actors: Vec<(String, i32, i32, i32)> = (&vname, &vpos, &vstats).iter().map(|(name, pos, stats)| (name.name.clone(), pos.ps[0].x, pos.ps[0].y, stats.hp)).collect();
+    actors.sort();
+
+    (turn, tick, game.world_sim.get_map().tiles.clone(), actors)
+}
+
+// run frames until the world is waiting on the player again
+fn settle(game: &mut Game) {
+    for _ in 0..10 {
+        if game.state == GameState::PreTurn {
+            return;
+        }
+        game.update();
+    }
+}
+
+#[test]
+fn record_and_load_replay() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join("trenchwizard_replay_test.jsonl");
+    let path = path.to_str().unwrap();
+
+    let settings = get_config("roguelike")?.with_seed(Some(42));
+    {
+        let mut recorder = Recorder::create(path)?;
+        recorder.start(&settings);
+        recorder.record(3, 0, InputCommand::Move { dir: 6 });
+        recorder.record(3, 1, InputCommand::ShowInventory);
+        recorder.record(10, 1, InputCommand::Drop);
+    }
+
+    let mut replay = Replay::load(path)?;
+    std::fs::remove_file(path)?;
+
+    assert_eq!(replay.mode(), Some("roguelike".to_string()));
+    assert_eq!(replay.next_start(), Some((42, "roguelike".to_string())));
+
+    // commands only come out once their tick is reached
+    assert_eq!(replay.next_command(2), None);
+    assert_eq!(replay.next_command(3), Some((0, InputCommand::Move { dir: 6 })));
+    assert_eq!(replay.next_command(3), Some((1, InputCommand::ShowInventory)));
+    assert_eq!(replay.next_command(3), None);
+    assert_eq!(replay.next_command(10), Some((1, InputCommand::Drop)));
+    assert!(replay.is_finished());
+
+    Ok(())
+}
+
+#[test]
+fn replaying_commands_rebuilds_the_world() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join("trenchwizard_replay_world_test.jsonl");
+    let path = path.to_str().unwrap();
+    let commands = [
+        InputCommand::Move { dir: 6 },
+        InputCommand::Move { dir: 2 },
+        InputCommand::Wait,
+        InputCommand::ShowInventory,
+        InputCommand::Escape,
+        InputCommand::Move { dir: 4 },
+        InputCommand::Move { dir: 8 },
+        InputCommand::Wait,
+    ];
+
+    let mut game = Game::new("roguelike", Some(7));
+    game.start_recording(path)?;
+    game.set_state(GameState::PreTurn);
+    for command in commands.iter() {
+        let state = input_handler::run_command(*command, &mut game);
+        if state != GameState::None {
+            game.set_state(state);
+        }
+        settle(&mut game);
+    }
+    game.recorder = None;
+
+    // a different seed, the replay brings its own
+    let mut replayed = Game::new("roguelike", Some(1));
+    replayed.start_replay(path)?;
+    replayed.set_state(GameState::PreTurn);
+    for _ in 0..100 {
+        if replayed.replay.is_none() {
+            break;
+        }
+        replayed.replay_input();
+        settle(&mut replayed);
+    }
+    std::fs::remove_file(path)?;
+
+    assert!(replayed.replay.is_none());
+    assert!(world_state(&game).1 > 0);
+    assert_eq!(world_state(&replayed), world_state(&game));
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use engine::{simulation::Simulation, world::components::{Actor, Inventory, Position}, world_sim::WorldSim};
+
+#[test]
+fn save_and_load_village_sim() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::new("village_sim")?;
+    sim.step_n(50);
+
+    let path = std::env::temp_dir().join("trenchwizard_save_test.json");
+    let path = path.to_str().unwrap();
+    sim.world_sim.save(path)?;
+
+    let mut loaded = Simulation { world_sim: WorldSim::load(path)? };
+    std::fs::remove_file(path)?;
+
+    assert_eq!(loaded.turn(), sim.turn());
+    assert_eq!(loaded.count::<Actor>(), sim.count::<Actor>());
+    assert_eq!(loaded.count::<Position>(), sim.count::<Position>());
+    assert_eq!(loaded.count::<Inventory>(), sim.count::<Inventory>());
+
+    // the loaded world should keep running
+    loaded.step_n(50);
+
+    Ok(())
+}
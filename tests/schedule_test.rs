@@ -0,0 +1,22 @@
+use engine::{simulation::Simulation};
+
+#[test]
+fn disabled_systems_dont_run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 7)?;
+    sim.world_sim.schedule.set_enabled("gas", false);
+
+    // timings only change when a system runs
+    let before: Vec<f32> = sim.world_sim.schedule.systems.iter().map(|s| s.avg_ms).collect();
+    sim.step_n(20);
+
+    for (system, before) in sim.world_sim.schedule.systems.iter().zip(before) {
+        if system.name == "gas" {
+            assert!(!system.enabled);
+            assert_eq!(system.avg_ms, before);
+        } else {
+            assert!(system.enabled);
+        }
+    }
+
+    Ok(())
+}
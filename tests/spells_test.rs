@@ -0,0 +1,124 @@
+mod common;
+
+use common::{mana, player};
+use engine::{
+    config::get_config,
+    simulation::Simulation,
+    world::components::{Mana, Name, Position, Spell, Spellbook, WantsToUseItem},
+    world_sim::WorldSim,
+};
+use rltk::Point;
+use shipyard::{AddComponent, EntityId, Get, View, ViewMut};
+
+fn known_spell(sim: &Simulation, name: &str) -> EntityId {
+    let book = sim.world_sim.world.borrow::<View<Spellbook>>().unwrap();
+    let vname = sim.world_sim.world.borrow::<View<Name>>().unwrap();
+    book.get(player(sim)).unwrap().spells.iter().copied().find(|s| vname.get(*s).unwrap().name == name).unwrap()
+}
+
+fn spell(sim: &Simulation, id: EntityId) -> Spell {
+    *sim.world_sim.world.borrow::<View<Spell>>().unwrap().get(id).unwrap()
+}
+
+fn set_mana(sim: &Simulation, current: i32) {
+    (&mut sim.world_sim.world.borrow::<ViewMut<Mana>>().unwrap()).get(player(sim)).unwrap().current = current;
+}
+
+/// Casts at an empty spot next to the player
+fn cast(sim: &Simulation, spell: EntityId) {
+    let p = sim.world_sim.get_player_pos().0;
+    sim.world_sim.world.borrow::<ViewMut<WantsToUseItem>>().unwrap().add_component_unchecked(player(sim), WantsToUseItem { item: spell, target: Some(Point::new(p.x + 1, p.y)) });
+}
+
+#[test]
+fn player_starts_with_spells() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("roguelike", 1)?;
+    let settings = get_config("roguelike")?;
+
+    let book = sim.world_sim.world.borrow::<View<Spellbook>>().unwrap().get(player(&sim)).unwrap().clone();
+    assert_eq!(book.spells.len(), settings.starting_spells.len());
+
+    // spells live in the book, not on the map
+    let vpos = sim.world_sim.world.borrow::<View<Position>>().unwrap();
+    for s in book.spells.iter() {
+        assert!(vpos.get(*s).is_err());
+    }
+
+    assert_eq!(mana(&sim, player(&sim)).current, mana(&sim, player(&sim)).max);
+
+    Ok(())
+}
+
+#[test]
+fn casting_costs_mana_and_starts_cooldown() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 1)?;
+    let fireball = known_spell(&sim, "Fireball");
+    let before = mana(&sim, player(&sim)).current;
+
+    cast(&sim, fireball);
+    sim.step();
+
+    // the step ends on the next turn start, which already recharges once
+    let regen = mana(&sim, player(&sim)).regen;
+    assert_eq!(mana(&sim, player(&sim)).current, before - spell(&sim, fireball).mana_cost + regen);
+    assert_eq!(spell(&sim, fireball).cooldown_left, spell(&sim, fireball).cooldown - 1);
+
+    // still recharging, so a second cast does nothing
+    let before = mana(&sim, player(&sim)).current;
+    cast(&sim, fireball);
+    sim.step();
+    assert!(mana(&sim, player(&sim)).current >= before);
+
+    Ok(())
+}
+
+#[test]
+fn spells_fizzle_without_mana() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 1)?;
+    let fireball = known_spell(&sim, "Fireball");
+    set_mana(&sim, 0);
+
+    cast(&sim, fireball);
+    sim.step();
+
+    assert_eq!(spell(&sim, fireball).cooldown_left, 0);
+    assert!(sim.get_log().messages.iter().any(|m| m.contains("needs more mana")));
+
+    Ok(())
+}
+
+#[test]
+fn mana_and_cooldowns_recover_over_time() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 1)?;
+    let missile = known_spell(&sim, "Magic Missile");
+
+    cast(&sim, missile);
+    sim.step();
+    set_mana(&sim, 0);
+
+    sim.step_n(5);
+
+    let m = mana(&sim, player(&sim));
+    assert!(m.current > 0 && m.current <= m.max);
+    assert_eq!(spell(&sim, missile).cooldown_left, 0);
+
+    Ok(())
+}
+
+#[test]
+fn spellbooks_survive_save_and_load() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("roguelike", 1)?;
+
+    let path = std::env::temp_dir().join("trenchwizard_spells_test.json");
+    let path = path.to_str().unwrap();
+    sim.world_sim.save(path)?;
+
+    let loaded = Simulation { world_sim: WorldSim::load(path)? };
+    std::fs::remove_file(path)?;
+
+    let fireball = known_spell(&loaded, "Fireball");
+    assert_eq!(spell(&loaded, fireball).mana_cost, spell(&sim, known_spell(&sim, "Fireball")).mana_cost);
+    assert_eq!(mana(&loaded, player(&loaded)), mana(&sim, player(&sim)));
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use engine::{
+    simulation::Simulation,
+    world::{
+        components::{PhysicalStats, StatusEffect, StatusEffects, StatusKind, Tick, Vision},
+        effects::{EffectType, Targets},
+        systems::system_energy::{ACTION_COST, TICKS_PER_TURN},
+    },
+};
+use shipyard::{Get, UniqueView, View};
+
+fn status(kind: StatusKind, turns: i32, magnitude: i32) -> StatusEffect {
+    StatusEffect { kind, turns, magnitude }
+}
+
+fn inflict(sim: &Simulation, status: StatusEffect) {
+    let player_id = sim.world_sim.get_player_id().0;
+    sim.world_sim.add_effect(None, EffectType::Status { status, target: Targets::Single { target: player_id } });
+}
+
+#[test]
+fn stacking_rules() {
+    let mut statuses = StatusEffects::default();
+
+    // confusion lasts longer the more you get
+    statuses.add(status(StatusKind::Confused, 3, 1));
+    statuses.add(status(StatusKind::Confused, 2, 1));
+    assert_eq!(statuses.get(StatusKind::Confused).unwrap().turns, 5);
+
+    // poison gets stronger
+    statuses.add(status(StatusKind::Poisoned, 3, 1));
+    statuses.add(status(StatusKind::Poisoned, 5, 2));
+    assert_eq!(*statuses.get(StatusKind::Poisoned).unwrap(), status(StatusKind::Poisoned, 5, 3));
+
+    // everything else keeps the longest
+    statuses.add(status(StatusKind::Hasted, 6, 1));
+    statuses.add(status(StatusKind::Hasted, 2, 1));
+    assert_eq!(statuses.get(StatusKind::Hasted).unwrap().turns, 6);
+
+    assert_eq!(statuses.effects.len(), 3);
+}
+
+#[test]
+fn poison_hurts_every_turn_then_wears_off() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 5)?;
+    let player_id = sim.world_sim.get_player_id().0;
+    let hp = |sim: &Simulation| sim.world_sim.world.borrow::<View<PhysicalStats>>().unwrap().get(player_id).unwrap().hp;
+
+    inflict(&sim, status(StatusKind::Poisoned, 2, 3));
+    let before = hp(&sim);
+    sim.step();
+    assert!(hp(&sim) <= before - 3);
+
+    sim.step();
+    assert!(sim.world_sim.world.borrow::<View<StatusEffects>>().unwrap().get(player_id).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn haste_and_blindness() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 5)?;
+    let player_id = sim.world_sim.get_player_id().0;
+
+    inflict(&sim, status(StatusKind::Hasted, 10, 1));
+    inflict(&sim, status(StatusKind::Blind, 10, 1));
+    sim.step();
+
+    let tick = |sim: &Simulation| sim.world_sim.world.borrow::<UniqueView<Tick>>().unwrap().0;
+    sim.world_sim.spend_energy(player_id, ACTION_COST);
+    let start = tick(&sim);
+    sim.world_sim.run_until_player_can_act();
+    assert_eq!(tick(&sim) - start, TICKS_PER_TURN / 2);
+
+    let vvision = sim.world_sim.world.borrow::<View<Vision>>().unwrap();
+    let player_pos = sim.world_sim.get_player_pos().0;
+    for p in vvision.get(player_id).unwrap().visible_tiles.iter() {
+        assert!((p.x - player_pos.x).abs() <= 1 && (p.y - player_pos.y).abs() <= 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use engine::{
+    entity_factory,
+    simulation::Simulation,
+    world::{
+        components::{Inventory, ItemType, PlayerID, Stockpile},
+        systems::system_stockpile,
+    },
+};
+use shipyard::{AllStoragesViewMut, Get, UniqueView, UniqueViewMut, View, ViewMut};
+
+fn logs(sim: &Simulation) -> i32 {
+    sim.world_sim.world.borrow::<UniqueView<Stockpile>>().unwrap().count(ItemType::Log)
+}
+
+#[test]
+fn ledger_counts_and_spends_stored_items() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 4)?;
+
+    sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        let mill = entity_factory::spawn_named(&mut store, "Lumber Mill", (1, 1)).unwrap();
+        let logs: Vec<_> = (0..3).map(|_| entity_factory::spawn_named(&mut store, "Log", (1, 1)).unwrap()).collect();
+        (&mut store.borrow::<ViewMut<Inventory>>().unwrap()).get(mill).unwrap().items.extend(logs);
+
+        // whatever the player carries isn't village property
+        let player_log = entity_factory::spawn_named(&mut store, "Log", (1, 1)).unwrap();
+        let player_id = store.borrow::<UniqueView<PlayerID>>().unwrap().0;
+        (&mut store.borrow::<ViewMut<Inventory>>().unwrap()).get(player_id).unwrap().items.push(player_log);
+    });
+
+    sim.world_sim.run_systems();
+    assert_eq!(logs(&sim), 3);
+
+    let (took_two, took_too_many) = sim.world_sim.world.run(|store: AllStoragesViewMut| {
+        (system_stockpile::withdraw(&store, ItemType::Log, 2), system_stockpile::withdraw(&store, ItemType::Log, 2))
+    });
+    assert!(took_two);
+    assert!(!took_too_many);
+    assert_eq!(logs(&sim), 1);
+
+    sim.world_sim.run_systems();
+    assert_eq!(logs(&sim), 1);
+
+    Ok(())
+}
+
+#[test]
+fn stale_ledger_withdraws_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("roguelike", 4)?;
+
+    let mill = sim.world_sim.world.run(|mut store: AllStoragesViewMut| {
+        let mill = entity_factory::spawn_named(&mut store, "Lumber Mill", (1, 1)).unwrap();
+        let log = entity_factory::spawn_named(&mut store, "Log", (1, 1)).unwrap();
+        (&mut store.borrow::<ViewMut<Inventory>>().unwrap()).get(mill).unwrap().items.push(log);
+        mill
+    });
+    sim.world_sim.run_systems();
+
+    // the ledger thinks there are more logs than the mill holds
+    sim.world_sim.world.borrow::<UniqueViewMut<Stockpile>>().unwrap().counts.insert(ItemType::Log, 5);
+
+    let took = sim.world_sim.world.run(|store: AllStoragesViewMut| system_stockpile::withdraw(&store, ItemType::Log, 2));
+    assert!(!took);
+    assert_eq!(sim.world_sim.world.borrow::<View<Inventory>>().unwrap().get(mill).unwrap().items.len(), 1);
+    assert_eq!(logs(&sim), 5);
+
+    Ok(())
+}
@@ -1,14 +1,14 @@
-use engine::{config::GameMode, world::{Game, GameState}};
+use engine::{simulation::Simulation, world::components::Actor};
 
 #[test]
 fn run_village_sim() -> Result<(), Box<dyn std::error::Error>> {
-    let mut game = Game::new(GameMode::VillageSim);
-    game.set_state(GameState::PreTurn);
-    game.autorun = true;
+    let mut sim = Simulation::new("village_sim")?;
+    let start_turn = sim.turn();
 
-    for _ in 0..1000 {
-        game.update();
-    }
+    sim.step_n(300);
+
+    assert_eq!(sim.turn() - start_turn, 300);
+    assert!(sim.count::<Actor>() > 0);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use common::{intent, mana, spawn_for};
+use engine::{
+    simulation::Simulation,
+    tiles::TileType,
+    world::{
+        components::{Actor, ActorType, Energy, Faction, Inventory, Item, ItemType, PhysicalStats, Position, Spellbook, War},
+        map::XY,
+        systems::system_war::GARRISON_SIZE,
+    },
+};
+use rltk::Point;
+use shipyard::{EntityId, Get, IntoIter, IntoWithId, UniqueView, View, ViewMut};
+
+fn wizard(sim: &Simulation, faction: Faction) -> EntityId {
+    let vactor = sim.world_sim.world.borrow::<View<Actor>>().unwrap();
+    vactor.iter().with_id().find(|(_, a)| a.atype == ActorType::Wizard && a.faction == faction).map(|(id, _)| id).unwrap()
+}
+
+fn pos(sim: &Simulation, id: EntityId) -> Point {
+    sim.world_sim.world.borrow::<View<Position>>().unwrap().get(id).unwrap().ps[0]
+}
+
+fn spells(sim: &Simulation, id: EntityId) -> usize {
+    sim.world_sim.world.borrow::<View<Spellbook>>().unwrap().get(id).unwrap().spells.len()
+}
+
+/// Unoccupied trench tiles on the west side
+fn west_trench(sim: &Simulation) -> Vec<XY> {
+    let map = sim.get_map();
+    (0..map.tiles.len())
+        .filter(|idx| map.tiles[*idx] == TileType::Trench && !map.blocked[*idx])
+        .map(|idx| map.idx_xy(idx))
+        .filter(|xy| xy.0 < map.size.0 / 2)
+        .collect()
+}
+
+#[test]
+fn wizards_dig_in_on_opposite_sides() -> Result<(), Box<dyn std::error::Error>> {
+    let sim = Simulation::with_seed("trench_war", 1)?;
+    let (w, _) = sim.get_map().size;
+
+    let west = wizard(&sim, Faction::Wizard1);
+    let east = wizard(&sim, Faction::Wizard2);
+    assert!(pos(&sim, west).x < w / 2);
+    assert!(pos(&sim, east).x > w / 2);
+
+    // each starts with a few spells to throw and the mana to throw them
+    assert_eq!(spells(&sim, west), 3);
+    assert_eq!(spells(&sim, east), 3);
+    assert_eq!(mana(&sim, west).current, mana(&sim, west).max);
+
+    assert!(!west_trench(&sim).is_empty());
+    assert_eq!(sim.world_sim.world.borrow::<UniqueView<War>>().unwrap().sides.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn minions_man_the_trench() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("trench_war", 1)?;
+    let minion = spawn_for(&sim, "Orc", (5, 5), Faction::Wizard1);
+
+    // orcs are slow, a fresh one needs more than a turn to build up the energy to act
+    sim.step_n(2);
+
+    assert_eq!(intent(&sim, minion).unwrap().name, "man the trench");
+
+    Ok(())
+}
+
+#[test]
+fn full_trench_sends_minions_over_the_top() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("trench_war", 1)?;
+    for xy in west_trench(&sim).into_iter().take(GARRISON_SIZE) {
+        spawn_for(&sim, "Orc", xy, Faction::Wizard1);
+    }
+    let minion = spawn_for(&sim, "Orc", (5, 5), Faction::Wizard1);
+
+    // orcs are slow, a fresh one needs more than a turn to build up the energy to act
+    sim.step_n(2);
+
+    assert_eq!(intent(&sim, minion).unwrap().name, "go over the top");
+
+    Ok(())
+}
+
+#[test]
+fn holding_minions_dig_in() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("trench_war", 1)?;
+    let west_side = |sim: &Simulation| {
+        let map = sim.get_map();
+        (0..map.tiles.len()).filter(|idx| map.tiles[*idx] == TileType::Trench && map.idx_xy(*idx).0 < map.size.0 / 2).count()
+    };
+    let before = west_side(&sim);
+
+    sim.step_n(12);
+
+    // the starting garrison widens its trench and carries off what it dug out
+    assert!(west_side(&sim) > before);
+    let vactor = sim.world_sim.world.borrow::<View<Actor>>().unwrap();
+    let vinv = sim.world_sim.world.borrow::<View<Inventory>>().unwrap();
+    let vitem = sim.world_sim.world.borrow::<View<Item>>().unwrap();
+    assert!((&vactor, &vinv).iter().any(|(actor, inv)| actor.faction == Faction::Wizard1 && inv.count_type(&vitem, ItemType::Dirt) > 0));
+
+    Ok(())
+}
+
+#[test]
+fn wizards_cast_at_enemies_in_range() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("trench_war", 1)?;
+    let west = wizard(&sim, Faction::Wizard1);
+    let wp = pos(&sim, west);
+
+    // an enemy that stands still just out of reach of its own blast
+    let enemy = spawn_for(&sim, "Orc", (wp.x, wp.y + 5), Faction::Wizard2);
+    (&mut sim.world_sim.world.borrow::<ViewMut<Energy>>().unwrap()).get(enemy).unwrap().value = -100000;
+
+    let mut cast = false;
+    for _ in 0..10 {
+        sim.step();
+
+        if intent(&sim, west).is_some_and(|i| i.name == "cast spell") {
+            cast = true;
+            break;
+        }
+    }
+    assert!(cast);
+    let m = mana(&sim, west);
+    assert!(m.current < m.max);
+
+    Ok(())
+}
+
+#[test]
+fn war_ends_when_a_wizard_dies() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("trench_war", 1)?;
+    let east = wizard(&sim, Faction::Wizard2);
+
+    sim.step();
+    assert!(!sim.world_sim.world.borrow::<UniqueView<War>>().unwrap().over);
+
+    (&mut sim.world_sim.world.borrow::<ViewMut<PhysicalStats>>().unwrap()).get(east).unwrap().hp = 0;
+    sim.step_n(2);
+
+    let war = sim.world_sim.world.borrow::<UniqueView<War>>().unwrap();
+    assert!(war.over);
+    assert_eq!(war.winner, Some(Faction::Wizard1));
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{actors, intent, remove_actors, spawn};
+use engine::{
+    ai::intent::Task,
+    simulation::Simulation,
+    world::{
+        components::{Actor, ActorType, Energy, PhysicalStats, Position},
+        map::XY,
+        systems::system_ai_wolf::PACK_RANGE,
+    },
+};
+use rltk::DistanceAlg;
+use shipyard::{EntityId, Get, View, ViewMut};
+
+// a villager that stands still so the pack has something to hunt
+fn bait(sim: &mut Simulation, xy: XY) -> EntityId {
+    remove_actors(sim, ActorType::Villager);
+    remove_actors(sim, ActorType::Wolf);
+
+    let villager = spawn(sim, "Villager", xy);
+    (&mut sim.world_sim.world.borrow::<ViewMut<Energy>>().unwrap()).get(villager).unwrap().value = -100000;
+    villager
+}
+
+#[test]
+fn pack_sticks_together() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let wolves = actors(&sim, ActorType::Wolf);
+    assert!(wolves.len() > 1);
+
+    sim.step_n(20);
+
+    let vpos = sim.world_sim.world.borrow::<View<Position>>().unwrap();
+    for a in wolves.iter() {
+        for b in wolves.iter() {
+            let dist = DistanceAlg::Pythagoras.distance2d(vpos.get(*a).unwrap().ps[0], vpos.get(*b).unwrap().ps[0]);
+            assert!(dist <= PACK_RANGE);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn pack_surrounds_before_attacking() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let villager = bait(&mut sim, (150, 45));
+    let wolves: Vec<EntityId> = [(145, 50), (146, 51), (147, 51)].iter().map(|xy| spawn(&sim, "Wolf", *xy)).collect();
+
+    sim.step();
+
+    // too far apart to attack yet
+    for w in wolves.iter() {
+        let i = intent(&sim, *w).unwrap();
+        assert!(!matches!(i.task, Task::Attack(_)));
+        assert_eq!(i.name, "surround");
+    }
+
+    let mut bitten = false;
+    for _ in 0..20 {
+        sim.step();
+
+        let vstats = sim.world_sim.world.borrow::<View<PhysicalStats>>().unwrap();
+        if vstats.get(villager).map_or(true, |s| s.hp < s.max_hp) {
+            bitten = true;
+            break;
+        }
+    }
+    assert!(bitten);
+
+    Ok(())
+}
+
+#[test]
+fn hurt_pack_flees() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    bait(&mut sim, (150, 45));
+    let wolves: Vec<EntityId> = [(148, 47), (149, 48), (147, 48)].iter().map(|xy| spawn(&sim, "Wolf", *xy)).collect();
+
+    {
+        let mut vstats = sim.world_sim.world.borrow::<ViewMut<PhysicalStats>>().unwrap();
+        for w in wolves.iter() {
+            (&mut vstats).get(*w).unwrap().hp = 1;
+        }
+    }
+
+    sim.step();
+
+    for w in wolves.iter() {
+        assert_eq!(intent(&sim, *w).unwrap().name, "flee");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn wolves_snatch_fish_from_the_shore() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Simulation::with_seed("village_sim", 2)?;
+    let (w, h) = sim.get_map().size;
+    remove_actors(&mut sim, ActorType::Villager);
+    remove_actors(&mut sim, ActorType::Wolf);
+    remove_actors(&mut sim, ActorType::Fish);
+
+    let wolf = spawn(&sim, "Wolf", (w / 2, h - 10));
+    let fish = spawn(&sim, "Fish", (w / 2, h - 9));
+
+    sim.step();
+
+    assert_eq!(intent(&sim, wolf).unwrap().task, Task::Fish);
+    assert!(sim.world_sim.world.borrow::<View<Actor>>().unwrap().get(fish).is_err());
+
+    Ok(())
+}